@@ -101,6 +101,16 @@ pub enum ErrorCode {
     AmountCalcOverflow, //0x1797
     #[msg("Amount remaining overflows")]
     AmountRemainingOverflow, //0x1798
+
+    #[msg("Reward vault mint does not match the reward mint")]
+    RewardVaultMintMismatch, //0x1799
+
+    #[msg("Remaining accounts do not match the initialized rewards of the whirlpool")]
+    InvalidRewardAccounts, //0x179a
+    #[msg("Position does not belong to the whirlpool")]
+    PositionWhirlpoolMismatch, //0x179b
+    #[msg("Reward vault is a token vault of the whirlpool or the vault of another reward")]
+    RewardVaultInUse, //0x179c
 }
 
 impl From<TryFromIntError> for ErrorCode {
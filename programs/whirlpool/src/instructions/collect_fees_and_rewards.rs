@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::{
+    errors::ErrorCode,
+    manager::liquidity_manager::calculate_fee_and_reward_growths,
+    state::*,
+    util::{to_timestamp_u64, transfer_from_vault_to_owner, verify_position_authority},
+};
+
+use super::calculate_collect_reward;
+
+#[derive(Accounts)]
+pub struct CollectFeesAndRewards<'info> {
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(has_one = whirlpool)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(has_one = whirlpool)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == whirlpool.token_mint_a)]
+    pub token_owner_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_b.mint == whirlpool.token_mint_b)]
+    pub token_owner_account_b: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    // remaining accounts
+    // - for each initialized reward, in reward index order:
+    //   - reward_owner_account
+    //   - reward_vault
+}
+
+/// Updates the fees and rewards owed to a position and collects all of them.
+///
+/// If the position has liquidity, its fees and rewards are brought up to date first, the same way
+/// `update_fees_and_rewards` does. Rewards are then collected the same way `collect_reward` does:
+/// if a reward vault does not have enough tokens, the unharvested amount remains tracked.
+///
+/// # Returns
+/// - `Ok`: All fees and rewards owed to the position have been collected
+/// - `Err`: `InvalidRewardAccounts` if the remaining accounts do not hold a reward owner account
+///          and the reward vault for each initialized reward, in reward index order
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CollectFeesAndRewards<'info>>,
+) -> ProgramResult {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let initialized_reward_indexes: Vec<usize> = (0..NUM_REWARDS)
+        .filter(|index| ctx.accounts.whirlpool.reward_infos[*index].initialized())
+        .collect();
+    if ctx.remaining_accounts.len() != initialized_reward_indexes.len() * 2 {
+        return Err(ErrorCode::InvalidRewardAccounts.into());
+    }
+
+    // Calculating fees and rewards for positions with zero liquidity will result in an error,
+    // and those positions already have the most updated values.
+    if ctx.accounts.position.liquidity > 0 {
+        let clock = Clock::get()?;
+        let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+        let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+            &ctx.accounts.whirlpool,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            timestamp,
+        )?;
+
+        ctx.accounts
+            .whirlpool
+            .update_rewards(reward_infos, timestamp);
+        ctx.accounts.position.update(&position_update);
+    }
+
+    let position = &mut ctx.accounts.position;
+
+    // Store the fees owed to use as transfer amounts.
+    let fee_owed_a = position.fee_owed_a;
+    let fee_owed_b = position.fee_owed_b;
+
+    position.reset_fees_owed();
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program,
+        fee_owed_a,
+    )?;
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program,
+        fee_owed_b,
+    )?;
+
+    for (index, reward_accounts) in initialized_reward_indexes
+        .into_iter()
+        .zip(ctx.remaining_accounts.chunks(2))
+    {
+        let reward_info = &ctx.accounts.whirlpool.reward_infos[index];
+        let reward_owner_account = Account::<TokenAccount>::try_from(&reward_accounts[0])?;
+        let reward_vault = Account::<TokenAccount>::try_from(&reward_accounts[1])?;
+        if reward_owner_account.mint != reward_info.mint || reward_vault.key() != reward_info.vault
+        {
+            return Err(ErrorCode::InvalidRewardAccounts.into());
+        }
+
+        let position = &mut ctx.accounts.position;
+        let (transfer_amount, updated_amount_owed) =
+            calculate_collect_reward(position.reward_infos[index], reward_vault.amount);
+
+        position.update_reward_owed(index, updated_amount_owed);
+
+        transfer_from_vault_to_owner(
+            &ctx.accounts.whirlpool,
+            &reward_vault,
+            &reward_owner_account,
+            &ctx.accounts.token_program,
+            transfer_amount,
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 pub mod close_position;
 pub mod collect_fees;
+pub mod collect_fees_and_rewards;
 pub mod collect_protocol_fees;
 pub mod collect_reward;
 pub mod decrease_liquidity;
@@ -21,11 +22,14 @@ pub mod set_reward_authority;
 pub mod set_reward_authority_by_super_authority;
 pub mod set_reward_emissions;
 pub mod set_reward_emissions_super_authority;
+pub mod set_reward_vault_by_super_authority;
 pub mod swap;
 pub mod update_fees_and_rewards;
+pub mod update_fees_and_rewards_batch;
 
 pub use close_position::*;
 pub use collect_fees::*;
+pub use collect_fees_and_rewards::*;
 pub use collect_protocol_fees::*;
 pub use collect_reward::*;
 pub use decrease_liquidity::*;
@@ -47,5 +51,7 @@ pub use set_reward_authority::*;
 pub use set_reward_authority_by_super_authority::*;
 pub use set_reward_emissions::*;
 pub use set_reward_emissions_super_authority::*;
+pub use set_reward_vault_by_super_authority::*;
 pub use swap::*;
 pub use update_fees_and_rewards::*;
+pub use update_fees_and_rewards_batch::*;
@@ -2,13 +2,10 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 
 use crate::errors::ErrorCode;
-use crate::manager::whirlpool_manager::next_whirlpool_reward_infos;
-use crate::math::checked_mul_shift_right;
+use crate::manager::whirlpool_manager::{emissions_per_day, next_whirlpool_reward_infos};
 use crate::state::Whirlpool;
 use crate::util::to_timestamp_u64;
 
-const DAY_IN_SECONDS: u128 = 60 * 60 * 24;
-
 #[derive(Accounts)]
 #[instruction(reward_index: u8)]
 pub struct SetRewardEmissions<'info> {
@@ -30,8 +27,7 @@ pub fn handler(
     let whirlpool = &ctx.accounts.whirlpool;
     let reward_vault = &ctx.accounts.reward_vault;
 
-    let emissions_per_day = checked_mul_shift_right(DAY_IN_SECONDS, emissions_per_second_x64)?;
-    if reward_vault.amount < emissions_per_day {
+    if reward_vault.amount < emissions_per_day(emissions_per_second_x64)? {
         return Err(ErrorCode::RewardVaultAmountInsufficient.into());
     }
 
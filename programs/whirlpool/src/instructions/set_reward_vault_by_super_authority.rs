@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::{
+    state::{Whirlpool, WhirlpoolsConfig},
+    util::transfer_from_vault_to_owner,
+};
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetRewardVaultBySuperAuthority<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = whirlpools_config.reward_emissions_super_authority)]
+    pub reward_emissions_super_authority: Signer<'info>,
+
+    #[account(mut, address = whirlpool.reward_infos[reward_index as usize].vault)]
+    pub old_reward_vault: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = new_reward_vault.owner == whirlpool.key())]
+    pub new_reward_vault: Account<'info, TokenAccount>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+/// Set the whirlpool reward vault at the provided `reward_index`.
+/// The balance of the old reward vault is moved to the new reward vault, so no reward tokens are
+/// left in a vault the whirlpool no longer transfers from.
+/// Only the current reward emissions super authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<SetRewardVaultBySuperAuthority>, reward_index: u8) -> ProgramResult {
+    let old_reward_vault = &ctx.accounts.old_reward_vault;
+    let new_reward_vault = &ctx.accounts.new_reward_vault;
+
+    ctx.accounts.whirlpool.update_reward_vault(
+        reward_index as usize,
+        new_reward_vault.mint,
+        new_reward_vault.key(),
+    )?;
+
+    if old_reward_vault.amount == 0 {
+        return Ok(());
+    }
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.whirlpool,
+        old_reward_vault,
+        new_reward_vault,
+        &ctx.accounts.token_program,
+        old_reward_vault.amount,
+    )
+}
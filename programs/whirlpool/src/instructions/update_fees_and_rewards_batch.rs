@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode, manager::liquidity_manager::calculate_fee_and_reward_growths, state::*,
+    util::to_timestamp_u64,
+};
+
+#[derive(Accounts)]
+pub struct UpdateFeesAndRewardsBatch<'info> {
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+    // remaining accounts
+    // - tick arrays hosting the lower and upper ticks of the positions (num_tick_arrays accounts)
+    // - positions to update
+}
+
+/// Updates the fees and rewards owed to every position passed in the remaining accounts.
+///
+/// The positions are updated the same way `update_fees_and_rewards` does, with the tick arrays
+/// looked up among the provided ones. Positions with zero liquidity already have the most updated
+/// fees and reward values and are skipped.
+///
+/// # Parameters
+/// - `num_tick_arrays` - The number of tick arrays preceding the positions in the remaining accounts.
+///
+/// # Returns
+/// - `Ok`: The fees and rewards owed to every position have been updated
+/// - `Err`: `InvalidTickArraySequence` if there are fewer remaining accounts than `num_tick_arrays`
+///          `PositionWhirlpoolMismatch` if a position does not belong to the whirlpool
+///          `TickNotFound` if no provided tick array of the whirlpool hosts a position's tick
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, UpdateFeesAndRewardsBatch<'info>>,
+    num_tick_arrays: u8,
+) -> ProgramResult {
+    let whirlpool = &mut ctx.accounts.whirlpool;
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let num_tick_arrays = num_tick_arrays as usize;
+    if ctx.remaining_accounts.len() < num_tick_arrays {
+        return Err(ErrorCode::InvalidTickArraySequence.into());
+    }
+    let (tick_array_infos, position_infos) = ctx.remaining_accounts.split_at(num_tick_arrays);
+
+    let mut tick_arrays: Vec<AccountLoader<'info, TickArray>> = vec![];
+    for tick_array_info in tick_array_infos {
+        let tick_array = AccountLoader::<TickArray>::try_from(tick_array_info)?;
+        // Tick arrays of other whirlpools can never host the ticks of the positions.
+        if tick_array.load()?.whirlpool == whirlpool.key() {
+            tick_arrays.push(tick_array);
+        }
+    }
+
+    for position_info in position_infos {
+        let mut position = Account::<Position>::try_from(position_info)?;
+        if position.whirlpool != whirlpool.key() {
+            return Err(ErrorCode::PositionWhirlpoolMismatch.into());
+        }
+        if position.liquidity == 0 {
+            continue;
+        }
+
+        let tick_array_lower = find_tick_array(
+            &tick_arrays,
+            position.tick_lower_index,
+            whirlpool.tick_spacing,
+        )?;
+        let tick_array_upper = find_tick_array(
+            &tick_arrays,
+            position.tick_upper_index,
+            whirlpool.tick_spacing,
+        )?;
+
+        let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+            whirlpool,
+            &position,
+            tick_array_lower,
+            tick_array_upper,
+            timestamp,
+        )?;
+
+        whirlpool.update_rewards(reward_infos, timestamp);
+        position.update(&position_update);
+        position.exit(ctx.program_id)?;
+    }
+
+    Ok(())
+}
+
+fn find_tick_array<'a, 'info>(
+    tick_arrays: &'a [AccountLoader<'info, TickArray>],
+    tick_index: i32,
+    tick_spacing: u16,
+) -> Result<&'a AccountLoader<'info, TickArray>, ProgramError> {
+    for tick_array in tick_arrays {
+        if tick_array
+            .load()?
+            .check_in_array_bounds(tick_index, tick_spacing)
+        {
+            return Ok(tick_array);
+        }
+    }
+    Err(ErrorCode::TickNotFound.into())
+}
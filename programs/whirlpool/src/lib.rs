@@ -271,6 +271,25 @@ pub mod whirlpool {
         return instructions::update_fees_and_rewards::handler(ctx);
     }
 
+    /// Update the accrued fees and rewards for a batch of positions of this Whirlpool.
+    ///
+    /// The tick arrays hosting the positions' ticks are passed as remaining accounts, followed by
+    /// the positions. Positions with zero liquidity are skipped.
+    ///
+    /// #### Parameters
+    /// - `num_tick_arrays` - The number of tick arrays preceding the positions in the remaining accounts.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTickArraySequence` - There are fewer remaining accounts than `num_tick_arrays`.
+    /// - `TickNotFound` - No provided tick array of this Whirlpool contains the tick of a position.
+    /// - `PositionWhirlpoolMismatch` - A position does not belong to this Whirlpool.
+    pub fn update_fees_and_rewards_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateFeesAndRewardsBatch<'info>>,
+        num_tick_arrays: u8,
+    ) -> ProgramResult {
+        return instructions::update_fees_and_rewards_batch::handler(ctx, num_tick_arrays);
+    }
+
     /// Collect fees accrued for this position.
     ///
     /// ### Authority
@@ -287,6 +306,23 @@ pub mod whirlpool {
         return instructions::collect_reward::handler(ctx, reward_index);
     }
 
+    /// Update and collect all fees and rewards accrued for this position in a single instruction.
+    ///
+    /// The reward owner account and reward vault of each initialized reward are passed as
+    /// remaining accounts, in reward index order.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// #### Special Errors
+    /// - `TickNotFound` - Provided tick array account does not contain the tick for this position.
+    /// - `InvalidRewardAccounts` - The remaining accounts do not match the initialized rewards of this Whirlpool.
+    pub fn collect_fees_and_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectFeesAndRewards<'info>>,
+    ) -> ProgramResult {
+        return instructions::collect_fees_and_rewards::handler(ctx);
+    }
+
     /// Collect the protocol fees accrued in this Whirlpool
     ///
     /// ### Authority
@@ -488,4 +524,25 @@ pub mod whirlpool {
     ) -> ProgramResult {
         return instructions::set_reward_emissions_super_authority::handler(ctx);
     }
+
+    /// Set the whirlpool reward vault at the provided `reward_index`, to re-point a misconfigured vault.
+    /// The balance of the old reward vault is moved to the new reward vault.
+    /// Only the current reward super authority has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "reward_emissions_super_authority" - Set authority that can control reward authorities for all pools in this config space.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index of the vault to update. (0 <= index <= NUM_REWARDS)
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardIndex` - If the provided reward index exceeds NUM_REWARDS or the reward is not initialized.
+    /// - `RewardVaultMintMismatch` - If the new reward vault does not hold the reward mint.
+    /// - `RewardVaultInUse` - If the new reward vault is a token vault of the whirlpool or the vault of another reward.
+    pub fn set_reward_vault_by_super_authority(
+        ctx: Context<SetRewardVaultBySuperAuthority>,
+        reward_index: u8,
+    ) -> ProgramResult {
+        return instructions::set_reward_vault_by_super_authority::handler(ctx, reward_index);
+    }
 }
@@ -7,7 +7,11 @@ use super::{
 };
 use crate::{
     errors::ErrorCode,
-    math::{get_amount_delta_a, get_amount_delta_b, sqrt_price_from_tick_index},
+    math::{
+        convert_to_liquidity_delta, get_amount_delta_a, get_amount_delta_b,
+        get_max_amount_with_slippage, get_min_amount_with_slippage, sqrt_price_from_tick_index,
+        U256,
+    },
     state::*,
 };
 use anchor_lang::prelude::{AccountLoader, ProgramError};
@@ -199,6 +203,402 @@ pub fn calculate_liquidity_token_deltas(
     Ok((delta_a, delta_b))
 }
 
+#[derive(Debug, PartialEq)]
+pub struct LiquidityDepositRounding {
+    pub token_a: u64,
+    pub token_b: u64,
+    pub token_a_surplus: u64,
+    pub token_b_surplus: u64,
+}
+
+// Calculates the token amounts required to add liquidity to the given position, and the surplus
+// paid over the ideal continuous amounts because deposits are rounded up.
+// The ideal amounts are bounded below by the rounded down amounts, so each surplus is at most 1.
+pub fn calculate_liquidity_deposit_rounding(
+    current_tick_index: i32,
+    sqrt_price: u128,
+    position: &Position,
+    liquidity: u128,
+) -> Result<LiquidityDepositRounding, ErrorCode> {
+    let liquidity_delta = convert_to_liquidity_delta(liquidity, true)?;
+    let (token_a, token_b) = calculate_liquidity_token_deltas(
+        current_tick_index,
+        sqrt_price,
+        position,
+        liquidity_delta,
+    )?;
+    let (ideal_token_a, ideal_token_b) = calculate_liquidity_token_deltas(
+        current_tick_index,
+        sqrt_price,
+        position,
+        -liquidity_delta,
+    )?;
+
+    Ok(LiquidityDepositRounding {
+        token_a,
+        token_b,
+        token_a_surplus: token_a - ideal_token_a,
+        token_b_surplus: token_b - ideal_token_b,
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PartialWithdrawal {
+    pub liquidity_amount: u128,
+    pub token_est_a: u64,
+    pub token_est_b: u64,
+    pub token_min_a: u64,
+    pub token_min_b: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PartialWithdrawalError {
+    // The fraction to withdraw is above 10_000 basis points
+    InvalidFraction,
+    // The liquidity math failed
+    Liquidity(ErrorCode),
+}
+
+impl From<ErrorCode> for PartialWithdrawalError {
+    fn from(error: ErrorCode) -> Self {
+        PartialWithdrawalError::Liquidity(error)
+    }
+}
+
+// Calculates the liquidity to remove to withdraw `fraction_bps` basis points of the given position,
+// the token amounts the withdrawal returns at the current price, and the minimum amounts to accept
+// given `slippage_bps`, adjusted with `get_min_amount_with_slippage`.
+pub fn calculate_partial_withdrawal(
+    current_tick_index: i32,
+    sqrt_price: u128,
+    position: &Position,
+    fraction_bps: u16,
+    slippage_bps: u16,
+) -> Result<PartialWithdrawal, PartialWithdrawalError> {
+    if fraction_bps > 10_000 {
+        return Err(PartialWithdrawalError::InvalidFraction);
+    }
+
+    let liquidity_amount =
+        (U256::from(position.liquidity) * U256::from(fraction_bps) / U256::from(10_000)).as_u128();
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
+    let (token_est_a, token_est_b) = calculate_liquidity_token_deltas(
+        current_tick_index,
+        sqrt_price,
+        position,
+        liquidity_delta,
+    )?;
+
+    Ok(PartialWithdrawal {
+        liquidity_amount,
+        token_est_a,
+        token_est_b,
+        token_min_a: get_min_amount_with_slippage(token_est_a, slippage_bps),
+        token_min_b: get_min_amount_with_slippage(token_est_b, slippage_bps),
+    })
+}
+
+// Calculates the minimum liquidity for which withdrawing the whole position yields at least one
+// unit of every token the position holds at the current price. Below this liquidity, the rounded
+// down withdrawal amount of at least one token is zero and the position is dust.
+pub fn calculate_min_withdrawable_liquidity(
+    current_tick_index: i32,
+    sqrt_price: u128,
+    position: &Position,
+) -> Result<u128, ErrorCode> {
+    let lower_price = sqrt_price_from_tick_index(position.tick_lower_index);
+    let upper_price = sqrt_price_from_tick_index(position.tick_upper_index);
+
+    if current_tick_index < position.tick_lower_index {
+        // current tick below position
+        min_liquidity_for_amount_a(lower_price, upper_price)
+    } else if current_tick_index < position.tick_upper_index {
+        // current tick inside position, a token is only held if the price is strictly inside
+        let min_liquidity_a = min_liquidity_for_amount_a(sqrt_price, upper_price)?;
+        let min_liquidity_b = min_liquidity_for_amount_b(lower_price, sqrt_price)?;
+        Ok(min_liquidity_a.max(min_liquidity_b))
+    } else {
+        // current tick above position
+        min_liquidity_for_amount_b(lower_price, upper_price)
+    }
+}
+
+// Smallest liquidity with get_amount_delta_a(sqrt_price_lower, sqrt_price_upper, liquidity, false) >= 1
+// liquidity * (sqrt_price_upper - sqrt_price_lower) * 2^64 >= sqrt_price_upper * sqrt_price_lower
+fn min_liquidity_for_amount_a(
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+) -> Result<u128, ErrorCode> {
+    if sqrt_price_upper <= sqrt_price_lower {
+        return Ok(0);
+    }
+    let numerator = U256::from(sqrt_price_upper) * U256::from(sqrt_price_lower);
+    let denominator = U256::from(sqrt_price_upper - sqrt_price_lower) << 64;
+    let (quotient, remainder) = numerator.div_mod(denominator);
+    let min_liquidity = if remainder.is_zero() {
+        quotient
+    } else {
+        quotient + U256::one()
+    };
+    min_liquidity
+        .try_into_u128()
+        .map_err(|_| ErrorCode::LiquidityOverflow)
+}
+
+// Smallest liquidity with get_amount_delta_b(sqrt_price_lower, sqrt_price_upper, liquidity, false) >= 1
+// liquidity * (sqrt_price_upper - sqrt_price_lower) >= 2^64
+fn min_liquidity_for_amount_b(
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+) -> Result<u128, ErrorCode> {
+    if sqrt_price_upper <= sqrt_price_lower {
+        return Ok(0);
+    }
+    let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
+    let quotient = (1u128 << 64) / sqrt_price_diff;
+    let remainder = (1u128 << 64) % sqrt_price_diff;
+    Ok(if remainder > 0 {
+        quotient + 1
+    } else {
+        quotient
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SingleSidedDeposit {
+    pub liquidity: u128,
+    pub token_a: u64,
+    pub token_b: u64,
+    pub unused_amount: u64,
+}
+
+// Calculates the liquidity that can be added to the given position from `amount` of a single token,
+// and the token amounts that deposit requires. When the price is inside the position, the deposit
+// also requires the other token. When the position only holds the other token at the current price,
+// the input token cannot be deposited at all and is returned in full as `unused_amount`.
+// The liquidity is rounded down so the rounded up deposit never exceeds `amount`.
+pub fn calculate_single_sided_deposit(
+    current_tick_index: i32,
+    sqrt_price: u128,
+    position: &Position,
+    amount: u64,
+    is_token_a: bool,
+) -> Result<SingleSidedDeposit, ErrorCode> {
+    let lower_price = sqrt_price_from_tick_index(position.tick_lower_index);
+    let upper_price = sqrt_price_from_tick_index(position.tick_upper_index);
+
+    let liquidity = if current_tick_index < position.tick_lower_index {
+        // current tick below position, only token a is deposited
+        if is_token_a {
+            liquidity_from_amount_a(amount, lower_price, upper_price)?
+        } else {
+            0
+        }
+    } else if current_tick_index < position.tick_upper_index {
+        // current tick inside position
+        if is_token_a {
+            liquidity_from_amount_a(amount, sqrt_price, upper_price)?
+        } else {
+            liquidity_from_amount_b(amount, lower_price, sqrt_price)?
+        }
+    } else {
+        // current tick above position, only token b is deposited
+        if is_token_a {
+            0
+        } else {
+            liquidity_from_amount_b(amount, lower_price, upper_price)?
+        }
+    };
+
+    if liquidity == 0 {
+        return Ok(SingleSidedDeposit {
+            liquidity: 0,
+            token_a: 0,
+            token_b: 0,
+            unused_amount: amount,
+        });
+    }
+
+    let (token_a, token_b) = calculate_liquidity_token_deltas(
+        current_tick_index,
+        sqrt_price,
+        position,
+        convert_to_liquidity_delta(liquidity, true)?,
+    )?;
+    let used_amount = if is_token_a { token_a } else { token_b };
+
+    Ok(SingleSidedDeposit {
+        liquidity,
+        token_a,
+        token_b,
+        unused_amount: amount - used_amount,
+    })
+}
+
+// Largest liquidity with get_amount_delta_a(sqrt_price_lower, sqrt_price_upper, liquidity, true) <= amount
+// liquidity = amount * sqrt_price_upper * sqrt_price_lower / ((sqrt_price_upper - sqrt_price_lower) * 2^64)
+fn liquidity_from_amount_a(
+    amount: u64,
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+) -> Result<u128, ErrorCode> {
+    if sqrt_price_upper <= sqrt_price_lower {
+        return Ok(0);
+    }
+    let numerator = U256::from(amount)
+        .checked_mul(U256::from(sqrt_price_upper) * U256::from(sqrt_price_lower))
+        .ok_or(ErrorCode::MultiplicationOverflow)?;
+    let denominator = U256::from(sqrt_price_upper - sqrt_price_lower) << 64;
+    (numerator / denominator)
+        .try_into_u128()
+        .map_err(|_| ErrorCode::LiquidityOverflow)
+}
+
+// Largest liquidity with get_amount_delta_b(sqrt_price_lower, sqrt_price_upper, liquidity, true) <= amount
+// liquidity = amount * 2^64 / (sqrt_price_upper - sqrt_price_lower)
+fn liquidity_from_amount_b(
+    amount: u64,
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+) -> Result<u128, ErrorCode> {
+    if sqrt_price_upper <= sqrt_price_lower {
+        return Ok(0);
+    }
+    Ok(((amount as u128) << 64) / (sqrt_price_upper - sqrt_price_lower))
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RepositionQuote {
+    pub token_withdrawn_a: u64,
+    pub token_withdrawn_b: u64,
+    pub token_min_withdrawn_a: u64,
+    pub token_min_withdrawn_b: u64,
+    pub liquidity: u128,
+    pub token_deposit_a: u64,
+    pub token_deposit_b: u64,
+    pub token_max_deposit_a: u64,
+    pub token_max_deposit_b: u64,
+    pub token_delta_a: i128,
+    pub token_delta_b: i128,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RepositionQuoteError {
+    // The new tick range is the same as the current tick range of the position
+    SameTickRangeNotAllowed,
+    // The new tick range is invalid or the liquidity math failed
+    Liquidity(ErrorCode),
+}
+
+impl From<ErrorCode> for RepositionQuoteError {
+    fn from(error: ErrorCode) -> Self {
+        RepositionQuoteError::Liquidity(error)
+    }
+}
+
+// Calculates the result of moving all liquidity of a position to a new tick range at the current
+// price: the tokens released by withdrawing the position, the largest liquidity the new range can
+// take from those tokens plus up to `top_up_a` / `top_up_b` tokens from the wallet, and the token
+// deltas for the wallet (positive when the wallet pays). Fees and rewards owed are not included.
+// Slippage adjusts the amounts with `get_min_amount_with_slippage` & `get_max_amount_with_slippage`.
+pub fn calculate_reposition_quote(
+    whirlpool: &Whirlpool,
+    position: &Position,
+    new_tick_lower_index: i32,
+    new_tick_upper_index: i32,
+    top_up_a: u64,
+    top_up_b: u64,
+    slippage_bps: u16,
+) -> Result<RepositionQuote, RepositionQuoteError> {
+    if new_tick_lower_index == position.tick_lower_index
+        && new_tick_upper_index == position.tick_upper_index
+    {
+        return Err(RepositionQuoteError::SameTickRangeNotAllowed);
+    }
+    if !Tick::check_is_usable_tick(new_tick_lower_index, whirlpool.tick_spacing)
+        || !Tick::check_is_usable_tick(new_tick_upper_index, whirlpool.tick_spacing)
+        || new_tick_lower_index >= new_tick_upper_index
+    {
+        return Err(ErrorCode::InvalidTickIndex.into());
+    }
+
+    let (token_withdrawn_a, token_withdrawn_b) = if position.liquidity == 0 {
+        (0, 0)
+    } else {
+        calculate_liquidity_token_deltas(
+            whirlpool.tick_current_index,
+            whirlpool.sqrt_price,
+            position,
+            -convert_to_liquidity_delta(position.liquidity, false)?,
+        )?
+    };
+
+    let available_a = token_withdrawn_a
+        .checked_add(top_up_a)
+        .ok_or(ErrorCode::TokenMaxExceeded)?;
+    let available_b = token_withdrawn_b
+        .checked_add(top_up_b)
+        .ok_or(ErrorCode::TokenMaxExceeded)?;
+
+    let new_position = Position {
+        tick_lower_index: new_tick_lower_index,
+        tick_upper_index: new_tick_upper_index,
+        ..Default::default()
+    };
+    let lower_price = sqrt_price_from_tick_index(new_tick_lower_index);
+    let upper_price = sqrt_price_from_tick_index(new_tick_upper_index);
+    let sqrt_price = whirlpool.sqrt_price;
+
+    // Each token bounds the liquidity only if the new range holds it at the current price.
+    let liquidity = if whirlpool.tick_current_index < new_tick_lower_index {
+        liquidity_from_amount_a(available_a, lower_price, upper_price)?
+    } else if whirlpool.tick_current_index < new_tick_upper_index {
+        let mut liquidity = u128::MAX;
+        if sqrt_price < upper_price {
+            liquidity = liquidity.min(liquidity_from_amount_a(
+                available_a,
+                sqrt_price,
+                upper_price,
+            )?);
+        }
+        if sqrt_price > lower_price {
+            liquidity = liquidity.min(liquidity_from_amount_b(
+                available_b,
+                lower_price,
+                sqrt_price,
+            )?);
+        }
+        liquidity
+    } else {
+        liquidity_from_amount_b(available_b, lower_price, upper_price)?
+    };
+
+    if liquidity == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+
+    let (token_deposit_a, token_deposit_b) = calculate_liquidity_token_deltas(
+        whirlpool.tick_current_index,
+        sqrt_price,
+        &new_position,
+        convert_to_liquidity_delta(liquidity, true)?,
+    )?;
+
+    Ok(RepositionQuote {
+        token_withdrawn_a,
+        token_withdrawn_b,
+        token_min_withdrawn_a: get_min_amount_with_slippage(token_withdrawn_a, slippage_bps),
+        token_min_withdrawn_b: get_min_amount_with_slippage(token_withdrawn_b, slippage_bps),
+        liquidity,
+        token_deposit_a,
+        token_deposit_b,
+        token_max_deposit_a: get_max_amount_with_slippage(token_deposit_a, slippage_bps)?,
+        token_max_deposit_b: get_max_amount_with_slippage(token_deposit_b, slippage_bps)?,
+        token_delta_a: token_deposit_a as i128 - token_withdrawn_a as i128,
+        token_delta_b: token_deposit_b as i128 - token_withdrawn_b as i128,
+    })
+}
+
 pub fn sync_modify_liquidity_values<'info>(
     whirlpool: &mut Whirlpool,
     position: &mut Position,
@@ -3599,3 +3999,441 @@ mod calculate_modify_liquidity_unit_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod calculate_liquidity_deposit_rounding_tests {
+    use super::*;
+    use crate::math::TO_Q64;
+    use crate::state::position_builder::PositionBuilder;
+
+    #[test]
+    fn test_rounding_surplus_in_range() {
+        let position = PositionBuilder::new(-128, 128).build();
+        let rounding =
+            calculate_liquidity_deposit_rounding(0, TO_Q64, &position, 1_000_003).unwrap();
+        assert_eq!(
+            rounding,
+            LiquidityDepositRounding {
+                token_a: 6380,
+                token_b: 6380,
+                token_a_surplus: 1,
+                token_b_surplus: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_no_rounding_surplus_on_exact_amounts() {
+        // With liquidity of 2^64, the token b amount is exactly the difference in sqrt-price
+        let position = PositionBuilder::new(-128, 128).build();
+        let rounding = calculate_liquidity_deposit_rounding(
+            256,
+            sqrt_price_from_tick_index(256),
+            &position,
+            TO_Q64,
+        )
+        .unwrap();
+        assert_eq!(
+            rounding.token_b as u128,
+            sqrt_price_from_tick_index(128) - sqrt_price_from_tick_index(-128)
+        );
+        assert_eq!(rounding.token_a, 0);
+        assert_eq!(rounding.token_a_surplus, 0);
+        assert_eq!(rounding.token_b_surplus, 0);
+    }
+
+    #[test]
+    fn test_rounding_surplus_is_at_most_one() {
+        let position = PositionBuilder::new(-1280, 1280).build();
+        for liquidity in [1, 7, 1_000, 123_456_789, u64::MAX as u128] {
+            let rounding = calculate_liquidity_deposit_rounding(
+                100,
+                sqrt_price_from_tick_index(100),
+                &position,
+                liquidity,
+            )
+            .unwrap();
+            assert!(rounding.token_a_surplus <= 1);
+            assert!(rounding.token_b_surplus <= 1);
+        }
+    }
+
+    #[test]
+    fn test_liquidity_zero() {
+        let position = PositionBuilder::new(-128, 128).build();
+        assert_eq!(
+            calculate_liquidity_deposit_rounding(0, TO_Q64, &position, 0).unwrap_err(),
+            ErrorCode::LiquidityZero
+        );
+    }
+}
+
+#[cfg(test)]
+mod calculate_min_withdrawable_liquidity_tests {
+    use super::*;
+    use crate::math::TO_Q64;
+    use crate::state::position_builder::PositionBuilder;
+
+    // Asserts that withdrawing the threshold liquidity yields every held token and that
+    // withdrawing one less yields zero of at least one held token.
+    fn assert_dust_threshold(current_tick_index: i32, sqrt_price: u128, position: &Position) {
+        let min_liquidity =
+            calculate_min_withdrawable_liquidity(current_tick_index, sqrt_price, position).unwrap();
+        let withdraw = |liquidity: u128| {
+            calculate_liquidity_token_deltas(
+                current_tick_index,
+                sqrt_price,
+                position,
+                -(liquidity as i128),
+            )
+            .unwrap()
+        };
+        let lower_price = sqrt_price_from_tick_index(position.tick_lower_index);
+        let upper_price = sqrt_price_from_tick_index(position.tick_upper_index);
+        let holds_a = current_tick_index < position.tick_upper_index && sqrt_price < upper_price;
+        let holds_b = current_tick_index >= position.tick_lower_index && sqrt_price > lower_price;
+
+        let (token_a, token_b) = withdraw(min_liquidity);
+        assert!(!holds_a || token_a >= 1);
+        assert!(!holds_b || token_b >= 1);
+
+        let (token_a, token_b) = withdraw(min_liquidity - 1);
+        assert!((holds_a && token_a == 0) || (holds_b && token_b == 0));
+    }
+
+    #[test]
+    fn test_in_range() {
+        let position = PositionBuilder::new(-128, 128).build();
+        assert_eq!(
+            calculate_min_withdrawable_liquidity(0, TO_Q64, &position).unwrap(),
+            157
+        );
+        assert_dust_threshold(0, TO_Q64, &position);
+    }
+
+    #[test]
+    fn test_several_ranges() {
+        for (tick_lower_index, tick_upper_index, current_tick_index) in [
+            (-128, 128, 64),
+            (-1280, 1280, -1000),
+            (0, 64, 32),
+            (-443584, 443584, 0),
+            (100_000, 200_000, 150_000),
+            (-200_000, -100_000, -150_000),
+        ] {
+            let position = PositionBuilder::new(tick_lower_index, tick_upper_index).build();
+            assert_dust_threshold(
+                current_tick_index,
+                sqrt_price_from_tick_index(current_tick_index),
+                &position,
+            );
+        }
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        // Below the range, the position only holds token A
+        let position = PositionBuilder::new(1280, 2560).build();
+        assert_dust_threshold(0, TO_Q64, &position);
+
+        // Above the range, the position only holds token B
+        let position = PositionBuilder::new(-2560, -1280).build();
+        assert_dust_threshold(0, TO_Q64, &position);
+    }
+
+    #[test]
+    fn test_narrow_range_needs_more_liquidity() {
+        let narrow = PositionBuilder::new(-64, 64).build();
+        let wide = PositionBuilder::new(-6400, 6400).build();
+        assert!(
+            calculate_min_withdrawable_liquidity(0, TO_Q64, &narrow).unwrap()
+                > calculate_min_withdrawable_liquidity(0, TO_Q64, &wide).unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod calculate_partial_withdrawal_tests {
+    use super::*;
+    use crate::math::TO_Q64;
+    use crate::state::position_builder::PositionBuilder;
+
+    fn withdraw(fraction_bps: u16) -> PartialWithdrawal {
+        let position = PositionBuilder::new(-1280, 1280)
+            .liquidity(1_000_000_000)
+            .build();
+        calculate_partial_withdrawal(0, TO_Q64, &position, fraction_bps, 100).unwrap()
+    }
+
+    #[test]
+    fn test_quarter_withdrawal() {
+        let withdrawal = withdraw(2_500);
+        assert_eq!(withdrawal.liquidity_amount, 250_000_000);
+        // 250_000_000 * (1 - 1.0001^-640) ~= 15_497_999.76, rounded down
+        assert_eq!(withdrawal.token_est_a, 15_497_999);
+        assert_eq!(withdrawal.token_est_b, 15_497_999);
+        // 15_497_999 / 1.01
+        assert_eq!(withdrawal.token_min_a, 15_344_553);
+        assert_eq!(withdrawal.token_min_b, 15_344_553);
+    }
+
+    #[test]
+    fn test_half_withdrawal() {
+        let half = withdraw(5_000);
+        let quarter = withdraw(2_500);
+        assert_eq!(half.liquidity_amount, 500_000_000);
+        assert!(half.token_est_a >= 2 * quarter.token_est_a);
+        assert!(half.token_est_a <= 2 * quarter.token_est_a + 1);
+        assert!(half.token_min_a < half.token_est_a);
+    }
+
+    #[test]
+    fn test_full_withdrawal() {
+        let position = PositionBuilder::new(-1280, 1280)
+            .liquidity(1_000_000_000)
+            .build();
+        let withdrawal = withdraw(10_000);
+        assert_eq!(withdrawal.liquidity_amount, position.liquidity);
+        assert_eq!(
+            (withdrawal.token_est_a, withdrawal.token_est_b),
+            calculate_liquidity_token_deltas(0, TO_Q64, &position, -1_000_000_000).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_withdrawal() {
+        let position = PositionBuilder::new(1280, 2560)
+            .liquidity(1_000_000_000)
+            .build();
+        let withdrawal = calculate_partial_withdrawal(0, TO_Q64, &position, 5_000, 0).unwrap();
+        assert!(withdrawal.token_est_a > 0);
+        assert_eq!(withdrawal.token_est_b, 0);
+        assert_eq!(withdrawal.token_min_a, withdrawal.token_est_a);
+        assert_eq!(withdrawal.token_min_b, 0);
+    }
+
+    #[test]
+    fn test_fraction_above_position() {
+        let position = PositionBuilder::new(-1280, 1280)
+            .liquidity(1_000_000_000)
+            .build();
+        assert_eq!(
+            calculate_partial_withdrawal(0, TO_Q64, &position, 10_001, 0).unwrap_err(),
+            PartialWithdrawalError::InvalidFraction
+        );
+    }
+}
+
+#[cfg(test)]
+mod calculate_single_sided_deposit_tests {
+    use super::*;
+    use crate::math::TO_Q64;
+    use crate::state::position_builder::PositionBuilder;
+
+    #[test]
+    fn test_token_a_below_range() {
+        let position = PositionBuilder::new(1280, 2560).build();
+        let deposit =
+            calculate_single_sided_deposit(0, TO_Q64, &position, 1_000_000, true).unwrap();
+        assert!(deposit.liquidity > 0);
+        assert_eq!(deposit.token_b, 0);
+        assert_eq!(deposit.token_a + deposit.unused_amount, 1_000_000);
+        // Rounding down the liquidity leaves at most one unit of dust
+        assert!(deposit.unused_amount <= 1);
+    }
+
+    #[test]
+    fn test_token_b_above_range() {
+        let position = PositionBuilder::new(-2560, -1280).build();
+        let deposit =
+            calculate_single_sided_deposit(0, TO_Q64, &position, 1_000_000, false).unwrap();
+        assert!(deposit.liquidity > 0);
+        assert_eq!(deposit.token_a, 0);
+        assert_eq!(deposit.token_b + deposit.unused_amount, 1_000_000);
+        assert!(deposit.unused_amount <= 1);
+    }
+
+    #[test]
+    fn test_in_range_requires_both_tokens() {
+        let position = PositionBuilder::new(-1280, 1280).build();
+        for is_token_a in [true, false] {
+            let deposit =
+                calculate_single_sided_deposit(0, TO_Q64, &position, 1_000_000, is_token_a)
+                    .unwrap();
+            assert!(deposit.liquidity > 0);
+            assert!(deposit.token_a > 0);
+            assert!(deposit.token_b > 0);
+            let used_amount = if is_token_a {
+                deposit.token_a
+            } else {
+                deposit.token_b
+            };
+            assert!(used_amount <= 1_000_000);
+            assert_eq!(used_amount + deposit.unused_amount, 1_000_000);
+        }
+    }
+
+    #[test]
+    fn test_in_range_split_matches_price() {
+        // At price 1 in a range symmetric around it, both tokens are deposited in equal amounts
+        let position = PositionBuilder::new(-1280, 1280).build();
+        let deposit =
+            calculate_single_sided_deposit(0, TO_Q64, &position, 1_000_000, true).unwrap();
+        assert!(deposit.token_b >= deposit.token_a - 1);
+        assert!(deposit.token_b <= deposit.token_a + 1);
+    }
+
+    #[test]
+    fn test_unusable_token() {
+        // Above the range, the position only takes token b
+        let position = PositionBuilder::new(-2560, -1280).build();
+        assert_eq!(
+            calculate_single_sided_deposit(0, TO_Q64, &position, 1_000_000, true).unwrap(),
+            SingleSidedDeposit {
+                liquidity: 0,
+                token_a: 0,
+                token_b: 0,
+                unused_amount: 1_000_000,
+            }
+        );
+
+        // Below the range, the position only takes token a
+        let position = PositionBuilder::new(1280, 2560).build();
+        assert_eq!(
+            calculate_single_sided_deposit(0, TO_Q64, &position, 1_000_000, false)
+                .unwrap()
+                .unused_amount,
+            1_000_000
+        );
+    }
+
+    #[test]
+    fn test_deposit_never_exceeds_amount() {
+        let position = PositionBuilder::new(-443584, 443584).build();
+        for amount in [1, 7, 1_000, 123_456_789, 1 << 40] {
+            for is_token_a in [true, false] {
+                let deposit =
+                    calculate_single_sided_deposit(0, TO_Q64, &position, amount, is_token_a)
+                        .unwrap();
+                let used_amount = if is_token_a {
+                    deposit.token_a
+                } else {
+                    deposit.token_b
+                };
+                assert_eq!(used_amount + deposit.unused_amount, amount);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod calculate_reposition_quote_tests {
+    use super::*;
+    use crate::math::TO_Q64;
+    use crate::state::{position_builder::PositionBuilder, whirlpool_builder::WhirlpoolBuilder};
+
+    fn whirlpool_at_tick(tick_current_index: i32) -> Whirlpool {
+        WhirlpoolBuilder::new()
+            .tick_spacing(64)
+            .tick_current_index(tick_current_index)
+            .sqrt_price(sqrt_price_from_tick_index(tick_current_index))
+            .build()
+    }
+
+    #[test]
+    fn test_narrow_range_takes_more_liquidity() {
+        let whirlpool = whirlpool_at_tick(0);
+        let position = PositionBuilder::new(-1280, 1280)
+            .liquidity(1_000_000_000)
+            .build();
+        let quote =
+            calculate_reposition_quote(&whirlpool, &position, -640, 640, 0, 0, 100).unwrap();
+
+        assert_eq!(
+            (quote.token_withdrawn_a, quote.token_withdrawn_b),
+            calculate_liquidity_token_deltas(0, TO_Q64, &position, -1_000_000_000).unwrap()
+        );
+        assert!(quote.liquidity > position.liquidity);
+        // The deposit is funded by the withdrawn tokens only
+        assert!(quote.token_deposit_a <= quote.token_withdrawn_a);
+        assert!(quote.token_deposit_b <= quote.token_withdrawn_b);
+        assert!(quote.token_delta_a <= 0);
+        assert!(quote.token_delta_b <= 0);
+        assert!(quote.token_min_withdrawn_a < quote.token_withdrawn_a);
+        assert!(quote.token_max_deposit_a > quote.token_deposit_a);
+    }
+
+    #[test]
+    fn test_top_up_increases_liquidity() {
+        let whirlpool = whirlpool_at_tick(0);
+        let position = PositionBuilder::new(-1280, 1280)
+            .liquidity(1_000_000_000)
+            .build();
+        let quote =
+            calculate_reposition_quote(&whirlpool, &position, -2560, 2560, 0, 0, 0).unwrap();
+        let topped_up =
+            calculate_reposition_quote(&whirlpool, &position, -2560, 2560, 1_000_000, 1_000_000, 0)
+                .unwrap();
+
+        assert!(topped_up.liquidity > quote.liquidity);
+        assert!(topped_up.token_delta_a > 0 || topped_up.token_delta_b > 0);
+        assert!(topped_up.token_delta_a <= 1_000_000);
+        assert!(topped_up.token_delta_b <= 1_000_000);
+    }
+
+    #[test]
+    fn test_out_of_range_position_moved_in_range() {
+        // The position below the current price only holds token B, so a range around the
+        // current price needs token A from the wallet
+        let whirlpool = whirlpool_at_tick(0);
+        let position = PositionBuilder::new(-2560, -1280)
+            .liquidity(1_000_000_000)
+            .build();
+
+        assert_eq!(
+            calculate_reposition_quote(&whirlpool, &position, -1280, 1280, 0, 0, 0).unwrap_err(),
+            RepositionQuoteError::Liquidity(ErrorCode::LiquidityZero)
+        );
+
+        let quote = calculate_reposition_quote(&whirlpool, &position, -1280, 1280, 1_000_000, 0, 0)
+            .unwrap();
+        assert!(quote.liquidity > 0);
+        assert!(quote.token_delta_a > 0);
+    }
+
+    #[test]
+    fn test_same_tick_range() {
+        let whirlpool = whirlpool_at_tick(0);
+        let position = PositionBuilder::new(-1280, 1280)
+            .liquidity(1_000_000_000)
+            .build();
+        assert_eq!(
+            calculate_reposition_quote(&whirlpool, &position, -1280, 1280, 0, 0, 0).unwrap_err(),
+            RepositionQuoteError::SameTickRangeNotAllowed
+        );
+    }
+
+    #[test]
+    fn test_invalid_new_range() {
+        let whirlpool = whirlpool_at_tick(0);
+        let position = PositionBuilder::new(-1280, 1280)
+            .liquidity(1_000_000_000)
+            .build();
+        for (new_tick_lower_index, new_tick_upper_index) in [(-1000, 1280), (1280, -1280), (64, 64)]
+        {
+            assert_eq!(
+                calculate_reposition_quote(
+                    &whirlpool,
+                    &position,
+                    new_tick_lower_index,
+                    new_tick_upper_index,
+                    0,
+                    0,
+                    0
+                )
+                .unwrap_err(),
+                RepositionQuoteError::Liquidity(ErrorCode::InvalidTickIndex)
+            );
+        }
+    }
+}
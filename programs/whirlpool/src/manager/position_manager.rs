@@ -1,7 +1,11 @@
 use crate::{
     errors::ErrorCode,
+    manager::{
+        tick_manager::{next_fee_growths_inside, next_reward_growths_inside},
+        whirlpool_manager::next_whirlpool_reward_infos,
+    },
     math::{add_liquidity_delta, checked_mul_shift_right},
-    state::{Position, PositionUpdate, NUM_REWARDS},
+    state::{Position, PositionUpdate, Tick, Whirlpool, NUM_REWARDS},
 };
 
 pub fn next_position_modify_liquidity_update(
@@ -53,6 +57,64 @@ pub fn next_position_modify_liquidity_update(
     Ok(update)
 }
 
+#[derive(Debug, PartialEq)]
+pub struct PositionCollectables {
+    pub fee_owed_a: u64,
+    pub fee_owed_b: u64,
+    pub rewards_owed: [u64; NUM_REWARDS],
+}
+
+// Calculates the fees and rewards a position can collect at the given timestamp.
+// Only the decoded whirlpool, position and boundary tick accounts are read, so the amounts can be
+// derived from account snapshots without any RPC access. No account state is modified.
+pub fn position_collectables(
+    whirlpool: &Whirlpool,
+    position: &Position,
+    tick_lower: &Tick,
+    tick_upper: &Tick,
+    timestamp: u64,
+) -> Result<PositionCollectables, ErrorCode> {
+    let next_reward_infos = next_whirlpool_reward_infos(whirlpool, timestamp)?;
+
+    let (fee_growth_inside_a, fee_growth_inside_b) = next_fee_growths_inside(
+        whirlpool.tick_current_index,
+        tick_lower,
+        position.tick_lower_index,
+        tick_upper,
+        position.tick_upper_index,
+        whirlpool.fee_growth_global_a,
+        whirlpool.fee_growth_global_b,
+    );
+
+    let reward_growths_inside = next_reward_growths_inside(
+        whirlpool.tick_current_index,
+        tick_lower,
+        position.tick_lower_index,
+        tick_upper,
+        position.tick_upper_index,
+        &next_reward_infos,
+    );
+
+    let update = next_position_modify_liquidity_update(
+        position,
+        0,
+        fee_growth_inside_a,
+        fee_growth_inside_b,
+        &reward_growths_inside,
+    )?;
+
+    let mut rewards_owed = [0; NUM_REWARDS];
+    for (reward_owed, reward_info) in rewards_owed.iter_mut().zip(update.reward_infos.iter()) {
+        *reward_owed = reward_info.amount_owed;
+    }
+
+    Ok(PositionCollectables {
+        fee_owed_a: update.fee_owed_a,
+        fee_owed_b: update.fee_owed_b,
+        rewards_owed,
+    })
+}
+
 #[cfg(test)]
 mod position_manager_unit_tests {
     use crate::{
@@ -320,3 +382,159 @@ mod position_manager_unit_tests {
         )
     }
 }
+
+#[cfg(test)]
+mod position_collectables_tests {
+    use anchor_lang::prelude::*;
+
+    use super::*;
+    use crate::{
+        math::Q64_RESOLUTION,
+        state::{
+            position_builder::PositionBuilder, tick_builder::TickBuilder,
+            whirlpool_builder::WhirlpoolBuilder, PositionRewardInfo, WhirlpoolRewardInfo,
+        },
+    };
+
+    // Round-trip an account through its on-chain byte representation
+    fn decode<T: AccountSerialize + AccountDeserialize>(account: &T) -> T {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        T::try_deserialize(&mut data.as_slice()).unwrap()
+    }
+
+    fn reward_info(emissions_per_second_x64: u128, growth_global_x64: u128) -> WhirlpoolRewardInfo {
+        WhirlpoolRewardInfo {
+            mint: Pubkey::new_unique(),
+            emissions_per_second_x64,
+            growth_global_x64,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn in_range_position_accrues_fees_and_rewards() {
+        let whirlpool = decode(
+            &WhirlpoolBuilder::new()
+                .tick_current_index(0)
+                .liquidity(1_000)
+                .fee_growth_global_a(30 << Q64_RESOLUTION)
+                .fee_growth_global_b(40 << Q64_RESOLUTION)
+                .reward_last_updated_timestamp(100)
+                .reward_info(0, reward_info(1_000 << Q64_RESOLUTION, 5 << Q64_RESOLUTION))
+                .build(),
+        );
+        let position = decode(
+            &PositionBuilder::new(-10, 10)
+                .liquidity(500)
+                .fee_owed_a(7)
+                .fee_growth_checkpoint_a(10 << Q64_RESOLUTION)
+                .fee_growth_checkpoint_b(10 << Q64_RESOLUTION)
+                .reward_info(
+                    0,
+                    PositionRewardInfo {
+                        growth_inside_checkpoint: 1 << Q64_RESOLUTION,
+                        amount_owed: 3,
+                    },
+                )
+                .build(),
+        );
+        let tick_lower = TickBuilder::default()
+            .initialized(true)
+            .fee_growth_outside_a(5 << Q64_RESOLUTION)
+            .fee_growth_outside_b(5 << Q64_RESOLUTION)
+            .reward_growths_outside([1 << Q64_RESOLUTION, 0, 0])
+            .build();
+        let tick_upper = TickBuilder::default()
+            .initialized(true)
+            .fee_growth_outside_a(5 << Q64_RESOLUTION)
+            .fee_growth_outside_b(5 << Q64_RESOLUTION)
+            .build();
+
+        let collectables =
+            position_collectables(&whirlpool, &position, &tick_lower, &tick_upper, 110).unwrap();
+
+        // fee growth inside = 30 - 5 - 5 = 20 (A), 40 - 5 - 5 = 30 (B)
+        // reward growth global = 5 + 10 * 1000 / 1000 = 15, inside = 15 - 1 - 0 = 14
+        assert_eq!(
+            collectables,
+            PositionCollectables {
+                fee_owed_a: 7 + 500 * (20 - 10),
+                fee_owed_b: 500 * (30 - 10),
+                rewards_owed: [3 + 500 * (14 - 1), 0, 0],
+            }
+        );
+    }
+
+    #[test]
+    fn out_of_range_position_does_not_accrue() {
+        let whirlpool = decode(
+            &WhirlpoolBuilder::new()
+                .tick_current_index(100)
+                .liquidity(1_000)
+                .fee_growth_global_a(30 << Q64_RESOLUTION)
+                .fee_growth_global_b(40 << Q64_RESOLUTION)
+                .reward_last_updated_timestamp(100)
+                .reward_info(0, reward_info(1_000 << Q64_RESOLUTION, 0))
+                .build(),
+        );
+        // Both ticks were crossed after initialization, so all growth happened above the position
+        let tick = TickBuilder::default()
+            .initialized(true)
+            .fee_growth_outside_a(10 << Q64_RESOLUTION)
+            .fee_growth_outside_b(10 << Q64_RESOLUTION)
+            .build();
+        let position = decode(
+            &PositionBuilder::new(-10, 10)
+                .liquidity(500)
+                .fee_owed_b(9)
+                .build(),
+        );
+
+        let collectables = position_collectables(&whirlpool, &position, &tick, &tick, 200).unwrap();
+
+        assert_eq!(
+            collectables,
+            PositionCollectables {
+                fee_owed_a: 0,
+                fee_owed_b: 9,
+                rewards_owed: [0, 0, 0],
+            }
+        );
+    }
+
+    #[test]
+    fn zero_liquidity_position_returns_owed_amounts() {
+        let whirlpool = decode(
+            &WhirlpoolBuilder::new()
+                .fee_growth_global_a(30 << Q64_RESOLUTION)
+                .build(),
+        );
+        let position = decode(
+            &PositionBuilder::new(-10, 10)
+                .fee_owed_a(11)
+                .fee_owed_b(12)
+                .build(),
+        );
+        let tick = TickBuilder::default().build();
+
+        let collectables = position_collectables(&whirlpool, &position, &tick, &tick, 0).unwrap();
+
+        assert_eq!(collectables.fee_owed_a, 11);
+        assert_eq!(collectables.fee_owed_b, 12);
+    }
+
+    #[test]
+    fn timestamp_before_last_update_errors() {
+        let whirlpool = WhirlpoolBuilder::new()
+            .reward_last_updated_timestamp(100)
+            .build();
+        let position = PositionBuilder::new(-10, 10).build();
+        let tick = TickBuilder::default().build();
+
+        assert_eq!(
+            position_collectables(&whirlpool, &position, &tick, &tick, 99),
+            Err(ErrorCode::InvalidTimestamp)
+        );
+    }
+}
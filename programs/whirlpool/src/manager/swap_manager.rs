@@ -7,12 +7,15 @@ use crate::{
     state::*,
     util::SwapTickSequence,
 };
+use anchor_lang::prelude::Pubkey;
+use std::cell::RefCell;
 use std::convert::TryInto;
 
 #[derive(Debug)]
 pub struct PostSwapUpdate {
     pub amount_a: u64,
     pub amount_b: u64,
+    pub fee_amount: u64,
     pub next_liquidity: u128,
     pub next_tick_index: i32,
     pub next_sqrt_price: u128,
@@ -55,6 +58,7 @@ pub fn swap(
     let mut curr_tick_index = whirlpool.tick_current_index;
     let mut curr_liquidity = whirlpool.liquidity;
     let mut curr_protocol_fee: u64 = 0;
+    let mut curr_fee_amount: u64 = 0;
     let mut curr_array_index: usize = 0;
     let mut curr_fee_growth_global_input = if a_to_b {
         whirlpool.fee_growth_global_a
@@ -108,6 +112,10 @@ pub fn swap(
                 .ok_or(ErrorCode::AmountCalcOverflow)?;
         }
 
+        curr_fee_amount = curr_fee_amount
+            .checked_add(swap_computation.fee_amount)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+
         let (next_protocol_fee, next_fee_growth_global_input) = calculate_fees(
             swap_computation.fee_amount,
             protocol_fee_rate,
@@ -188,6 +196,7 @@ pub fn swap(
     Ok(PostSwapUpdate {
         amount_a,
         amount_b,
+        fee_amount: curr_fee_amount,
         next_liquidity: curr_liquidity,
         next_tick_index: curr_tick_index,
         next_sqrt_price: curr_sqrt_price,
@@ -197,6 +206,476 @@ pub fn swap(
     })
 }
 
+#[derive(Debug, PartialEq)]
+pub struct SwapSlippageEstimate {
+    pub price_impact_bps: u32,
+    pub suggested_slippage_bps: u32,
+}
+
+/// Estimate the slippage tolerance a swap needs to succeed against the current depth of the whirlpool.
+///
+/// The swap is simulated without a sqrt-price limit and the price impact is measured from the whirlpool's
+/// sqrt-price before and after the swap. The suggested slippage adds `margin_bps` on top of the price impact
+/// to absorb price movement between quoting and execution.
+///
+/// # Parameters
+/// - `whirlpool` - The whirlpool to swap on
+/// - `swap_tick_sequence` - The tick arrays the swap traverses
+/// - `amount` - The amount of input or output token to swap
+/// - `amount_specified_is_input` - Whether `amount` represents the input token of the swap
+/// - `a_to_b` - The direction of the swap
+/// - `margin_bps` - The margin to add on top of the price impact, in basis points
+/// - `timestamp` - The current timestamp, used to update reward growths during the simulation
+///
+/// # Returns
+/// - `Ok`: The price impact & suggested slippage of the swap, in basis points
+/// - Any error returned by `swap` for the simulated swap
+pub fn estimate_swap_slippage(
+    whirlpool: &Whirlpool,
+    swap_tick_sequence: &mut SwapTickSequence,
+    amount: u64,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    margin_bps: u16,
+    timestamp: u64,
+) -> Result<SwapSlippageEstimate, ErrorCode> {
+    let sqrt_price_limit = if a_to_b {
+        MIN_SQRT_PRICE_X64
+    } else {
+        MAX_SQRT_PRICE_X64
+    };
+    let post_swap = swap(
+        whirlpool,
+        swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        timestamp,
+    )?;
+
+    let price_impact_bps = price_impact_bps(whirlpool.sqrt_price, post_swap.next_sqrt_price);
+    Ok(SwapSlippageEstimate {
+        price_impact_bps,
+        suggested_slippage_bps: price_impact_bps.saturating_add(margin_bps as u32),
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SwapSequenceEstimate {
+    pub sqrt_price: u128,
+    pub tick_current_index: i32,
+    pub amount_a_delta: i128,
+    pub amount_b_delta: i128,
+}
+
+/// Estimate the state of the whirlpool after applying a sequence of exact-input swaps.
+///
+/// Each swap is simulated against the whirlpool & ticks left behind by the previous swap, exactly as if
+/// the swaps were executed one after another. The tick arrays each swap traverses are selected from
+/// `tick_arrays` starting at the array holding the current tick index, in the direction of the swap.
+///
+/// # Parameters
+/// - `whirlpool` - The whirlpool before the first swap
+/// - `tick_arrays` - Contiguous tick arrays sorted by ascending start tick index, updated as ticks are crossed
+/// - `swaps` - The swaps to apply in order, as (input amount, a_to_b) pairs
+/// - `timestamp` - The current timestamp, used to update reward growths during the simulation
+///
+/// # Returns
+/// - `Ok`: The final sqrt-price & tick index of the whirlpool, and the cumulative net change of the
+///   token A & token B vaults (positive when tokens flow into the whirlpool)
+/// - `InvalidTickArraySequence`: No tick array in `tick_arrays` holds the current tick index
+/// - Any error returned by `swap` for one of the swaps
+pub fn estimate_swap_sequence(
+    whirlpool: &Whirlpool,
+    tick_arrays: &[RefCell<TickArray>],
+    swaps: &[(u64, bool)],
+    timestamp: u64,
+) -> Result<SwapSequenceEstimate, ErrorCode> {
+    let mut next_whirlpool = whirlpool.clone();
+    let mut amount_a_delta: i128 = 0;
+    let mut amount_b_delta: i128 = 0;
+
+    for &(amount, a_to_b) in swaps {
+        let start_array_index = tick_arrays
+            .iter()
+            .position(|tick_array| {
+                tick_array.borrow().check_in_array_bounds(
+                    next_whirlpool.tick_current_index,
+                    next_whirlpool.tick_spacing,
+                )
+            })
+            .ok_or(ErrorCode::InvalidTickArraySequence)?;
+        let mut sequence_arrays = (0..3).filter_map(|offset| {
+            let array_index = if a_to_b {
+                start_array_index.checked_sub(offset)?
+            } else {
+                start_array_index + offset
+            };
+            tick_arrays.get(array_index).map(RefCell::borrow_mut)
+        });
+        let mut swap_tick_sequence = SwapTickSequence::new(
+            sequence_arrays.next().unwrap(),
+            sequence_arrays.next(),
+            sequence_arrays.next(),
+        );
+
+        let sqrt_price_limit = if a_to_b {
+            MIN_SQRT_PRICE_X64
+        } else {
+            MAX_SQRT_PRICE_X64
+        };
+        let post_swap = swap(
+            &next_whirlpool,
+            &mut swap_tick_sequence,
+            amount,
+            sqrt_price_limit,
+            true,
+            a_to_b,
+            timestamp,
+        )?;
+
+        if a_to_b {
+            amount_a_delta += post_swap.amount_a as i128;
+            amount_b_delta -= post_swap.amount_b as i128;
+        } else {
+            amount_a_delta -= post_swap.amount_a as i128;
+            amount_b_delta += post_swap.amount_b as i128;
+        }
+
+        next_whirlpool.update_after_swap(
+            post_swap.next_liquidity,
+            post_swap.next_tick_index,
+            post_swap.next_sqrt_price,
+            post_swap.next_fee_growth_global,
+            post_swap.next_reward_infos,
+            post_swap.next_protocol_fee,
+            a_to_b,
+            timestamp,
+        );
+    }
+
+    Ok(SwapSequenceEstimate {
+        sqrt_price: next_whirlpool.sqrt_price,
+        tick_current_index: next_whirlpool.tick_current_index,
+        amount_a_delta,
+        amount_b_delta,
+    })
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SwapBatchQuote {
+    pub amount: u64,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub next_sqrt_price: u128,
+    pub price_impact_bps: u32,
+    pub effective_price_x64: u128,
+}
+
+/// Quote a batch of swap amounts in the same direction against the same whirlpool state.
+///
+/// Every amount is quoted independently from the whirlpool's current state over the same tick arrays,
+/// so the tick arrays only need to be loaded once. Amounts are expected in ascending order: the batch
+/// stops after the first amount that can not be filled before reaching `sqrt_price_limit`, as every
+/// larger amount would be filled identically.
+///
+/// The price impact is the move of the whirlpool's price (token B per token A) from its current sqrt-price to
+/// the quote's next sqrt-price, rounded up. It is the same formula in both directions: a_to_b swaps lower
+/// the price and b_to_a swaps raise it.
+///
+/// The effective price is the execution price of the quote, as a Q64.64 amount of token B per token A:
+/// amount_out / amount_in for a_to_b swaps and amount_in / amount_out for b_to_a swaps. It is expressed in
+/// the same unit as the whirlpool's price so both directions can be compared against it, and is 0 when
+/// no token A is swapped.
+///
+/// The ticks crossed by each quote are updated in `swap_tick_sequence`. Crossing updates only flip the
+/// fee & reward growths outside of a tick, so they do not affect the quoted amounts, but the tick arrays
+/// should be discarded after quoting.
+///
+/// # Parameters
+/// - `whirlpool` - The whirlpool to quote against
+/// - `swap_tick_sequence` - The tick arrays the swaps traverse
+/// - `amounts` - The amounts of input or output token to quote, in ascending order
+/// - `sqrt_price_limit` - The sqrt-price the swaps can not move the whirlpool past
+/// - `amount_specified_is_input` - Whether `amounts` represent the input token of the swaps
+/// - `a_to_b` - The direction of the swaps
+/// - `timestamp` - The current timestamp, used to update reward growths during the simulation
+///
+/// # Returns
+/// - `Ok`: One quote per amount, up to & including the first amount the liquidity can not fill
+/// - Any error returned by `swap` for one of the amounts
+pub fn quote_swap_batch(
+    whirlpool: &Whirlpool,
+    swap_tick_sequence: &mut SwapTickSequence,
+    amounts: &[u64],
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    timestamp: u64,
+) -> Result<Vec<SwapBatchQuote>, ErrorCode> {
+    let mut quotes = Vec::with_capacity(amounts.len());
+
+    for &amount in amounts {
+        let post_swap = swap(
+            whirlpool,
+            swap_tick_sequence,
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            timestamp,
+        )?;
+
+        let (amount_in, amount_out) = if a_to_b {
+            (post_swap.amount_a, post_swap.amount_b)
+        } else {
+            (post_swap.amount_b, post_swap.amount_a)
+        };
+        let effective_price_x64 = if post_swap.amount_a == 0 {
+            0
+        } else {
+            ((post_swap.amount_b as u128) << Q64_RESOLUTION) / post_swap.amount_a as u128
+        };
+        quotes.push(SwapBatchQuote {
+            amount,
+            amount_in,
+            amount_out,
+            next_sqrt_price: post_swap.next_sqrt_price,
+            price_impact_bps: price_impact_bps(whirlpool.sqrt_price, post_swap.next_sqrt_price),
+            effective_price_x64,
+        });
+
+        let amount_filled = if amount_specified_is_input {
+            amount_in
+        } else {
+            amount_out
+        };
+        if amount_filled < amount {
+            break;
+        }
+    }
+
+    Ok(quotes)
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ExactOutSwapQuote {
+    pub token_in: u64,
+    pub fee: u64,
+    pub token_out: u64,
+    pub partial_fill: bool,
+}
+
+/// Quote the input required to receive an exact amount of output token.
+///
+/// `token_in` is the total amount of input token the swap transfers from the user, including the fee.
+/// `fee` is the part of `token_in` charged as fees (incl. the protocol fee), so the amount of input
+/// token actually swapped is `token_in - fee`.
+///
+/// If the swap reaches `sqrt_price_limit` before the whole amount is filled, `token_out` is less than
+/// `amount_out` and `partial_fill` is set, so callers can reject the quote instead of receiving less
+/// than requested. Without an explicit `sqrt_price_limit` the swap may move the price up to the
+/// min/max sqrt-price, so an amount that is not filled within the provided tick arrays fails with
+/// `TickArraySequenceInvalidIndex` instead of returning a partial fill.
+///
+/// # Parameters
+/// - `whirlpool` - The whirlpool to swap on
+/// - `swap_tick_sequence` - The tick arrays the swap traverses
+/// - `amount_out` - The exact amount of output token to receive
+/// - `sqrt_price_limit` - The sqrt-price the swap can not move the whirlpool past, if any
+/// - `a_to_b` - The direction of the swap
+/// - `timestamp` - The current timestamp, used to update reward growths during the simulation
+///
+/// # Returns
+/// - `Ok`: The input required including fees, the fee, the output filled & whether the fill is partial
+/// - `TickArraySequenceInvalidIndex`: Without a `sqrt_price_limit`, the amount is not filled within
+///   the provided tick arrays
+/// - Any error returned by `swap` for the simulated swap
+pub fn quote_exact_out_swap(
+    whirlpool: &Whirlpool,
+    swap_tick_sequence: &mut SwapTickSequence,
+    amount_out: u64,
+    sqrt_price_limit: Option<u128>,
+    a_to_b: bool,
+    timestamp: u64,
+) -> Result<ExactOutSwapQuote, ErrorCode> {
+    let sqrt_price_limit = sqrt_price_limit.unwrap_or(if a_to_b {
+        MIN_SQRT_PRICE_X64
+    } else {
+        MAX_SQRT_PRICE_X64
+    });
+    let post_swap = swap(
+        whirlpool,
+        swap_tick_sequence,
+        amount_out,
+        sqrt_price_limit,
+        false,
+        a_to_b,
+        timestamp,
+    )?;
+
+    let (token_in, token_out) = if a_to_b {
+        (post_swap.amount_a, post_swap.amount_b)
+    } else {
+        (post_swap.amount_b, post_swap.amount_a)
+    };
+    Ok(ExactOutSwapQuote {
+        token_in,
+        fee: post_swap.fee_amount,
+        token_out,
+        partial_fill: token_out < amount_out,
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TwoHopSwapQuoteError {
+    // The output mint of the first hop or the input mint of the second hop is not the intermediary mint
+    InvalidIntermediaryMint,
+    // Both hops swap on the same whirlpool
+    DuplicateTwoHopPool,
+    // The simulation of one of the hops failed
+    Swap(ErrorCode),
+}
+
+impl From<ErrorCode> for TwoHopSwapQuoteError {
+    fn from(error: ErrorCode) -> Self {
+        TwoHopSwapQuoteError::Swap(error)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TwoHopSwapQuote {
+    pub amount_in: u64,
+    pub amount_intermediate: u64,
+    pub amount_out: u64,
+    pub minimum_amount_out: u64,
+    pub price_impact_bps: u32,
+}
+
+/// Quote an exact-input swap routed through two whirlpools that share an intermediary mint.
+///
+/// The output of the swap on `whirlpool_one` is used as the exact input of the swap on `whirlpool_two`.
+/// Both swaps are simulated without a sqrt-price limit.
+///
+/// The price impact compounds the price impacts of both hops, each capped at 100%:
+/// 1 - (1 - impact_one) * (1 - impact_two), rounded up.
+///
+/// # Parameters
+/// - `whirlpool_one` - The whirlpool of the first hop
+/// - `swap_tick_sequence_one` - The tick arrays the first hop traverses
+/// - `a_to_b_one` - The direction of the first hop
+/// - `whirlpool_two` - The whirlpool of the second hop
+/// - `swap_tick_sequence_two` - The tick arrays the second hop traverses
+/// - `a_to_b_two` - The direction of the second hop
+/// - `intermediary_mint` - The mint received from the first hop and spent on the second hop
+/// - `amount` - The amount of input token to swap on the first hop
+/// - `slippage_tolerance_bps` - The slippage tolerance applied to the output amount with
+///   `get_min_amount_with_slippage`, in basis points
+/// - `timestamp` - The current timestamp, used to update reward growths during the simulation
+///
+/// # Returns
+/// - `Ok`: The amounts swapped on each hop, the minimum output amount within the slippage tolerance
+///   and the combined price impact
+/// - `DuplicateTwoHopPool`: Both hops swap on the same whirlpool
+/// - `InvalidIntermediaryMint`: The output mint of the first hop or the input mint of the second hop
+///   is not `intermediary_mint`
+/// - `Swap`: Any error returned by `swap` for one of the hops
+pub fn quote_two_hop_swap(
+    whirlpool_one: &Whirlpool,
+    swap_tick_sequence_one: &mut SwapTickSequence,
+    a_to_b_one: bool,
+    whirlpool_two: &Whirlpool,
+    swap_tick_sequence_two: &mut SwapTickSequence,
+    a_to_b_two: bool,
+    intermediary_mint: Pubkey,
+    amount: u64,
+    slippage_tolerance_bps: u16,
+    timestamp: u64,
+) -> Result<TwoHopSwapQuote, TwoHopSwapQuoteError> {
+    // A whirlpool address is derived from its config, mints & tick spacing
+    if whirlpool_one.whirlpools_config == whirlpool_two.whirlpools_config
+        && whirlpool_one.token_mint_a == whirlpool_two.token_mint_a
+        && whirlpool_one.token_mint_b == whirlpool_two.token_mint_b
+        && whirlpool_one.tick_spacing == whirlpool_two.tick_spacing
+    {
+        return Err(TwoHopSwapQuoteError::DuplicateTwoHopPool);
+    }
+
+    let output_mint_one = if a_to_b_one {
+        whirlpool_one.token_mint_b
+    } else {
+        whirlpool_one.token_mint_a
+    };
+    let input_mint_two = if a_to_b_two {
+        whirlpool_two.token_mint_a
+    } else {
+        whirlpool_two.token_mint_b
+    };
+    if output_mint_one != intermediary_mint || input_mint_two != intermediary_mint {
+        return Err(TwoHopSwapQuoteError::InvalidIntermediaryMint);
+    }
+
+    let post_swap_one = swap(
+        whirlpool_one,
+        swap_tick_sequence_one,
+        amount,
+        if a_to_b_one {
+            MIN_SQRT_PRICE_X64
+        } else {
+            MAX_SQRT_PRICE_X64
+        },
+        true,
+        a_to_b_one,
+        timestamp,
+    )?;
+    let (amount_in, amount_intermediate) = if a_to_b_one {
+        (post_swap_one.amount_a, post_swap_one.amount_b)
+    } else {
+        (post_swap_one.amount_b, post_swap_one.amount_a)
+    };
+
+    let post_swap_two = swap(
+        whirlpool_two,
+        swap_tick_sequence_two,
+        amount_intermediate,
+        if a_to_b_two {
+            MIN_SQRT_PRICE_X64
+        } else {
+            MAX_SQRT_PRICE_X64
+        },
+        true,
+        a_to_b_two,
+        timestamp,
+    )?;
+    let amount_out = if a_to_b_two {
+        post_swap_two.amount_b
+    } else {
+        post_swap_two.amount_a
+    };
+
+    let minimum_amount_out = get_min_amount_with_slippage(amount_out, slippage_tolerance_bps);
+
+    let max_impact_bps = PRICE_TOLERANCE_BPS_MUL_VALUE as u64;
+    let impact_one_bps = (price_impact_bps(whirlpool_one.sqrt_price, post_swap_one.next_sqrt_price)
+        as u64)
+        .min(max_impact_bps);
+    let impact_two_bps = (price_impact_bps(whirlpool_two.sqrt_price, post_swap_two.next_sqrt_price)
+        as u64)
+        .min(max_impact_bps);
+    // Rounding the product down rounds the combined impact up
+    let price_impact =
+        impact_one_bps + impact_two_bps - impact_one_bps * impact_two_bps / max_impact_bps;
+
+    Ok(TwoHopSwapQuote {
+        amount_in,
+        amount_intermediate,
+        amount_out,
+        minimum_amount_out,
+        price_impact_bps: price_impact as u32,
+    })
+}
+
 fn calculate_fees(
     fee_amount: u64,
     protocol_fee_rate: u16,
@@ -2535,3 +3014,560 @@ mod swap_error_tests {
     }
 
 }
+
+#[cfg(test)]
+mod estimate_swap_slippage_tests {
+    use super::*;
+    use crate::util::test_utils::swap_test_fixture::*;
+
+    fn estimate(trade_amount: u64, margin_bps: u16) -> SwapSlippageEstimate {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 1_000_000_000_000,
+            curr_tick_index: 1000,
+            start_tick_index: 0,
+            a_to_b: true,
+            array_2_ticks: Some(&vec![]),
+            array_3_ticks: Some(&vec![]),
+            ..Default::default()
+        });
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        estimate_swap_slippage(
+            &swap_test_info.whirlpool,
+            &mut tick_sequence,
+            trade_amount,
+            true,
+            true,
+            margin_bps,
+            100,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn small_swap_suggests_low_slippage() {
+        let estimate = estimate(1_000, 50);
+        assert!(estimate.price_impact_bps <= 1);
+        assert_eq!(
+            estimate.suggested_slippage_bps,
+            estimate.price_impact_bps + 50
+        );
+    }
+
+    #[test]
+    fn large_swap_suggests_high_slippage() {
+        // Moves 1/sqrt(price) from ~0.951 to ~1.051, dropping the price by ~18%
+        let estimate = estimate(100_000_000_000, 50);
+        assert!(estimate.price_impact_bps > 1_700 && estimate.price_impact_bps < 1_900);
+        assert_eq!(
+            estimate.suggested_slippage_bps,
+            estimate.price_impact_bps + 50
+        );
+    }
+}
+
+#[cfg(test)]
+mod estimate_swap_sequence_tests {
+    use super::*;
+    use crate::util::test_utils::swap_test_fixture::*;
+
+    // Moves the tick to ~380, then across 2560 to ~3663, back across 2560 & -1280 to ~-2707, then to ~-910
+    const SWAPS: [(u64, bool); 4] = [
+        (30_000_000_000, true),
+        (150_000_000_000, false),
+        (250_000_000_000, true),
+        (50_000_000_000, false),
+    ];
+
+    // Tick arrays starting at -11264, 0 & 11264, with initialized ticks on both sides of tick 1000
+    fn fixture() -> SwapTestFixture {
+        SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 1_000_000_000_000,
+            curr_tick_index: 1000,
+            start_tick_index: -11264,
+            a_to_b: false,
+            fee_rate: 3000,
+            array_1_ticks: &vec![TestTickInfo {
+                index: -1280,
+                liquidity_net: 500_000_000_000,
+                ..Default::default()
+            }],
+            array_2_ticks: Some(&vec![TestTickInfo {
+                index: 2560,
+                liquidity_net: -500_000_000_000,
+                ..Default::default()
+            }]),
+            array_3_ticks: Some(&vec![]),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn sequence_matches_swaps_applied_one_at_a_time() {
+        let sequence_fixture = fixture();
+        let sequence = estimate_swap_sequence(
+            &sequence_fixture.whirlpool,
+            &sequence_fixture.tick_arrays,
+            &SWAPS,
+            100,
+        )
+        .unwrap();
+
+        let step_fixture = fixture();
+        let mut whirlpool = step_fixture.whirlpool.clone();
+        let mut amount_a_delta = 0;
+        let mut amount_b_delta = 0;
+        for (amount, a_to_b) in SWAPS {
+            let arrays = &step_fixture.tick_arrays;
+            let mut tick_sequence = match (a_to_b, whirlpool.tick_current_index < 0) {
+                (true, false) => SwapTickSequence::new(
+                    arrays[1].borrow_mut(),
+                    Some(arrays[0].borrow_mut()),
+                    None,
+                ),
+                (true, true) => SwapTickSequence::new(arrays[0].borrow_mut(), None, None),
+                (false, false) => SwapTickSequence::new(
+                    arrays[1].borrow_mut(),
+                    Some(arrays[2].borrow_mut()),
+                    None,
+                ),
+                (false, true) => SwapTickSequence::new(
+                    arrays[0].borrow_mut(),
+                    Some(arrays[1].borrow_mut()),
+                    Some(arrays[2].borrow_mut()),
+                ),
+            };
+            let sqrt_price_limit = if a_to_b {
+                MIN_SQRT_PRICE_X64
+            } else {
+                MAX_SQRT_PRICE_X64
+            };
+            let post_swap = swap(
+                &whirlpool,
+                &mut tick_sequence,
+                amount,
+                sqrt_price_limit,
+                true,
+                a_to_b,
+                100,
+            )
+            .unwrap();
+            whirlpool.update_after_swap(
+                post_swap.next_liquidity,
+                post_swap.next_tick_index,
+                post_swap.next_sqrt_price,
+                post_swap.next_fee_growth_global,
+                post_swap.next_reward_infos,
+                post_swap.next_protocol_fee,
+                a_to_b,
+                100,
+            );
+            let (amount_a, amount_b) = (post_swap.amount_a as i128, post_swap.amount_b as i128);
+            if a_to_b {
+                amount_a_delta += amount_a;
+                amount_b_delta -= amount_b;
+            } else {
+                amount_a_delta -= amount_a;
+                amount_b_delta += amount_b;
+            }
+        }
+
+        assert_eq!(sequence.sqrt_price, whirlpool.sqrt_price);
+        assert_eq!(sequence.tick_current_index, whirlpool.tick_current_index);
+        assert_eq!(sequence.amount_a_delta, amount_a_delta);
+        assert_eq!(sequence.amount_b_delta, amount_b_delta);
+    }
+
+    #[test]
+    fn single_swap_matches_swap() {
+        let swap_test_info = fixture();
+        let estimate = estimate_swap_sequence(
+            &swap_test_info.whirlpool,
+            &swap_test_info.tick_arrays,
+            &[(10_000_000_000, true)],
+            100,
+        )
+        .unwrap();
+
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[1].borrow_mut(),
+            Some(swap_test_info.tick_arrays[0].borrow_mut()),
+            None,
+        );
+        let post_swap = swap(
+            &swap_test_info.whirlpool,
+            &mut tick_sequence,
+            10_000_000_000,
+            MIN_SQRT_PRICE_X64,
+            true,
+            true,
+            100,
+        )
+        .unwrap();
+
+        assert_eq!(estimate.sqrt_price, post_swap.next_sqrt_price);
+        assert_eq!(estimate.tick_current_index, post_swap.next_tick_index);
+        assert_eq!(estimate.amount_a_delta, post_swap.amount_a as i128);
+        assert_eq!(estimate.amount_b_delta, -(post_swap.amount_b as i128));
+    }
+
+    #[test]
+    fn opposite_swaps_return_close_to_start() {
+        let swap_test_info = fixture();
+        let there = estimate_swap_sequence(
+            &swap_test_info.whirlpool,
+            &swap_test_info.tick_arrays,
+            &[(10_000_000_000, true)],
+            100,
+        )
+        .unwrap();
+        let back = estimate_swap_sequence(
+            &swap_test_info.whirlpool,
+            &swap_test_info.tick_arrays,
+            &[
+                (10_000_000_000, true),
+                (-there.amount_b_delta as u64, false),
+            ],
+            100,
+        )
+        .unwrap();
+
+        // Fees are kept by the pool on both legs, so the round trip leaves the price slightly lower
+        assert!(back.sqrt_price < swap_test_info.whirlpool.sqrt_price);
+        assert!(back.sqrt_price > there.sqrt_price);
+        assert_eq!(back.amount_b_delta, 0);
+        assert!(back.amount_a_delta > 0);
+    }
+
+    #[test]
+    fn current_tick_outside_tick_arrays() {
+        let swap_test_info = fixture();
+        let result = estimate_swap_sequence(
+            &swap_test_info.whirlpool,
+            &swap_test_info.tick_arrays[2..],
+            &SWAPS,
+            100,
+        );
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidTickArraySequence);
+    }
+}
+
+#[cfg(test)]
+mod quote_swap_batch_tests {
+    use super::*;
+    use crate::util::test_utils::swap_test_fixture::*;
+
+    fn fixture() -> SwapTestFixture {
+        SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 1_000_000_000_000,
+            curr_tick_index: 1000,
+            start_tick_index: 0,
+            a_to_b: true,
+            fee_rate: 3000,
+            array_1_ticks: &vec![TestTickInfo {
+                index: 256,
+                liquidity_net: -500_000_000_000,
+                ..Default::default()
+            }],
+            array_2_ticks: Some(&vec![]),
+            array_3_ticks: Some(&vec![]),
+            ..Default::default()
+        })
+    }
+
+    fn quote_batch(amounts: &[u64], sqrt_price_limit: u128) -> Vec<SwapBatchQuote> {
+        let swap_test_info = fixture();
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        quote_swap_batch(
+            &swap_test_info.whirlpool,
+            &mut tick_sequence,
+            amounts,
+            sqrt_price_limit,
+            true,
+            true,
+            100,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn batch_matches_individual_swaps() {
+        let amounts = [1_000_000, 10_000_000_000, 100_000_000_000];
+        let quotes = quote_batch(&amounts, MIN_SQRT_PRICE_X64);
+        assert_eq!(quotes.len(), amounts.len());
+
+        for (quote, amount) in quotes.iter().zip(amounts) {
+            let swap_test_info = fixture();
+            let mut tick_sequence = SwapTickSequence::new(
+                swap_test_info.tick_arrays[0].borrow_mut(),
+                Some(swap_test_info.tick_arrays[1].borrow_mut()),
+                Some(swap_test_info.tick_arrays[2].borrow_mut()),
+            );
+            let post_swap = swap(
+                &swap_test_info.whirlpool,
+                &mut tick_sequence,
+                amount,
+                MIN_SQRT_PRICE_X64,
+                true,
+                true,
+                100,
+            )
+            .unwrap();
+            assert_eq!(quote.amount, amount);
+            assert_eq!(quote.amount_in, post_swap.amount_a);
+            assert_eq!(quote.amount_out, post_swap.amount_b);
+            assert_eq!(quote.next_sqrt_price, post_swap.next_sqrt_price);
+        }
+
+        // The largest amount crosses the initialized tick at 256
+        assert!(quotes[2].next_sqrt_price < sqrt_price_from_tick_index(256));
+    }
+
+    #[test]
+    fn batch_stops_when_liquidity_is_exhausted() {
+        let sqrt_price_limit = sqrt_price_from_tick_index(500);
+        let quotes = quote_batch(
+            &[1_000_000, 100_000_000_000, 200_000_000_000, 300_000_000_000],
+            sqrt_price_limit,
+        );
+
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes[0].amount_in, 1_000_000);
+        assert!(quotes[1].amount_in < 100_000_000_000);
+        assert_eq!(quotes[1].next_sqrt_price, sqrt_price_limit);
+    }
+
+    #[test]
+    fn batch_price_impact_and_effective_price() {
+        let swap_test_info = fixture();
+        let price_x64 =
+            price_x64_from_tick_index(swap_test_info.whirlpool.tick_current_index).unwrap();
+        let quotes = quote_batch(&[1_000_000, 10_000_000_000], MIN_SQRT_PRICE_X64);
+
+        // A small swap barely moves the price and executes just below it, by the 0.3% fee
+        assert_eq!(quotes[0].price_impact_bps, 1);
+        assert!(quotes[0].effective_price_x64 < price_x64);
+        assert!(quotes[0].effective_price_x64 > price_x64 * 996 / 1000);
+
+        // A larger swap moves the price further and executes between the start & end prices
+        assert!(quotes[1].price_impact_bps > quotes[0].price_impact_bps);
+        assert!(quotes[1].effective_price_x64 < quotes[0].effective_price_x64);
+        let next_price_x64 = price_x64_from_sqrt_price_x64(quotes[1].next_sqrt_price).unwrap();
+        assert!(quotes[1].effective_price_x64 > next_price_x64);
+    }
+
+    #[test]
+    fn empty_batch() {
+        assert!(quote_batch(&[], MIN_SQRT_PRICE_X64).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod quote_two_hop_swap_tests {
+    use super::*;
+    use crate::util::test_utils::swap_test_fixture::*;
+
+    fn fixture(token_mint_a: Pubkey, token_mint_b: Pubkey, a_to_b: bool) -> SwapTestFixture {
+        let mut swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 1_000_000_000_000,
+            curr_tick_index: if a_to_b { 1000 } else { -1000 },
+            start_tick_index: if a_to_b { 0 } else { -11264 },
+            a_to_b,
+            fee_rate: 3000,
+            array_2_ticks: Some(&vec![]),
+            array_3_ticks: Some(&vec![]),
+            ..Default::default()
+        });
+        swap_test_info.whirlpool.token_mint_a = token_mint_a;
+        swap_test_info.whirlpool.token_mint_b = token_mint_b;
+        swap_test_info
+    }
+
+    fn quote(
+        pool_one: &SwapTestFixture,
+        a_to_b_one: bool,
+        pool_two: &SwapTestFixture,
+        a_to_b_two: bool,
+        intermediary_mint: Pubkey,
+        amount: u64,
+    ) -> Result<TwoHopSwapQuote, TwoHopSwapQuoteError> {
+        let mut tick_sequence_one = SwapTickSequence::new(
+            pool_one.tick_arrays[0].borrow_mut(),
+            Some(pool_one.tick_arrays[1].borrow_mut()),
+            Some(pool_one.tick_arrays[2].borrow_mut()),
+        );
+        let mut tick_sequence_two = SwapTickSequence::new(
+            pool_two.tick_arrays[0].borrow_mut(),
+            Some(pool_two.tick_arrays[1].borrow_mut()),
+            Some(pool_two.tick_arrays[2].borrow_mut()),
+        );
+        quote_two_hop_swap(
+            &pool_one.whirlpool,
+            &mut tick_sequence_one,
+            a_to_b_one,
+            &pool_two.whirlpool,
+            &mut tick_sequence_two,
+            a_to_b_two,
+            intermediary_mint,
+            amount,
+            100,
+            100,
+        )
+    }
+
+    #[test]
+    fn chains_the_output_of_hop_one_into_hop_two() {
+        let (mint_x, mint_y, mint_z) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        // x -> y on (x, y) swaps a to b, y -> z on (z, y) swaps b to a
+        let pool_one = fixture(mint_x, mint_y, true);
+        let pool_two = fixture(mint_z, mint_y, false);
+        let quote = quote(&pool_one, true, &pool_two, false, mint_y, 1_000_000).unwrap();
+
+        let single_hop = |pool: &SwapTestFixture, amount: u64, a_to_b: bool| {
+            let mut tick_sequence = SwapTickSequence::new(
+                pool.tick_arrays[0].borrow_mut(),
+                Some(pool.tick_arrays[1].borrow_mut()),
+                Some(pool.tick_arrays[2].borrow_mut()),
+            );
+            let sqrt_price_limit = if a_to_b {
+                MIN_SQRT_PRICE_X64
+            } else {
+                MAX_SQRT_PRICE_X64
+            };
+            swap(
+                &pool.whirlpool,
+                &mut tick_sequence,
+                amount,
+                sqrt_price_limit,
+                true,
+                a_to_b,
+                100,
+            )
+            .unwrap()
+        };
+        let hop_one = single_hop(&fixture(mint_x, mint_y, true), 1_000_000, true);
+        let hop_two = single_hop(&fixture(mint_z, mint_y, false), hop_one.amount_b, false);
+
+        assert_eq!(quote.amount_in, 1_000_000);
+        assert_eq!(quote.amount_intermediate, hop_one.amount_b);
+        assert_eq!(quote.amount_out, hop_two.amount_a);
+        assert_eq!(quote.minimum_amount_out, quote.amount_out * 10_000 / 10_100);
+        assert!(quote.price_impact_bps >= 2);
+    }
+
+    #[test]
+    fn rejects_mismatched_intermediary_mint() {
+        let (mint_x, mint_y, mint_z) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let pool_one = fixture(mint_x, mint_y, true);
+        let pool_two = fixture(mint_z, mint_y, false);
+
+        // The intermediary mint is not the output of hop one
+        assert_eq!(
+            quote(&pool_one, true, &pool_two, false, mint_x, 1_000_000).unwrap_err(),
+            TwoHopSwapQuoteError::InvalidIntermediaryMint
+        );
+        // Hop two spends z instead of the intermediary mint
+        assert_eq!(
+            quote(&pool_one, true, &pool_two, true, mint_y, 1_000_000).unwrap_err(),
+            TwoHopSwapQuoteError::InvalidIntermediaryMint
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_pool() {
+        let (mint_x, mint_y) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let pool_one = fixture(mint_x, mint_y, true);
+        let pool_two = fixture(mint_x, mint_y, false);
+        assert_eq!(
+            quote(&pool_one, true, &pool_two, false, mint_y, 1_000_000).unwrap_err(),
+            TwoHopSwapQuoteError::DuplicateTwoHopPool
+        );
+    }
+}
+
+#[cfg(test)]
+mod quote_exact_out_swap_tests {
+    use super::*;
+    use crate::util::test_utils::swap_test_fixture::*;
+
+    fn quote(
+        amount_out: u64,
+        sqrt_price_limit: Option<u128>,
+    ) -> Result<ExactOutSwapQuote, ErrorCode> {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 1_000_000_000_000,
+            curr_tick_index: 1000,
+            start_tick_index: 0,
+            a_to_b: true,
+            fee_rate: 3000,
+            array_2_ticks: Some(&vec![]),
+            array_3_ticks: Some(&vec![]),
+            ..Default::default()
+        });
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        quote_exact_out_swap(
+            &swap_test_info.whirlpool,
+            &mut tick_sequence,
+            amount_out,
+            sqrt_price_limit,
+            true,
+            100,
+        )
+    }
+
+    #[test]
+    fn token_in_includes_fee() {
+        let quote = quote(1_000_000, None).unwrap();
+        assert_eq!(quote.token_out, 1_000_000);
+        assert!(!quote.partial_fill);
+        // The fee is 0.3% of the input, charged on top of the amount swapped
+        let amount_swapped = (quote.token_in - quote.fee) as u128;
+        assert!(quote.fee > 0);
+        assert_eq!(
+            quote.fee as u128,
+            (amount_swapped * 3000 + 996_999) / 997_000
+        );
+    }
+
+    #[test]
+    fn reports_partial_fill_at_price_limit() {
+        let sqrt_price_limit = sqrt_price_from_tick_index(500);
+        let quote = quote(100_000_000_000, Some(sqrt_price_limit)).unwrap();
+        assert!(quote.partial_fill);
+        assert!(quote.token_out < 100_000_000_000);
+        assert!(quote.fee > 0);
+    }
+
+    #[test]
+    fn fails_without_price_limit_when_tick_arrays_run_out() {
+        // The tick arrays end at tick -22528, about 7.3e11 of token B below the current price
+        assert_eq!(
+            quote(10_000_000_000_000, None).unwrap_err(),
+            ErrorCode::TickArraySequenceInvalidIndex
+        );
+    }
+}
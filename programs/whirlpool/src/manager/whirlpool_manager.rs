@@ -1,7 +1,9 @@
 use crate::errors::ErrorCode;
-use crate::math::{add_liquidity_delta, checked_mul_div};
+use crate::math::{add_liquidity_delta, checked_mul_div, checked_mul_shift_right};
 use crate::state::*;
 
+pub const DAY_IN_SECONDS: u128 = 60 * 60 * 24;
+
 // Calculates the next global reward growth variables based on the given timestamp.
 // The provided timestamp must be greater than or equal to the last updated timestamp.
 pub fn next_whirlpool_reward_infos(
@@ -45,6 +47,13 @@ pub fn next_whirlpool_reward_infos(
     Ok(next_reward_infos)
 }
 
+// Calculates the amount of reward tokens emitted over a day at the given emission rate, rounded down.
+// A reward vault must hold at least this amount for the emission rate to be set.
+pub fn emissions_per_day(emissions_per_second_x64: u128) -> Result<u64, ErrorCode> {
+    // Fails with MultiplicationShiftRightOverflow if the product does not fit in a u128
+    checked_mul_shift_right(DAY_IN_SECONDS, emissions_per_second_x64)
+}
+
 // Calculates the next global liquidity for a whirlpool depending on its position relative
 // to the lower and upper tick indexes and the liquidity_delta.
 pub fn next_whirlpool_liquidity(
@@ -217,3 +226,48 @@ mod whirlpool_manager_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod emissions_per_day_tests {
+    use super::*;
+    use crate::math::{div_round_up, Q64_RESOLUTION};
+
+    #[test]
+    fn test_emissions_per_day_whole_tokens() {
+        assert_eq!(emissions_per_day(0).unwrap(), 0);
+        assert_eq!(emissions_per_day(1 << Q64_RESOLUTION).unwrap(), 86_400);
+        assert_eq!(emissions_per_day(10 << Q64_RESOLUTION).unwrap(), 864_000);
+        // 0.5 per second
+        assert_eq!(
+            emissions_per_day(1 << (Q64_RESOLUTION - 1)).unwrap(),
+            43_200
+        );
+    }
+
+    #[test]
+    fn test_emissions_per_day_rounds_down_at_daily_boundary() {
+        // The smallest rate emitting 1 token per day
+        let one_per_day = div_round_up(1 << Q64_RESOLUTION, DAY_IN_SECONDS).unwrap();
+        assert_eq!(emissions_per_day(one_per_day).unwrap(), 1);
+        assert_eq!(emissions_per_day(one_per_day - 1).unwrap(), 0);
+
+        // The smallest rate emitting 1_000_000 tokens per day
+        let million_per_day = div_round_up(1_000_000 << Q64_RESOLUTION, DAY_IN_SECONDS).unwrap();
+        assert_eq!(emissions_per_day(million_per_day).unwrap(), 1_000_000);
+        assert_eq!(emissions_per_day(million_per_day - 1).unwrap(), 999_999);
+    }
+
+    #[test]
+    fn test_emissions_per_day_max() {
+        let max_rate = u128::MAX / DAY_IN_SECONDS;
+        assert_eq!(emissions_per_day(max_rate).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_emissions_per_day_overflow() {
+        assert_eq!(
+            emissions_per_day(u128::MAX / DAY_IN_SECONDS + 1).unwrap_err(),
+            ErrorCode::MultiplicationShiftRightOverflow
+        );
+    }
+}
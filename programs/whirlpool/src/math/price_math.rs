@@ -0,0 +1,925 @@
+use crate::errors::ErrorCode;
+use crate::state::Tick;
+
+use super::{
+    checked_mul_div, mul_u256, sqrt_price_from_tick_index, MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64,
+    Q64_RESOLUTION, U256,
+};
+
+// Tolerances and shares of a whole, such as the split of a position's value, are expressed in
+// basis points
+pub const PRICE_TOLERANCE_BPS_MUL_VALUE: u128 = 10_000;
+
+// The conversions in this module only use integer math and do not allocate,
+// so they can be used in no_std / wasm environments without float support.
+
+/// Derive the price of token A in terms of token B from a sqrt-price.
+/// The price is not adjusted for the decimals of either token.
+///
+/// # Parameters
+/// - `sqrt_price_x64` - A u128 Q64.64 integer representing the sqrt-price
+///
+/// # Returns
+/// - `Ok`: A u128 Q64.64 integer representing the price
+/// - `NumberDownCastError`: The sqrt-price is above the supported max sqrt-price and the price does not fit in a u128
+pub fn price_x64_from_sqrt_price_x64(sqrt_price_x64: u128) -> Result<u128, ErrorCode> {
+    // Q64.64 * Q64.64 => Q128.128, shift back down to Q64.64
+    mul_u256(sqrt_price_x64, sqrt_price_x64)
+        .shift_right(Q64_RESOLUTION as u32)
+        .try_into_u128()
+}
+
+/// Derive the price of token A in terms of token B from a tick index.
+/// The price is not adjusted for the decimals of either token.
+///
+/// # Parameters
+/// - `tick` - A i32 integer representing the tick integer
+///
+/// # Returns
+/// - `Ok`: A u128 Q64.64 integer representing the price
+/// - `InvalidTickIndex`: The tick is outside of [MIN_TICK_INDEX, MAX_TICK_INDEX]
+pub fn price_x64_from_tick_index(tick: i32) -> Result<u128, ErrorCode> {
+    if Tick::check_is_out_of_bounds(tick) {
+        return Err(ErrorCode::InvalidTickIndex);
+    }
+    // MAX_SQRT_PRICE_X64 is below 2^96, so the squared value always fits in a Q64.64
+    price_x64_from_sqrt_price_x64(sqrt_price_from_tick_index(tick))
+}
+
+/// Derive the decimals-adjusted price of token A in terms of token B from a tick index.
+///
+/// # Parameters
+/// - `tick` - A i32 integer representing the tick integer
+/// - `decimals_a` - The number of decimals of token A
+/// - `decimals_b` - The number of decimals of token B
+///
+/// # Returns
+/// - `Ok`: A u128 Q64.64 integer representing the decimals-adjusted price
+/// - `InvalidTickIndex`: The tick is outside of [MIN_TICK_INDEX, MAX_TICK_INDEX]
+/// - `MultiplicationOverflow`: The adjusted price does not fit in a u128
+pub fn price_x64_from_tick_index_with_decimals(
+    tick: i32,
+    decimals_a: u8,
+    decimals_b: u8,
+) -> Result<u128, ErrorCode> {
+    adjust_price_x64_for_decimals(price_x64_from_tick_index(tick)?, decimals_a, decimals_b)
+}
+
+/// Derive the sqrt-price implied by a pair of target reserve amounts, for initializing a pool.
+/// The price of token A in terms of token B is `amount_b / amount_a`, using raw token amounts.
+///
+/// # Parameters
+/// - `amount_a` - The target reserve amount of token A
+/// - `amount_b` - The target reserve amount of token B
+///
+/// # Returns
+/// - `Ok`: A u128 Q64.64 integer representing the sqrt-price, rounded down
+/// - `DivideByZero`: The amount of token A is zero
+/// - `SqrtPriceOutOfBounds`: The implied sqrt-price is outside of [MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64]
+pub fn sqrt_price_x64_from_amounts(amount_a: u64, amount_b: u64) -> Result<u128, ErrorCode> {
+    if amount_a == 0 {
+        return Err(ErrorCode::DivideByZero);
+    }
+
+    // sqrt(amount_b / amount_a) * 2^64 = sqrt((amount_b << 128) / amount_a)
+    let price_x128 = (U256::from(amount_b) << 128) / U256::from(amount_a);
+    let sqrt_price_x64 = price_x128.integer_sqrt();
+
+    if sqrt_price_x64 < U256::from(MIN_SQRT_PRICE_X64)
+        || sqrt_price_x64 > U256::from(MAX_SQRT_PRICE_X64)
+    {
+        return Err(ErrorCode::SqrtPriceOutOfBounds);
+    }
+    Ok(sqrt_price_x64.as_u128())
+}
+
+#[derive(PartialEq, Debug)]
+pub struct PositionRangeBoundPrices {
+    // At or below these prices the position only holds token A
+    pub sqrt_price_lower_x64: u128,
+    pub price_lower_x64: u128,
+    // At or above these prices the position only holds token B
+    pub sqrt_price_upper_x64: u128,
+    pub price_upper_x64: u128,
+}
+
+/// Derive the prices at which a position goes fully out of range and becomes single-sided.
+/// The decimals-adjusted prices are provided for displaying alerts to users.
+///
+/// # Parameters
+/// - `tick_lower_index` - The lower tick index of the position
+/// - `tick_upper_index` - The upper tick index of the position
+/// - `decimals_a` - The number of decimals of token A
+/// - `decimals_b` - The number of decimals of token B
+///
+/// # Returns
+/// - `Ok`: The sqrt-prices & decimals-adjusted prices of the lower and upper bounds of the position
+/// - `InvalidTickIndex`: The tick indices are out of bounds or the lower tick index is not below the upper tick index
+/// - `MultiplicationOverflow`: An adjusted price does not fit in a u128
+pub fn position_range_bound_prices(
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    decimals_a: u8,
+    decimals_b: u8,
+) -> Result<PositionRangeBoundPrices, ErrorCode> {
+    if Tick::check_is_out_of_bounds(tick_lower_index)
+        || Tick::check_is_out_of_bounds(tick_upper_index)
+        || tick_lower_index >= tick_upper_index
+    {
+        return Err(ErrorCode::InvalidTickIndex);
+    }
+
+    Ok(PositionRangeBoundPrices {
+        sqrt_price_lower_x64: sqrt_price_from_tick_index(tick_lower_index),
+        price_lower_x64: price_x64_from_tick_index_with_decimals(
+            tick_lower_index,
+            decimals_a,
+            decimals_b,
+        )?,
+        sqrt_price_upper_x64: sqrt_price_from_tick_index(tick_upper_index),
+        price_upper_x64: price_x64_from_tick_index_with_decimals(
+            tick_upper_index,
+            decimals_a,
+            decimals_b,
+        )?,
+    })
+}
+
+/// Derive the value of each token held by a unit of liquidity in a position range, in terms of token B.
+/// The ratio of the two values is the value split of the position at the current price.
+///
+/// # Parameters
+/// - `sqrt_price_lower_x64` - The sqrt-price of the lower bound of the position
+/// - `sqrt_price_upper_x64` - The sqrt-price of the upper bound of the position
+/// - `sqrt_price_x64` - The current sqrt-price, clamped to the position range
+///
+/// # Returns
+/// - Q64.64 integers representing the value of token A & token B per unit of liquidity
+pub fn position_token_values_x64(
+    sqrt_price_lower_x64: u128,
+    sqrt_price_upper_x64: u128,
+    sqrt_price_x64: u128,
+) -> (u128, u128) {
+    let sqrt_price_x64 = sqrt_price_x64.clamp(sqrt_price_lower_x64, sqrt_price_upper_x64);
+
+    // value_a = (1 / sqrt_price - 1 / sqrt_price_upper) * price
+    // value_b = sqrt_price - sqrt_price_lower
+    // Both values are below MAX_SQRT_PRICE_X64, so they fit in a u128
+    let value_a = U256::from(sqrt_price_upper_x64 - sqrt_price_x64) * U256::from(sqrt_price_x64)
+        / U256::from(sqrt_price_upper_x64);
+    let value_b = sqrt_price_x64 - sqrt_price_lower_x64;
+
+    (value_a.as_u128(), value_b)
+}
+
+#[derive(PartialEq, Debug)]
+pub struct PositionTokenRatio {
+    pub token_a_bps: u16,
+    pub token_b_bps: u16,
+}
+
+/// Derive the split of a position's value between token A & token B at a price, in basis points.
+/// The position is fully in token A at or below its lower bound and fully in token B at or above
+/// its upper bound.
+///
+/// # Parameters
+/// - `tick_lower_index` - The lower tick index of the position
+/// - `tick_upper_index` - The upper tick index of the position
+/// - `sqrt_price_x64` - The sqrt-price to derive the split at
+///
+/// # Returns
+/// - `Ok`: The share of the position value held in each token, summing to 10_000 bps
+/// - `InvalidTickIndex`: The tick indexes are out of bounds or the lower index is not below the upper index
+pub fn position_token_ratio(
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    sqrt_price_x64: u128,
+) -> Result<PositionTokenRatio, ErrorCode> {
+    if Tick::check_is_out_of_bounds(tick_lower_index)
+        || Tick::check_is_out_of_bounds(tick_upper_index)
+        || tick_lower_index >= tick_upper_index
+    {
+        return Err(ErrorCode::InvalidTickIndex);
+    }
+
+    let (value_a, value_b) = position_token_values_x64(
+        sqrt_price_from_tick_index(tick_lower_index),
+        sqrt_price_from_tick_index(tick_upper_index),
+        sqrt_price_x64,
+    );
+
+    // Round to the nearest basis point
+    let total = U256::from(value_a) + U256::from(value_b);
+    let token_a_bps = (U256::from(value_a) * U256::from(PRICE_TOLERANCE_BPS_MUL_VALUE)
+        + total / U256::from(2))
+        / total;
+    let token_a_bps = token_a_bps.as_u32() as u16;
+
+    Ok(PositionTokenRatio {
+        token_a_bps,
+        token_b_bps: PRICE_TOLERANCE_BPS_MUL_VALUE as u16 - token_a_bps,
+    })
+}
+
+//
+// Adjust a raw price for the decimals of the token pair
+//
+// price_adjusted = price_raw * 10^(decimals_a - decimals_b)
+pub fn adjust_price_x64_for_decimals(
+    price_x64: u128,
+    decimals_a: u8,
+    decimals_b: u8,
+) -> Result<u128, ErrorCode> {
+    if decimals_a >= decimals_b {
+        let factor = pow_10(decimals_a - decimals_b)?;
+        price_x64
+            .checked_mul(factor)
+            .ok_or(ErrorCode::MultiplicationOverflow)
+    } else {
+        let factor = pow_10(decimals_b - decimals_a)?;
+        Ok(price_x64 / factor)
+    }
+}
+
+/// Check that a price stayed within the bounds expected when a swap was simulated.
+/// The bounds span the pre-swap & expected post-swap prices, widened by `tolerance_bps` on each side.
+///
+/// # Parameters
+/// - `pre_swap_sqrt_price_x64` - The sqrt-price of the whirlpool when the swap was simulated
+/// - `expected_post_swap_sqrt_price_x64` - The sqrt-price the whirlpool is expected to reach after the swap
+/// - `sqrt_price_x64` - The sqrt-price to check
+/// - `tolerance_bps` - The allowed price drift outside of the expected range, in basis points
+///
+/// # Returns
+/// - `Ok`: true if the price is within bounds, false otherwise
+/// - `NumberDownCastError`: One of the sqrt-prices is above the supported max sqrt-price
+pub fn is_price_within_swap_bounds(
+    pre_swap_sqrt_price_x64: u128,
+    expected_post_swap_sqrt_price_x64: u128,
+    sqrt_price_x64: u128,
+    tolerance_bps: u16,
+) -> Result<bool, ErrorCode> {
+    let pre_swap_price_x64 = price_x64_from_sqrt_price_x64(pre_swap_sqrt_price_x64)?;
+    let post_swap_price_x64 = price_x64_from_sqrt_price_x64(expected_post_swap_sqrt_price_x64)?;
+    let price_x64 = price_x64_from_sqrt_price_x64(sqrt_price_x64)?;
+
+    let tolerance = tolerance_bps as u128;
+    let lower_bound = checked_mul_div(
+        pre_swap_price_x64.min(post_swap_price_x64),
+        PRICE_TOLERANCE_BPS_MUL_VALUE.saturating_sub(tolerance),
+        PRICE_TOLERANCE_BPS_MUL_VALUE,
+    )?;
+    // An upper bound above u128::MAX cannot be exceeded by any price
+    let upper_bound = checked_mul_div(
+        pre_swap_price_x64.max(post_swap_price_x64),
+        PRICE_TOLERANCE_BPS_MUL_VALUE + tolerance,
+        PRICE_TOLERANCE_BPS_MUL_VALUE,
+    )
+    .unwrap_or(u128::MAX);
+
+    Ok(price_x64 >= lower_bound && price_x64 <= upper_bound)
+}
+
+//
+// Price impact of a swap in basis points, rounded up
+//
+// impact = |price_after - price_before| / price_before
+//        = |sqrt_price_after^2 - sqrt_price_before^2| / sqrt_price_before^2
+pub fn price_impact_bps(sqrt_price_before_x64: u128, sqrt_price_after_x64: u128) -> u32 {
+    let price_before = U256::from(sqrt_price_before_x64) * U256::from(sqrt_price_before_x64);
+    let price_after = U256::from(sqrt_price_after_x64) * U256::from(sqrt_price_after_x64);
+    if price_before.is_zero() {
+        return u32::MAX;
+    }
+
+    let price_delta = if price_after > price_before {
+        price_after - price_before
+    } else {
+        price_before - price_after
+    };
+    // Any delta large enough to overflow is far above u32::MAX bps
+    let numerator = match price_delta.checked_mul(U256::from(PRICE_TOLERANCE_BPS_MUL_VALUE)) {
+        Some(numerator) => numerator,
+        None => return u32::MAX,
+    };
+    let (quotient, remainder) = numerator.div_mod(price_before);
+    let impact = if remainder.is_zero() {
+        quotient
+    } else {
+        quotient + U256::one()
+    };
+
+    if impact > U256::from(u32::MAX) {
+        u32::MAX
+    } else {
+        impact.as_u32()
+    }
+}
+
+/// Convert an amount of one token of a pool into the other at the spot price, without fees or price impact.
+/// The amount is scaled down by the decimals of the input token, converted at the decimals-adjusted price
+/// (see `adjust_price_x64_for_decimals`) and scaled back up by the decimals of the output token.
+///
+/// # Parameters
+/// - `amount` - The amount of the input token
+/// - `sqrt_price_x64` - A u128 Q64.64 integer representing the sqrt-price of the pool
+/// - `decimals_a` - The decimals of token A
+/// - `decimals_b` - The decimals of token B
+/// - `a_to_b` - If true, convert an amount of token A into token B. Otherwise, convert token B into token A.
+///
+/// # Returns
+/// - `Ok`: The equivalent amount of the output token, rounded down
+/// - `DivideByZero`: The adjusted price is zero and an amount of token B is converted
+/// - `MultiplicationOverflow`: The decimals are too large to scale the price or the amounts
+/// - `NumberDownCastError`: The equivalent amount does not fit in a u64
+pub fn convert_amount_at_price(
+    amount: u64,
+    sqrt_price_x64: u128,
+    decimals_a: u8,
+    decimals_b: u8,
+    a_to_b: bool,
+) -> Result<u64, ErrorCode> {
+    let factor_a = U256::from(pow_10(decimals_a)?);
+    let factor_b = U256::from(pow_10(decimals_b)?);
+
+    // Q64.64 * Q64.64 => Q128.128, adjusted by 10^(decimals_a - decimals_b)
+    let price_x128 = U256::from(sqrt_price_x64) * U256::from(sqrt_price_x64);
+    let adjusted_price_x128 = price_x128
+        .checked_mul(factor_a)
+        .ok_or(ErrorCode::MultiplicationOverflow)?
+        / factor_b;
+
+    let converted = if a_to_b {
+        // amount_b = amount_a / 10^decimals_a * adjusted_price * 10^decimals_b
+        let product = match U256::from(amount).checked_mul(adjusted_price_x128) {
+            Some(product) => product,
+            None => return Err(ErrorCode::NumberDownCastError),
+        };
+        (product
+            .checked_mul(factor_b)
+            .ok_or(ErrorCode::MultiplicationOverflow)?
+            / factor_a)
+            >> 128
+    } else {
+        // amount_a = amount_b / 10^decimals_b / adjusted_price * 10^decimals_a
+        let denominator = adjusted_price_x128
+            .checked_mul(factor_b)
+            .ok_or(ErrorCode::MultiplicationOverflow)?;
+        if denominator.is_zero() {
+            return Err(ErrorCode::DivideByZero);
+        }
+        (U256::from(amount) << 128)
+            .checked_mul(factor_a)
+            .ok_or(ErrorCode::MultiplicationOverflow)?
+            / denominator
+    };
+
+    if converted > U256::from(u64::MAX) {
+        return Err(ErrorCode::NumberDownCastError);
+    }
+    Ok(converted.as_u64())
+}
+
+fn pow_10(exponent: u8) -> Result<u128, ErrorCode> {
+    10u128
+        .checked_pow(exponent as u32)
+        .ok_or(ErrorCode::MultiplicationOverflow)
+}
+
+#[cfg(test)]
+mod price_x64_from_tick_index_tests {
+    use super::*;
+    use crate::math::{MAX_SQRT_PRICE_X64, TO_Q64};
+    use crate::state::{MAX_TICK_INDEX, MIN_TICK_INDEX};
+
+    fn float_price(tick: i32, decimals_a: u8, decimals_b: u8) -> f64 {
+        1.0001f64.powi(tick) * 10f64.powi(decimals_a as i32 - decimals_b as i32)
+    }
+
+    fn assert_close_to_float_price(price_x64: u128, expected: f64) {
+        let actual = price_x64 as f64 / TO_Q64 as f64;
+        let relative_error = ((actual - expected) / expected).abs();
+        assert!(
+            relative_error < 1e-9,
+            "actual {} expected {} (relative error {})",
+            actual,
+            expected,
+            relative_error
+        );
+    }
+
+    #[test]
+    fn test_price_at_zero_tick() {
+        assert_eq!(price_x64_from_tick_index(0).unwrap(), TO_Q64);
+    }
+
+    #[test]
+    fn test_price_matches_float_path() {
+        for tick in [
+            -200_000, -100_000, -22_000, -1, 1, 64, 22_000, 100_000, 200_000, 300_000,
+        ] {
+            assert_close_to_float_price(
+                price_x64_from_tick_index(tick).unwrap(),
+                float_price(tick, 0, 0),
+            );
+        }
+    }
+
+    #[test]
+    fn test_decimals_adjusted_price_matches_float_path() {
+        for (tick, decimals_a, decimals_b) in [
+            (-39_104, 9, 6), // ~SOL/USDC
+            (-39_104, 6, 9),
+            (25_000, 8, 8),
+            (100_000, 0, 12),
+            (-100_000, 12, 0),
+        ] {
+            let price_x64 =
+                price_x64_from_tick_index_with_decimals(tick, decimals_a, decimals_b).unwrap();
+            assert_close_to_float_price(price_x64, float_price(tick, decimals_a, decimals_b));
+        }
+    }
+
+    #[test]
+    fn test_price_at_tick_bounds() {
+        let max_price = price_x64_from_tick_index(MAX_TICK_INDEX).unwrap();
+        let min_price = price_x64_from_tick_index(MIN_TICK_INDEX).unwrap();
+        assert!(max_price > price_x64_from_tick_index(MAX_TICK_INDEX - 1).unwrap());
+        assert!(min_price <= price_x64_from_tick_index(MIN_TICK_INDEX + 1).unwrap());
+    }
+
+    #[test]
+    fn test_price_out_of_tick_bounds() {
+        for tick in [MAX_TICK_INDEX + 1, MIN_TICK_INDEX - 1, i32::MAX, i32::MIN] {
+            assert_eq!(
+                price_x64_from_tick_index(tick).unwrap_err(),
+                ErrorCode::InvalidTickIndex
+            );
+            assert_eq!(
+                price_x64_from_tick_index_with_decimals(tick, 9, 6).unwrap_err(),
+                ErrorCode::InvalidTickIndex
+            );
+        }
+    }
+
+    #[test]
+    fn test_decimals_adjusted_price_overflow() {
+        let result = price_x64_from_tick_index_with_decimals(MAX_TICK_INDEX, 18, 0);
+        assert_eq!(result.unwrap_err(), ErrorCode::MultiplicationOverflow);
+    }
+
+    #[test]
+    fn test_price_from_sqrt_price_above_max() {
+        assert!(price_x64_from_sqrt_price_x64(MAX_SQRT_PRICE_X64).is_ok());
+        assert_eq!(
+            price_x64_from_sqrt_price_x64(u128::MAX).unwrap_err(),
+            ErrorCode::NumberDownCastError
+        );
+    }
+}
+
+#[cfg(test)]
+mod position_range_bound_prices_tests {
+    use super::*;
+    use crate::math::{
+        get_amount_delta_a, get_amount_delta_b, MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64,
+    };
+    use crate::state::{MAX_TICK_INDEX, MIN_TICK_INDEX};
+
+    #[test]
+    fn test_prices_match_tick_bounds() {
+        let prices = position_range_bound_prices(-39_424, -38_400, 9, 6).unwrap();
+        assert_eq!(
+            prices,
+            PositionRangeBoundPrices {
+                sqrt_price_lower_x64: sqrt_price_from_tick_index(-39_424),
+                price_lower_x64: price_x64_from_tick_index_with_decimals(-39_424, 9, 6).unwrap(),
+                sqrt_price_upper_x64: sqrt_price_from_tick_index(-38_400),
+                price_upper_x64: price_x64_from_tick_index_with_decimals(-38_400, 9, 6).unwrap(),
+            }
+        );
+        assert!(prices.price_lower_x64 < prices.price_upper_x64);
+    }
+
+    #[test]
+    fn test_position_is_single_sided_at_bounds() {
+        let prices = position_range_bound_prices(-128, 256, 6, 6).unwrap();
+        let liquidity = 1_000_000_000;
+
+        // At the lower bound, the position holds no token B and all of its token A
+        let (lower, upper) = (prices.sqrt_price_lower_x64, prices.sqrt_price_upper_x64);
+        assert_eq!(
+            get_amount_delta_b(lower, lower, liquidity, true).unwrap(),
+            0
+        );
+        assert!(get_amount_delta_a(lower, upper, liquidity, true).unwrap() > 0);
+
+        // At the upper bound, the position holds no token A and all of its token B
+        assert_eq!(
+            get_amount_delta_a(upper, upper, liquidity, true).unwrap(),
+            0
+        );
+        assert!(get_amount_delta_b(lower, upper, liquidity, true).unwrap() > 0);
+    }
+
+    #[test]
+    fn test_full_range_bounds() {
+        let prices = position_range_bound_prices(MIN_TICK_INDEX, MAX_TICK_INDEX, 0, 0).unwrap();
+        assert_eq!(prices.sqrt_price_lower_x64, MIN_SQRT_PRICE_X64);
+        assert_eq!(prices.sqrt_price_upper_x64, MAX_SQRT_PRICE_X64);
+    }
+
+    #[test]
+    fn test_invalid_tick_indices() {
+        for (tick_lower_index, tick_upper_index) in [
+            (64, 64),
+            (128, 64),
+            (MIN_TICK_INDEX - 1, 0),
+            (0, MAX_TICK_INDEX + 1),
+        ] {
+            assert_eq!(
+                position_range_bound_prices(tick_lower_index, tick_upper_index, 6, 6).unwrap_err(),
+                ErrorCode::InvalidTickIndex
+            );
+        }
+    }
+
+    #[test]
+    fn test_adjusted_price_overflow() {
+        assert_eq!(
+            position_range_bound_prices(0, MAX_TICK_INDEX, 18, 0).unwrap_err(),
+            ErrorCode::MultiplicationOverflow
+        );
+    }
+}
+
+#[cfg(test)]
+mod is_price_within_swap_bounds_tests {
+    use super::*;
+    use crate::math::{MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64};
+
+    #[test]
+    fn test_price_within_expected_range() {
+        let pre = sqrt_price_from_tick_index(0);
+        let post = sqrt_price_from_tick_index(-100);
+        let mid = sqrt_price_from_tick_index(-50);
+        assert!(is_price_within_swap_bounds(pre, post, pre, 0).unwrap());
+        assert!(is_price_within_swap_bounds(pre, post, post, 0).unwrap());
+        assert!(is_price_within_swap_bounds(pre, post, mid, 0).unwrap());
+    }
+
+    #[test]
+    fn test_price_drift_within_tolerance() {
+        let pre = sqrt_price_from_tick_index(0);
+        let post = sqrt_price_from_tick_index(100);
+        // Each tick is ~1bp of price
+        let above = sqrt_price_from_tick_index(140);
+        let below = sqrt_price_from_tick_index(-40);
+        assert!(is_price_within_swap_bounds(pre, post, above, 50).unwrap());
+        assert!(is_price_within_swap_bounds(pre, post, below, 50).unwrap());
+    }
+
+    #[test]
+    fn test_price_drift_out_of_tolerance() {
+        let pre = sqrt_price_from_tick_index(0);
+        let post = sqrt_price_from_tick_index(100);
+        let above = sqrt_price_from_tick_index(160);
+        let below = sqrt_price_from_tick_index(-60);
+        assert!(!is_price_within_swap_bounds(pre, post, above, 50).unwrap());
+        assert!(!is_price_within_swap_bounds(pre, post, below, 50).unwrap());
+        assert!(!is_price_within_swap_bounds(pre, pre, pre + (1 << 32), 0).unwrap());
+    }
+
+    #[test]
+    fn test_tolerance_at_price_bounds() {
+        assert!(is_price_within_swap_bounds(
+            MAX_SQRT_PRICE_X64,
+            MAX_SQRT_PRICE_X64,
+            MAX_SQRT_PRICE_X64,
+            u16::MAX
+        )
+        .unwrap());
+        assert!(is_price_within_swap_bounds(
+            MIN_SQRT_PRICE_X64,
+            MIN_SQRT_PRICE_X64,
+            MIN_SQRT_PRICE_X64,
+            u16::MAX
+        )
+        .unwrap());
+        assert_eq!(
+            is_price_within_swap_bounds(u128::MAX, MIN_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64, 0)
+                .unwrap_err(),
+            ErrorCode::NumberDownCastError
+        );
+    }
+}
+
+#[cfg(test)]
+mod price_impact_bps_tests {
+    use super::*;
+    use crate::math::{MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64};
+
+    #[test]
+    fn test_no_price_movement() {
+        let sqrt_price = sqrt_price_from_tick_index(1000);
+        assert_eq!(price_impact_bps(sqrt_price, sqrt_price), 0);
+    }
+
+    #[test]
+    fn test_price_movement_in_both_directions() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        // 1.0001^100 ~ 1.01005, 1.0001^-100 ~ 0.99005
+        assert_eq!(
+            price_impact_bps(sqrt_price, sqrt_price_from_tick_index(100)),
+            101
+        );
+        assert_eq!(
+            price_impact_bps(sqrt_price, sqrt_price_from_tick_index(-100)),
+            100
+        );
+    }
+
+    #[test]
+    fn test_price_impact_rounds_up() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        assert_eq!(price_impact_bps(sqrt_price, sqrt_price + 1), 1);
+    }
+
+    #[test]
+    fn test_price_impact_saturates() {
+        assert_eq!(
+            price_impact_bps(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64),
+            u32::MAX
+        );
+        assert_eq!(price_impact_bps(0, MAX_SQRT_PRICE_X64), u32::MAX);
+    }
+}
+
+#[cfg(test)]
+mod convert_amount_at_price_tests {
+    use super::*;
+    use crate::math::{MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64};
+
+    #[test]
+    fn test_price_of_one() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        assert_eq!(
+            convert_amount_at_price(1_000_000, sqrt_price, 0, 0, true),
+            Ok(1_000_000)
+        );
+        assert_eq!(
+            convert_amount_at_price(1_000_000, sqrt_price, 0, 0, false),
+            Ok(1_000_000)
+        );
+    }
+
+    #[test]
+    fn test_both_directions() {
+        // price = 4
+        let sqrt_price = 2u128 << 64;
+        assert_eq!(
+            convert_amount_at_price(1_000, sqrt_price, 0, 0, true),
+            Ok(4_000)
+        );
+        assert_eq!(
+            convert_amount_at_price(4_000, sqrt_price, 0, 0, false),
+            Ok(1_000)
+        );
+        // price = 0.25
+        let sqrt_price = 1u128 << 63;
+        assert_eq!(
+            convert_amount_at_price(1_000, sqrt_price, 0, 0, true),
+            Ok(250)
+        );
+        assert_eq!(
+            convert_amount_at_price(250, sqrt_price, 0, 0, false),
+            Ok(1_000)
+        );
+    }
+
+    #[test]
+    fn test_more_decimals_for_a() {
+        // 150 B per A with 9 decimals for A and 6 decimals for B => raw price of 0.15
+        let sqrt_price = 7144393258922745604;
+        // 1 A => ~150 B (the sqrt-price is rounded down)
+        assert_eq!(
+            convert_amount_at_price(1_000_000_000, sqrt_price, 9, 6, true),
+            Ok(149_999_999)
+        );
+        // 150 B => 1 A
+        assert_eq!(
+            convert_amount_at_price(150_000_000, sqrt_price, 9, 6, false),
+            Ok(1_000_000_000)
+        );
+    }
+
+    #[test]
+    fn test_more_decimals_for_b() {
+        // 0.01 B per A with 6 decimals for A and 9 decimals for B => raw price of 10
+        let sqrt_price = 58333726687135158848;
+        // 1 A => ~0.01 B (the sqrt-price is rounded down)
+        assert_eq!(
+            convert_amount_at_price(1_000_000, sqrt_price, 6, 9, true),
+            Ok(9_999_999)
+        );
+        // 0.01 B => 1 A
+        assert_eq!(
+            convert_amount_at_price(10_000_000, sqrt_price, 6, 9, false),
+            Ok(1_000_000)
+        );
+    }
+
+    #[test]
+    fn test_decimals_overflow() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        assert_eq!(
+            convert_amount_at_price(1, sqrt_price, 39, 0, true),
+            Err(ErrorCode::MultiplicationOverflow)
+        );
+        assert_eq!(
+            convert_amount_at_price(1, sqrt_price, 0, 39, false),
+            Err(ErrorCode::MultiplicationOverflow)
+        );
+    }
+
+    #[test]
+    fn test_rounds_down() {
+        // price = 4
+        let sqrt_price = 2u128 << 64;
+        assert_eq!(convert_amount_at_price(3, sqrt_price, 0, 0, false), Ok(0));
+        assert_eq!(convert_amount_at_price(7, sqrt_price, 0, 0, false), Ok(1));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(
+            convert_amount_at_price(u64::MAX, MAX_SQRT_PRICE_X64, 0, 0, true),
+            Err(ErrorCode::NumberDownCastError)
+        );
+        assert_eq!(
+            convert_amount_at_price(u64::MAX, MIN_SQRT_PRICE_X64, 0, 0, false),
+            Err(ErrorCode::NumberDownCastError)
+        );
+        assert_eq!(
+            convert_amount_at_price(1, 0, 0, 0, false),
+            Err(ErrorCode::DivideByZero)
+        );
+        assert_eq!(convert_amount_at_price(u64::MAX, 0, 0, 0, true), Ok(0));
+    }
+}
+
+#[cfg(test)]
+mod sqrt_price_x64_from_amounts_tests {
+    use super::*;
+    use crate::math::tick_index_from_sqrt_price;
+
+    #[test]
+    fn test_exact_prices() {
+        assert_eq!(sqrt_price_x64_from_amounts(1_000, 1_000), Ok(1 << 64));
+        assert_eq!(sqrt_price_x64_from_amounts(1_000, 4_000), Ok(2 << 64));
+        assert_eq!(sqrt_price_x64_from_amounts(4_000, 1_000), Ok(1 << 63));
+    }
+
+    #[test]
+    fn test_round_trip_through_price() {
+        // 1 A (9 decimals) = 25 B (6 decimals) => raw price of 0.025
+        let sqrt_price = sqrt_price_x64_from_amounts(1_000_000_000, 25_000_000).unwrap();
+        let price_x64 = price_x64_from_sqrt_price_x64(sqrt_price).unwrap();
+        let expected_price_x64 = (1u128 << 64) / 40;
+        assert!(expected_price_x64 - price_x64 <= 1);
+
+        let adjusted_price_x64 = adjust_price_x64_for_decimals(price_x64, 9, 6).unwrap();
+        let expected_adjusted_price_x64 = 25u128 << 64;
+        assert!(expected_adjusted_price_x64 - adjusted_price_x64 < 1 << 16);
+    }
+
+    #[test]
+    fn test_round_trip_through_tick() {
+        let amount_a = 1_000_000_000u64;
+        for tick in [-50_000, -1_000, -1, 0, 1, 1_000, 50_000] {
+            let price_x64 = price_x64_from_tick_index(tick).unwrap();
+            let amount_b = ((price_x64 * amount_a as u128) >> 64) as u64;
+
+            let sqrt_price = sqrt_price_x64_from_amounts(amount_a, amount_b).unwrap();
+            // The amounts are rounded down, so the price may land just below the tick
+            let tick_index = tick_index_from_sqrt_price(&sqrt_price);
+            assert!(tick_index == tick || tick_index == tick - 1);
+        }
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        assert_eq!(
+            sqrt_price_x64_from_amounts(u64::MAX, 1),
+            Err(ErrorCode::SqrtPriceOutOfBounds)
+        );
+        assert_eq!(
+            sqrt_price_x64_from_amounts(1, u64::MAX),
+            Err(ErrorCode::SqrtPriceOutOfBounds)
+        );
+        assert_eq!(
+            sqrt_price_x64_from_amounts(1_000, 0),
+            Err(ErrorCode::SqrtPriceOutOfBounds)
+        );
+        assert_eq!(
+            sqrt_price_x64_from_amounts(0, 1_000),
+            Err(ErrorCode::DivideByZero)
+        );
+    }
+}
+
+#[cfg(test)]
+mod position_token_ratio_tests {
+    use super::*;
+    use crate::state::{MAX_TICK_INDEX, MIN_TICK_INDEX};
+
+    fn ratio(token_a_bps: u16, token_b_bps: u16) -> PositionTokenRatio {
+        PositionTokenRatio {
+            token_a_bps,
+            token_b_bps,
+        }
+    }
+
+    #[test]
+    fn test_boundaries() {
+        // At or below the lower bound the position is fully in token A
+        for tick in [-1000, -200, -100] {
+            let sqrt_price = sqrt_price_from_tick_index(tick);
+            assert_eq!(
+                position_token_ratio(-100, 100, sqrt_price),
+                Ok(ratio(10_000, 0))
+            );
+        }
+        // At or above the upper bound the position is fully in token B
+        for tick in [100, 200, 1000] {
+            let sqrt_price = sqrt_price_from_tick_index(tick);
+            assert_eq!(
+                position_token_ratio(-100, 100, sqrt_price),
+                Ok(ratio(0, 10_000))
+            );
+        }
+    }
+
+    #[test]
+    fn test_symmetric_range_is_balanced_at_center() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        assert_eq!(
+            position_token_ratio(-1000, 1000, sqrt_price),
+            Ok(ratio(5000, 5000))
+        );
+    }
+
+    #[test]
+    fn test_ratio_shifts_across_range() {
+        let mut prev_token_a_bps = 10_000;
+        for tick in (-2000..=2000).step_by(250) {
+            let sqrt_price = sqrt_price_from_tick_index(tick);
+            let split = position_token_ratio(-2000, 2000, sqrt_price).unwrap();
+            assert_eq!(split.token_a_bps + split.token_b_bps, 10_000);
+            // The share of token A decreases as the price increases
+            assert!(split.token_a_bps <= prev_token_a_bps);
+            prev_token_a_bps = split.token_a_bps;
+        }
+        assert_eq!(prev_token_a_bps, 0);
+    }
+
+    #[test]
+    fn test_known_split() {
+        // Range [1, ~4] (ticks 0 & 13863) at a price of ~2:
+        // value_a = (1/sqrt(2) - 1/2) * 2 ~ 0.4142, value_b = sqrt(2) - 1 ~ 0.4142
+        let sqrt_price = sqrt_price_from_tick_index(6931); // ~ sqrt(2)
+        assert_eq!(
+            position_token_ratio(0, 13863, sqrt_price),
+            Ok(ratio(5000, 5000))
+        );
+
+        // Range [1, ~4] at a price of 1.5625 (sqrt = 1.25):
+        // value_a = (0.8 - 0.5) * 1.5625 = 0.46875, value_b = 0.25 => ~65.22% A
+        let sqrt_price = (5u128 << 64) / 4;
+        assert_eq!(
+            position_token_ratio(0, 13863, sqrt_price),
+            Ok(ratio(6522, 3478))
+        );
+
+        // The exact values for a range of [1, 4]
+        let (value_a, value_b) = position_token_values_x64(1u128 << 64, 2u128 << 64, sqrt_price);
+        assert_eq!(value_a, (15u128 << 64) / 32);
+        assert_eq!(value_b, 1u128 << 62);
+    }
+
+    #[test]
+    fn test_invalid_tick_indexes() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        for (tick_lower_index, tick_upper_index) in [
+            (100, 100),
+            (100, -100),
+            (MIN_TICK_INDEX - 1, 0),
+            (0, MAX_TICK_INDEX + 1),
+        ] {
+            assert_eq!(
+                position_token_ratio(tick_lower_index, tick_upper_index, sqrt_price),
+                Err(ErrorCode::InvalidTickIndex)
+            );
+        }
+    }
+}
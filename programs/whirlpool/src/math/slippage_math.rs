@@ -0,0 +1,66 @@
+use crate::errors::ErrorCode;
+use std::convert::TryFrom;
+
+use super::PRICE_TOLERANCE_BPS_MUL_VALUE;
+
+// Slippage tolerance is represented as a basis point, and adjusts amounts the same way as the SDK
+// quotes:
+// min = amount * 10_000 / (10_000 + slippage_bps)
+// max = amount * (10_000 + slippage_bps) / 10_000
+
+/// Get the minimum amount to accept for an estimated amount within the slippage tolerance.
+///
+/// # Parameters
+/// - `amount` - The estimated amount
+/// - `slippage_bps` - The slippage tolerance, in basis points
+///
+/// # Returns
+/// - `u64`: The minimum amount, rounded down
+pub fn get_min_amount_with_slippage(amount: u64, slippage_bps: u16) -> u64 {
+    (amount as u128 * PRICE_TOLERANCE_BPS_MUL_VALUE
+        / (PRICE_TOLERANCE_BPS_MUL_VALUE + slippage_bps as u128)) as u64
+}
+
+/// Get the maximum amount to pay for an estimated amount within the slippage tolerance.
+///
+/// # Parameters
+/// - `amount` - The estimated amount
+/// - `slippage_bps` - The slippage tolerance, in basis points
+///
+/// # Returns
+/// - `Ok`: The maximum amount, rounded down
+/// - `TokenMaxExceeded`: The maximum amount does not fit in a u64
+pub fn get_max_amount_with_slippage(amount: u64, slippage_bps: u16) -> Result<u64, ErrorCode> {
+    u64::try_from(
+        amount as u128 * (PRICE_TOLERANCE_BPS_MUL_VALUE + slippage_bps as u128)
+            / PRICE_TOLERANCE_BPS_MUL_VALUE,
+    )
+    .map_err(|_| ErrorCode::TokenMaxExceeded)
+}
+
+#[cfg(test)]
+mod test_slippage_math {
+    use super::*;
+
+    #[test]
+    fn test_min_amount() {
+        assert_eq!(get_min_amount_with_slippage(1_000_000, 0), 1_000_000);
+        assert_eq!(get_min_amount_with_slippage(1_010_000, 100), 1_000_000);
+        // Rounded down
+        assert_eq!(get_min_amount_with_slippage(1_000_000, 100), 990_099);
+        assert_eq!(
+            get_min_amount_with_slippage(u64::MAX, u16::MAX),
+            2_442_145_240_446_091_429
+        );
+    }
+
+    #[test]
+    fn test_max_amount() {
+        assert_eq!(get_max_amount_with_slippage(1_000_000, 0), Ok(1_000_000));
+        assert_eq!(get_max_amount_with_slippage(1_000_000, 100), Ok(1_010_000));
+        assert_eq!(
+            get_max_amount_with_slippage(u64::MAX, 1),
+            Err(ErrorCode::TokenMaxExceeded)
+        );
+    }
+}
@@ -2,6 +2,7 @@ use std::convert::TryInto;
 
 use crate::errors::ErrorCode;
 use crate::math::*;
+use crate::state::Tick;
 
 #[derive(PartialEq, Debug)]
 pub struct SwapStepComputation {
@@ -103,6 +104,149 @@ pub fn compute_swap(
     })
 }
 
+/// Compute the swap step that moves the price exactly onto the sqrt-price of a target tick.
+///
+/// This is a single step of the swap loop with an exact input large enough to reach the target,
+/// so the returned input is the minimum amount that brings the price onto the tick.
+///
+/// # Parameters
+/// - `fee_rate` - The fee rate of the whirlpool
+/// - `liquidity` - The liquidity active between the current price and the target tick
+/// - `sqrt_price_current` - The current sqrt-price of the whirlpool
+/// - `tick_index_target` - The tick index to move the price to, usually the next initialized tick
+/// - `a_to_b` - The direction of the swap
+///
+/// # Returns
+/// - `Ok`: A SwapStepComputation landing on the sqrt-price of `tick_index_target`, where
+///   `amount_in + fee_amount` is the fee-inclusive input
+/// - `InvalidTickIndex`: The target tick index is out of bounds
+/// - `InvalidSqrtPriceLimitDirection`: The target tick is on the wrong side of the current price
+/// - `AmountRemainingOverflow`: The input required to reach the target exceeds u64::MAX
+pub fn compute_swap_to_tick(
+    fee_rate: u16,
+    liquidity: u128,
+    sqrt_price_current: u128,
+    tick_index_target: i32,
+    a_to_b: bool,
+) -> Result<SwapStepComputation, ErrorCode> {
+    if Tick::check_is_out_of_bounds(tick_index_target) {
+        return Err(ErrorCode::InvalidTickIndex);
+    }
+
+    let sqrt_price_target = sqrt_price_from_tick_index(tick_index_target);
+    if (a_to_b && sqrt_price_target > sqrt_price_current)
+        || (!a_to_b && sqrt_price_target < sqrt_price_current)
+    {
+        return Err(ErrorCode::InvalidSqrtPriceLimitDirection);
+    }
+
+    let swap_computation = compute_swap(
+        u64::MAX,
+        fee_rate,
+        liquidity,
+        sqrt_price_current,
+        sqrt_price_target,
+        true,
+        a_to_b,
+    )
+    .map_err(|_| ErrorCode::AmountRemainingOverflow)?;
+
+    if swap_computation.next_price != sqrt_price_target {
+        return Err(ErrorCode::AmountRemainingOverflow);
+    }
+
+    Ok(swap_computation)
+}
+
+#[derive(PartialEq, Debug)]
+pub struct RebalanceSwap {
+    pub amount_in: u64,
+    pub a_to_b: bool,
+}
+
+/// Compute the minimal swap that brings a wallet to the token ratio required by a position range.
+/// The swap is priced at the spot price, and the pool fee is charged on the swapped portion.
+/// Price impact is not accounted for, so the result is exact only for swaps that are small
+/// relative to the pool liquidity.
+///
+/// # Parameters
+/// - `amount_a` - The amount of token A held by the wallet
+/// - `amount_b` - The amount of token B held by the wallet
+/// - `tick_lower_index` - The lower tick index of the position range
+/// - `tick_upper_index` - The upper tick index of the position range
+/// - `sqrt_price_current` - The current sqrt-price of the whirlpool
+/// - `fee_rate` - The fee rate of the whirlpool
+///
+/// # Returns
+/// - `Ok`: The swap direction and the input amount to swap, including the fee. The amount is zero
+///   if the wallet already holds the required ratio.
+/// - `InvalidTickIndex`: The tick indexes are out of bounds or the lower index is not below the upper index
+/// - `SqrtPriceOutOfBounds`: The sqrt-price is outside of [MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64]
+pub fn compute_rebalance_swap(
+    amount_a: u64,
+    amount_b: u64,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    sqrt_price_current: u128,
+    fee_rate: u16,
+) -> Result<RebalanceSwap, ErrorCode> {
+    if Tick::check_is_out_of_bounds(tick_lower_index)
+        || Tick::check_is_out_of_bounds(tick_upper_index)
+        || tick_lower_index >= tick_upper_index
+    {
+        return Err(ErrorCode::InvalidTickIndex);
+    }
+    if !(MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64).contains(&sqrt_price_current) {
+        return Err(ErrorCode::SqrtPriceOutOfBounds);
+    }
+
+    let (value_a, value_b) = position_token_values_x64(
+        sqrt_price_from_tick_index(tick_lower_index),
+        sqrt_price_from_tick_index(tick_upper_index),
+        sqrt_price_current,
+    );
+    let (value_a, value_b) = (U256::from(value_a), U256::from(value_b));
+
+    // Wallet amounts converted at the spot price. Prices are within [2^-64, 2^64],
+    // so both conversions fit in a u128.
+    let price_x128 = U256::from(sqrt_price_current) * U256::from(sqrt_price_current);
+    let amount_a_in_b = (U256::from(amount_a) * price_x128) >> 128;
+    let amount_b_in_a = (U256::from(amount_b) << 128) / price_x128;
+
+    let fee_mul = U256::from(FEE_RATE_MUL_VALUE);
+    let fee_complement = U256::from(FEE_RATE_MUL_VALUE - fee_rate as u128);
+
+    // Swapping x of A for x * (1 - fee) * price of B reaches the ratio when
+    // (A - x) * value_b = (B / price + x * (1 - fee)) * value_a
+    let excess_a = U256::from(amount_a) * value_b;
+    let required_a = amount_b_in_a * value_a;
+    if excess_a > required_a {
+        let amount_in =
+            (excess_a - required_a) * fee_mul / (value_b * fee_mul + value_a * fee_complement);
+        return Ok(RebalanceSwap {
+            amount_in: amount_in.as_u64(),
+            a_to_b: true,
+        });
+    }
+
+    // Symmetrically for swapping y of B for y * (1 - fee) / price of A
+    let excess_b = U256::from(amount_b) * value_a;
+    let required_b = amount_a_in_b * value_b;
+    if excess_b > required_b {
+        let amount_in =
+            (excess_b - required_b) * fee_mul / (value_a * fee_mul + value_b * fee_complement);
+        return Ok(RebalanceSwap {
+            amount_in: amount_in.as_u64(),
+            a_to_b: false,
+        });
+    }
+
+    Ok(RebalanceSwap {
+        amount_in: 0,
+        a_to_b: true,
+    })
+}
+
 fn get_amount_fixed_delta(
     sqrt_price_current: u128,
     sqrt_price_target: u128,
@@ -940,3 +1084,288 @@ mod unit_tests {
         assert_eq!(swap_computation.ok().unwrap(), expected);
     }
 }
+
+#[cfg(test)]
+mod compute_swap_to_tick_tests {
+    use super::*;
+    use crate::state::{MAX_TICK_INDEX, MIN_TICK_INDEX};
+
+    const FEE_RATES: [u16; 4] = [0, 300, 3000, 10_000];
+
+    fn assert_lands_on_tick(liquidity: u128, tick_current_index: i32, tick_index_target: i32) {
+        let a_to_b = tick_index_target <= tick_current_index;
+        let sqrt_price_current = sqrt_price_from_tick_index(tick_current_index);
+        let sqrt_price_target = sqrt_price_from_tick_index(tick_index_target);
+
+        for fee_rate in FEE_RATES {
+            let step = compute_swap_to_tick(
+                fee_rate,
+                liquidity,
+                sqrt_price_current,
+                tick_index_target,
+                a_to_b,
+            )
+            .unwrap();
+            assert_eq!(step.next_price, sqrt_price_target);
+
+            // The fee-inclusive input moves the price exactly onto the tick
+            let amount = step.amount_in + step.fee_amount;
+            let exact = compute_swap(
+                amount,
+                fee_rate,
+                liquidity,
+                sqrt_price_current,
+                sqrt_price_target,
+                true,
+                a_to_b,
+            )
+            .unwrap();
+            assert_eq!(exact, step);
+
+            // Any less input stops short of the tick
+            if amount > 0 {
+                let short = compute_swap(
+                    amount - 1,
+                    fee_rate,
+                    liquidity,
+                    sqrt_price_current,
+                    sqrt_price_target,
+                    true,
+                    a_to_b,
+                )
+                .unwrap();
+                assert_ne!(short.next_price, sqrt_price_target);
+            }
+        }
+    }
+
+    #[test]
+    fn test_a_to_b_lands_on_tick() {
+        assert_lands_on_tick(1_000_000, 0, -64);
+        assert_lands_on_tick(10_000_000_000, 22_000, 21_952);
+        assert_lands_on_tick(4_295_048_016, -443_000, -443_636);
+    }
+
+    #[test]
+    fn test_b_to_a_lands_on_tick() {
+        assert_lands_on_tick(1_000_000, 0, 64);
+        assert_lands_on_tick(10_000_000_000, -22_000, -21_952);
+        assert_lands_on_tick(4_295_048_016, 443_000, 443_636);
+    }
+
+    #[test]
+    fn test_target_at_current_price() {
+        let sqrt_price = sqrt_price_from_tick_index(128);
+        let step = compute_swap_to_tick(3000, 1_000_000, sqrt_price, 128, true).unwrap();
+        assert_eq!(
+            step,
+            SwapStepComputation {
+                amount_in: 0,
+                amount_out: 0,
+                next_price: sqrt_price,
+                fee_amount: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_direction() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        assert_eq!(
+            compute_swap_to_tick(3000, 1_000_000, sqrt_price, 64, true).unwrap_err(),
+            ErrorCode::InvalidSqrtPriceLimitDirection
+        );
+        assert_eq!(
+            compute_swap_to_tick(3000, 1_000_000, sqrt_price, -64, false).unwrap_err(),
+            ErrorCode::InvalidSqrtPriceLimitDirection
+        );
+    }
+
+    #[test]
+    fn test_invalid_tick_index() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        assert_eq!(
+            compute_swap_to_tick(3000, 1_000_000, sqrt_price, MIN_TICK_INDEX - 1, true)
+                .unwrap_err(),
+            ErrorCode::InvalidTickIndex
+        );
+    }
+
+    #[test]
+    fn test_input_exceeds_u64() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        assert_eq!(
+            compute_swap_to_tick(3000, u64::MAX as u128, sqrt_price, MAX_TICK_INDEX, false)
+                .unwrap_err(),
+            ErrorCode::AmountRemainingOverflow
+        );
+    }
+}
+
+#[cfg(test)]
+mod compute_rebalance_swap_tests {
+    use super::*;
+    use crate::state::{MAX_TICK_INDEX, MIN_TICK_INDEX};
+
+    // Sqrt-price of 1.0
+    const SQRT_PRICE_ONE: u128 = 1 << 64;
+
+    #[test]
+    fn test_balanced_wallet_does_not_swap() {
+        let swap = compute_rebalance_swap(1_000, 1_000, -100, 100, SQRT_PRICE_ONE, 3000).unwrap();
+        assert_eq!(swap.amount_in, 0);
+    }
+
+    #[test]
+    fn test_a_to_b() {
+        // A symmetric range at a price of 1.0 needs equal amounts of A & B
+        let swap = compute_rebalance_swap(1_000_000, 0, -100, 100, SQRT_PRICE_ONE, 0).unwrap();
+        assert_eq!(
+            swap,
+            RebalanceSwap {
+                amount_in: 500_000,
+                a_to_b: true
+            }
+        );
+
+        // The fee on the swapped portion is covered by swapping slightly more
+        // x = 1_000_000 / (1 + 0.997)
+        let swap = compute_rebalance_swap(1_000_000, 0, -100, 100, SQRT_PRICE_ONE, 3000).unwrap();
+        assert_eq!(
+            swap,
+            RebalanceSwap {
+                amount_in: 500_751,
+                a_to_b: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_b_to_a() {
+        let swap =
+            compute_rebalance_swap(200_000, 1_000_000, -100, 100, SQRT_PRICE_ONE, 3000).unwrap();
+        // y = 800_000 / (1 + 0.997)
+        assert_eq!(
+            swap,
+            RebalanceSwap {
+                amount_in: 400_600,
+                a_to_b: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_swaps_everything() {
+        // Below the range the position only holds token A
+        let sqrt_price = sqrt_price_from_tick_index(-200);
+        let swap = compute_rebalance_swap(100, 5_000, -100, 100, sqrt_price, 3000).unwrap();
+        assert_eq!(
+            swap,
+            RebalanceSwap {
+                amount_in: 5_000,
+                a_to_b: false
+            }
+        );
+
+        // Above the range the position only holds token B
+        let sqrt_price = sqrt_price_from_tick_index(200);
+        let swap = compute_rebalance_swap(5_000, 100, -100, 100, sqrt_price, 3000).unwrap();
+        assert_eq!(
+            swap,
+            RebalanceSwap {
+                amount_in: 5_000,
+                a_to_b: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_post_swap_ratio_matches_range() {
+        // Range skewed above the current price so the position needs more A than B
+        let sqrt_price = sqrt_price_from_tick_index(1000);
+        let (amount_a, amount_b) = (0u64, 10_000_000_000u64);
+        let swap = compute_rebalance_swap(amount_a, amount_b, 0, 5000, sqrt_price, 0).unwrap();
+        assert!(!swap.a_to_b);
+
+        let amount_a_after =
+            amount_a + convert_amount_at_price(swap.amount_in, sqrt_price, 0, 0, false).unwrap();
+        let amount_b_after = amount_b - swap.amount_in;
+
+        // Amounts required by a unit of liquidity in the range
+        let liquidity = 1u128 << 64;
+        let required_a = get_amount_delta_a(
+            sqrt_price,
+            sqrt_price_from_tick_index(5000),
+            liquidity,
+            false,
+        )
+        .unwrap();
+        let required_b =
+            get_amount_delta_b(sqrt_price_from_tick_index(0), sqrt_price, liquidity, false)
+                .unwrap();
+
+        // amount_a_after / amount_b_after == required_a / required_b, within 1bps
+        let lhs = amount_a_after as u128 * required_b as u128;
+        let rhs = amount_b_after as u128 * required_a as u128;
+        assert!(lhs.max(rhs) - lhs.min(rhs) <= lhs / 10_000);
+    }
+
+    #[test]
+    fn test_extreme_prices() {
+        let swap = compute_rebalance_swap(
+            u64::MAX,
+            u64::MAX,
+            MIN_TICK_INDEX,
+            MAX_TICK_INDEX,
+            MIN_SQRT_PRICE_X64,
+            10_000,
+        )
+        .unwrap();
+        assert!(!swap.a_to_b);
+        assert_eq!(swap.amount_in, u64::MAX);
+
+        let swap = compute_rebalance_swap(
+            u64::MAX,
+            u64::MAX,
+            MIN_TICK_INDEX,
+            MAX_TICK_INDEX,
+            MAX_SQRT_PRICE_X64,
+            10_000,
+        )
+        .unwrap();
+        assert!(swap.a_to_b);
+        assert_eq!(swap.amount_in, u64::MAX);
+    }
+
+    #[test]
+    fn test_invalid_tick_indexes() {
+        for (tick_lower_index, tick_upper_index) in [
+            (100, 100),
+            (100, -100),
+            (MIN_TICK_INDEX - 1, 0),
+            (0, MAX_TICK_INDEX + 1),
+        ] {
+            assert_eq!(
+                compute_rebalance_swap(
+                    1_000,
+                    1_000,
+                    tick_lower_index,
+                    tick_upper_index,
+                    SQRT_PRICE_ONE,
+                    3000
+                ),
+                Err(ErrorCode::InvalidTickIndex)
+            );
+        }
+    }
+
+    #[test]
+    fn test_sqrt_price_out_of_bounds() {
+        for sqrt_price in [0, MIN_SQRT_PRICE_X64 - 1, MAX_SQRT_PRICE_X64 + 1, u128::MAX] {
+            assert_eq!(
+                compute_rebalance_swap(1_000, 1_000, -100, 100, sqrt_price, 3000),
+                Err(ErrorCode::SqrtPriceOutOfBounds)
+            );
+        }
+    }
+}
@@ -91,6 +91,43 @@ pub fn get_amount_delta_b(
     checked_mul_shift_right_round_up_if(liquidity, price_upper - price_lower, round_up)
 }
 
+//
+// Get the token amounts held by liquidity in a price range at a given sqrt price
+//
+// Below the range, the liquidity is held entirely in token_a:
+// t_a = Δt_a(sqrt_price_lower, sqrt_price_upper)
+//
+// Inside the range, token_a covers the part above the current price and token_b the part below:
+// t_a = Δt_a(sqrt_price_current, sqrt_price_upper)
+// t_b = Δt_b(sqrt_price_lower, sqrt_price_current)
+//
+// Above the range, the liquidity is held entirely in token_b:
+// t_b = Δt_b(sqrt_price_lower, sqrt_price_upper)
+pub fn get_token_amounts_from_liquidity(
+    liquidity: u128,
+    current_sqrt_price: u128,
+    lower_sqrt_price: u128,
+    upper_sqrt_price: u128,
+    round_up: bool,
+) -> Result<(u64, u64), ErrorCode> {
+    let (lower_sqrt_price, upper_sqrt_price) =
+        increasing_price_order(lower_sqrt_price, upper_sqrt_price);
+
+    if current_sqrt_price <= lower_sqrt_price {
+        let amount_a = get_amount_delta_a(lower_sqrt_price, upper_sqrt_price, liquidity, round_up)?;
+        Ok((amount_a, 0))
+    } else if current_sqrt_price < upper_sqrt_price {
+        let amount_a =
+            get_amount_delta_a(current_sqrt_price, upper_sqrt_price, liquidity, round_up)?;
+        let amount_b =
+            get_amount_delta_b(lower_sqrt_price, current_sqrt_price, liquidity, round_up)?;
+        Ok((amount_a, amount_b))
+    } else {
+        let amount_b = get_amount_delta_b(lower_sqrt_price, upper_sqrt_price, liquidity, round_up)?;
+        Ok((0, amount_b))
+    }
+}
+
 pub fn increasing_price_order(sqrt_price_0: u128, sqrt_price_1: u128) -> (u128, u128) {
     if sqrt_price_0 > sqrt_price_1 {
         (sqrt_price_1, sqrt_price_0)
@@ -462,6 +499,7 @@ mod test_get_amount_delta {
     // Δt_a = ((liquidity * (sqrt_price_lower - sqrt_price_upper)) / sqrt_price_upper) / sqrt_price_lower
     use super::get_amount_delta_a;
     use super::get_amount_delta_b;
+    use super::get_token_amounts_from_liquidity;
 
     #[test]
     fn test_get_amount_delta_ok() {
@@ -492,4 +530,45 @@ mod test_get_amount_delta {
         assert!(get_amount_delta_a(1 << 64, 2 << 64, (u64::MAX as u128) << 1, true).is_ok());
         assert!(get_amount_delta_a(1 << 64, 2 << 64, u64::MAX as u128, true).is_ok());
     }
+
+    #[test]
+    fn test_get_token_amounts_from_liquidity_ok() {
+        // Below the range, only A
+        assert_eq!(
+            get_token_amounts_from_liquidity(4, 1 << 64, 2 << 64, 4 << 64, true).unwrap(),
+            (1, 0)
+        );
+        // At the lower bound, only A
+        assert_eq!(
+            get_token_amounts_from_liquidity(4, 2 << 64, 2 << 64, 4 << 64, true).unwrap(),
+            (1, 0)
+        );
+        // Inside the range, A above & B below the current price
+        assert_eq!(
+            get_token_amounts_from_liquidity(12, 3 << 64, 2 << 64, 4 << 64, false).unwrap(),
+            (1, 12)
+        );
+        // At or above the upper bound, only B
+        assert_eq!(
+            get_token_amounts_from_liquidity(4, 4 << 64, 2 << 64, 4 << 64, true).unwrap(),
+            (0, 8)
+        );
+        assert_eq!(
+            get_token_amounts_from_liquidity(4, 8 << 64, 4 << 64, 2 << 64, true).unwrap(),
+            (0, 8)
+        );
+    }
+
+    #[test]
+    fn test_get_token_amounts_from_liquidity_rounding() {
+        // A inside the range = 10 * (1/3 - 1/4) = 0.833.., B = 10 * (3 - 2) = 10
+        assert_eq!(
+            get_token_amounts_from_liquidity(10, 3 << 64, 2 << 64, 4 << 64, false).unwrap(),
+            (0, 10)
+        );
+        assert_eq!(
+            get_token_amounts_from_liquidity(10, 3 << 64, 2 << 64, 4 << 64, true).unwrap(),
+            (1, 10)
+        );
+    }
 }
@@ -2,6 +2,10 @@ use crate::state::WhirlpoolsConfig;
 use crate::{errors::ErrorCode, math::MAX_FEE_RATE};
 use anchor_lang::prelude::*;
 
+// Tick spacings of the fee tiers supported by the Whirlpools configs, from finest to coarsest.
+// One tick spacing moves the price by roughly `tick_spacing` basis points.
+pub const SUPPORTED_TICK_SPACINGS: [u16; 4] = [1, 8, 64, 128];
+
 #[account]
 pub struct FeeTier {
     pub whirlpools_config: Pubkey,
@@ -33,3 +37,74 @@ impl FeeTier {
         Ok(())
     }
 }
+
+/// Recommend a tick spacing from `SUPPORTED_TICK_SPACINGS` for a whirlpool with the given fee rate & volatility.
+///
+/// A tick spacing moves the price by roughly `tick_spacing` basis points, so it is compared against
+/// two granularity targets expressed in basis points:
+/// - The fee rate. A price step smaller than the fee earned on it adds tick crossings without giving
+///   liquidity providers meaningfully finer ranges.
+/// - 1% of the expected daily volatility. Volatile pairs are assumed to cross at most ~100 tick spacings
+///   in a day, which bounds the tick crossings (and compute) a day of swaps costs.
+///
+/// The finest supported spacing that covers the larger target is recommended, falling back to the
+/// coarsest supported spacing.
+///
+/// # Parameters
+/// - `fee_rate` - The target fee rate, in hundredths of a basis point
+/// - `volatility_bps` - The expected daily price volatility, in basis points
+///
+/// # Returns
+/// - `Ok`: The recommended tick spacing
+/// - `FeeRateMaxExceeded`: The fee rate exceeds MAX_FEE_RATE
+pub fn recommended_tick_spacing(fee_rate: u16, volatility_bps: u32) -> Result<u16, ErrorCode> {
+    if fee_rate > MAX_FEE_RATE {
+        return Err(ErrorCode::FeeRateMaxExceeded);
+    }
+
+    let fee_rate_bps = fee_rate as u32 / 100;
+    let target_bps = fee_rate_bps.max(volatility_bps / 100);
+
+    let coarsest = SUPPORTED_TICK_SPACINGS[SUPPORTED_TICK_SPACINGS.len() - 1];
+    Ok(SUPPORTED_TICK_SPACINGS
+        .iter()
+        .copied()
+        .find(|tick_spacing| *tick_spacing as u32 >= target_bps)
+        .unwrap_or(coarsest))
+}
+
+#[cfg(test)]
+mod recommended_tick_spacing_tests {
+    use super::*;
+
+    #[test]
+    fn test_low_fee_targets() {
+        // Stable pairs with 0.01% & 0.05% fees
+        assert_eq!(recommended_tick_spacing(100, 10).unwrap(), 1);
+        assert_eq!(recommended_tick_spacing(0, 0).unwrap(), 1);
+        assert_eq!(recommended_tick_spacing(500, 50).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_high_fee_targets() {
+        // Volatile pairs with 0.3% & 1% fees
+        assert_eq!(recommended_tick_spacing(3000, 500).unwrap(), 64);
+        assert_eq!(recommended_tick_spacing(MAX_FEE_RATE, 500).unwrap(), 128);
+    }
+
+    #[test]
+    fn test_volatility_coarsens_spacing() {
+        // A 0.05% fee pair moving 30% a day
+        assert_eq!(recommended_tick_spacing(500, 3_000).unwrap(), 64);
+        // Beyond the coarsest supported spacing
+        assert_eq!(recommended_tick_spacing(100, 1_000_000).unwrap(), 128);
+    }
+
+    #[test]
+    fn test_fee_rate_max_exceeded() {
+        assert_eq!(
+            recommended_tick_spacing(MAX_FEE_RATE + 1, 0).unwrap_err(),
+            ErrorCode::FeeRateMaxExceeded
+        );
+    }
+}
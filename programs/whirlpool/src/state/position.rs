@@ -85,6 +85,55 @@ impl Position {
     pub fn update_reward_owed(&mut self, index: usize, amount_owed: u64) {
         self.reward_infos[index].amount_owed = amount_owed;
     }
+
+    pub fn status(&self, current_tick_index: i32) -> PositionStatus {
+        position_status(
+            current_tick_index,
+            self.tick_lower_index,
+            self.tick_upper_index,
+        )
+    }
+
+    pub fn is_in_range(&self, current_tick_index: i32) -> bool {
+        self.status(current_tick_index) == PositionStatus::InRange
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PositionStatus {
+    BelowRange,
+    InRange,
+    AboveRange,
+}
+
+/// Whether the current tick is below, inside or above the [tick_lower_index, tick_upper_index)
+/// range of a position. A position is in range when it holds both tokens and earns fees.
+pub fn position_status(
+    current_tick_index: i32,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> PositionStatus {
+    if current_tick_index < tick_lower_index {
+        PositionStatus::BelowRange
+    } else if current_tick_index < tick_upper_index {
+        PositionStatus::InRange
+    } else {
+        PositionStatus::AboveRange
+    }
+}
+
+/// The number of ticks the current tick has to move for a position to be in range, or 0 if it
+/// already is. Above the range the current tick has to move below tick_upper_index.
+pub fn ticks_out_of_range(
+    current_tick_index: i32,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> u32 {
+    match position_status(current_tick_index, tick_lower_index, tick_upper_index) {
+        PositionStatus::BelowRange => (tick_lower_index - current_tick_index) as u32,
+        PositionStatus::InRange => 0,
+        PositionStatus::AboveRange => (current_tick_index - tick_upper_index + 1) as u32,
+    }
 }
 
 #[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
@@ -187,6 +236,38 @@ mod is_position_empty_tests {
     }
 }
 
+#[cfg(test)]
+mod position_status_tests {
+    use super::*;
+
+    #[test]
+    fn test_position_status() {
+        assert_eq!(position_status(-129, -128, 128), PositionStatus::BelowRange);
+        assert_eq!(position_status(-128, -128, 128), PositionStatus::InRange);
+        assert_eq!(position_status(127, -128, 128), PositionStatus::InRange);
+        assert_eq!(position_status(128, -128, 128), PositionStatus::AboveRange);
+    }
+
+    #[test]
+    fn test_ticks_out_of_range() {
+        assert_eq!(ticks_out_of_range(-200, -128, 128), 72);
+        assert_eq!(ticks_out_of_range(-129, -128, 128), 1);
+        assert_eq!(ticks_out_of_range(0, -128, 128), 0);
+        assert_eq!(ticks_out_of_range(128, -128, 128), 1);
+        assert_eq!(ticks_out_of_range(200, -128, 128), 73);
+        assert_eq!(ticks_out_of_range(443636, -443636, -443635), 887272);
+    }
+
+    #[test]
+    fn test_position_is_in_range() {
+        let position = position_builder::PositionBuilder::new(-128, 128).build();
+        assert_eq!(position.status(0), PositionStatus::InRange);
+        assert!(position.is_in_range(0));
+        assert!(!position.is_in_range(-129));
+        assert!(!position.is_in_range(128));
+    }
+}
+
 #[cfg(test)]
 pub mod position_builder {
     use anchor_lang::prelude::Pubkey;
@@ -158,6 +158,36 @@ impl Whirlpool {
         Ok(())
     }
 
+    /// Update the reward vault at the specified Whirlpool reward index.
+    /// The new vault must hold the mint of the initialized reward, and can not be one of the
+    /// token vaults of the whirlpool or the vault of another reward.
+    pub fn update_reward_vault(
+        &mut self,
+        index: usize,
+        vault_mint: Pubkey,
+        vault: Pubkey,
+    ) -> Result<(), ErrorCode> {
+        if index >= NUM_REWARDS || !self.reward_infos[index].initialized() {
+            return Err(ErrorCode::InvalidRewardIndex);
+        }
+        if vault_mint != self.reward_infos[index].mint {
+            return Err(ErrorCode::RewardVaultMintMismatch);
+        }
+        if vault == self.token_vault_a
+            || vault == self.token_vault_b
+            || self
+                .reward_infos
+                .iter()
+                .enumerate()
+                .any(|(i, reward_info)| i != index && reward_info.vault == vault)
+        {
+            return Err(ErrorCode::RewardVaultInUse);
+        }
+        self.reward_infos[index].vault = vault;
+
+        Ok(())
+    }
+
     pub fn update_emissions(
         &mut self,
         index: usize,
@@ -314,6 +344,77 @@ fn test_whirlpool_reward_info_initialized() {
     assert_eq!(reward_info.initialized(), true);
 }
 
+#[test]
+fn test_update_reward_vault() {
+    let mint = Pubkey::new_unique();
+    let vault = Pubkey::new_unique();
+    let whirlpool = &mut Whirlpool::default();
+    whirlpool.reward_infos[0].mint = mint;
+    whirlpool.reward_infos[0].vault = Pubkey::new_unique();
+
+    whirlpool.update_reward_vault(0, mint, vault).unwrap();
+    assert_eq!(whirlpool.reward_infos[0].mint, mint);
+    assert_eq!(whirlpool.reward_infos[0].vault, vault);
+}
+
+#[test]
+fn test_update_reward_vault_mint_mismatch() {
+    let whirlpool = &mut Whirlpool::default();
+    whirlpool.reward_infos[0].mint = Pubkey::new_unique();
+
+    assert_eq!(
+        whirlpool.update_reward_vault(0, Pubkey::new_unique(), Pubkey::new_unique()),
+        Err(ErrorCode::RewardVaultMintMismatch)
+    );
+}
+
+#[test]
+fn test_update_reward_vault_invalid_index() {
+    let mint = Pubkey::new_unique();
+    let whirlpool = &mut Whirlpool::default();
+    whirlpool.reward_infos[0].mint = mint;
+
+    // Uninitialized reward
+    assert_eq!(
+        whirlpool.update_reward_vault(1, mint, Pubkey::new_unique()),
+        Err(ErrorCode::InvalidRewardIndex)
+    );
+    assert_eq!(
+        whirlpool.update_reward_vault(NUM_REWARDS, mint, Pubkey::new_unique()),
+        Err(ErrorCode::InvalidRewardIndex)
+    );
+}
+
+#[test]
+fn test_update_reward_vault_in_use() {
+    let mint = Pubkey::new_unique();
+    let whirlpool = &mut Whirlpool::default();
+    whirlpool.token_mint_a = mint;
+    whirlpool.token_vault_a = Pubkey::new_unique();
+    whirlpool.token_mint_b = mint;
+    whirlpool.token_vault_b = Pubkey::new_unique();
+    whirlpool.reward_infos[0].mint = mint;
+    whirlpool.reward_infos[0].vault = Pubkey::new_unique();
+    whirlpool.reward_infos[1].mint = mint;
+    whirlpool.reward_infos[1].vault = Pubkey::new_unique();
+
+    for vault in [
+        whirlpool.token_vault_a,
+        whirlpool.token_vault_b,
+        whirlpool.reward_infos[1].vault,
+    ] {
+        assert_eq!(
+            whirlpool.update_reward_vault(0, mint, vault),
+            Err(ErrorCode::RewardVaultInUse)
+        );
+    }
+
+    // Setting the current vault again is allowed
+    let vault = whirlpool.reward_infos[0].vault;
+    whirlpool.update_reward_vault(0, mint, vault).unwrap();
+    assert_eq!(whirlpool.reward_infos[0].vault, vault);
+}
+
 #[cfg(test)]
 pub mod whirlpool_builder {
     use super::{Whirlpool, WhirlpoolRewardInfo, NUM_REWARDS};
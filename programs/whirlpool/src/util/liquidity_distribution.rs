@@ -0,0 +1,434 @@
+use crate::errors::ErrorCode;
+use crate::math::{add_liquidity_delta, price_x64_from_tick_index, U256};
+use crate::state::*;
+
+/// Compute the liquidity-weighted average price of the liquidity around the current tick.
+///
+/// The window is the tick range covered by `tick_arrays`, clamped to the min & max tick index.
+/// The window is split into segments at every initialized tick. Each segment contributes the price
+/// at its middle tick, weighted by the liquidity active in the segment multiplied by its width in ticks.
+///
+/// # Parameters
+/// - `tick_arrays` - Contiguous tick arrays sorted by ascending start tick index
+/// - `tick_spacing` - A u16 integer of the tick spacing for this whirlpool
+/// - `tick_current_index` - The current tick index of the whirlpool
+/// - `liquidity` - The liquidity of the whirlpool at the current tick index
+///
+/// # Returns
+/// - `Ok`: A u128 Q64.64 integer representing the liquidity-weighted average price
+/// - `InvalidTickSpacing`: The provided tick spacing is 0
+/// - `InvalidTickArraySequence`: The tick arrays are empty, not contiguous or do not cover the current tick index
+/// - `LiquidityZero`: There is no liquidity within the window
+/// - `MultiplicationOverflow`: The weighted sum of prices does not fit in a U256
+pub fn liquidity_weighted_price_x64(
+    tick_arrays: &[&TickArray],
+    tick_spacing: u16,
+    tick_current_index: i32,
+    liquidity: u128,
+) -> Result<u128, ErrorCode> {
+    if tick_spacing == 0 {
+        return Err(ErrorCode::InvalidTickSpacing);
+    }
+
+    let (window_lower, window_upper) = tick_array_sequence_bounds(tick_arrays, tick_spacing)?;
+    if tick_current_index < window_lower || tick_current_index >= window_upper {
+        return Err(ErrorCode::InvalidTickArraySequence);
+    }
+
+    // Segment boundaries are the window edges and every initialized tick in between
+    let mut boundaries: Vec<(i32, i128)> = vec![(window_lower, 0)];
+    for tick_array in tick_arrays {
+        let start_tick_index = tick_array.start_tick_index;
+        for offset in 0..TICK_ARRAY_SIZE_USIZE {
+            let tick = tick_array.ticks[offset];
+            let tick_index = start_tick_index + offset as i32 * tick_spacing as i32;
+            if tick.initialized && tick_index > window_lower && tick_index < window_upper {
+                boundaries.push((tick_index, tick.liquidity_net));
+            }
+        }
+    }
+    boundaries.push((window_upper, 0));
+
+    let current_segment = boundaries
+        .windows(2)
+        .position(|pair| tick_current_index >= pair[0].0 && tick_current_index < pair[1].0)
+        .unwrap();
+    let segment_count = boundaries.len() - 1;
+
+    // Walk outwards from the current segment, crossing initialized ticks to derive each segment's liquidity
+    let mut segment_liquidity = vec![0u128; segment_count];
+    segment_liquidity[current_segment] = liquidity;
+    for i in (current_segment + 1)..segment_count {
+        segment_liquidity[i] = add_liquidity_delta(segment_liquidity[i - 1], boundaries[i].1)?;
+    }
+    for i in (0..current_segment).rev() {
+        segment_liquidity[i] = add_liquidity_delta(segment_liquidity[i + 1], -boundaries[i + 1].1)?;
+    }
+
+    let mut total_weight = U256::zero();
+    let mut total_weighted_price = U256::zero();
+    for (i, pair) in boundaries.windows(2).enumerate() {
+        let (segment_lower, segment_upper) = (pair[0].0, pair[1].0);
+        let weight = U256::from(segment_liquidity[i])
+            .checked_mul(U256::from((segment_upper - segment_lower) as u128))
+            .ok_or(ErrorCode::MultiplicationOverflow)?;
+        let price_x64 = price_x64_from_tick_index((segment_lower + segment_upper) / 2)?;
+
+        total_weight = total_weight
+            .checked_add(weight)
+            .ok_or(ErrorCode::MultiplicationOverflow)?;
+        total_weighted_price = weight
+            .checked_mul(U256::from(price_x64))
+            .and_then(|weighted_price| total_weighted_price.checked_add(weighted_price))
+            .ok_or(ErrorCode::MultiplicationOverflow)?;
+    }
+
+    if total_weight.is_zero() {
+        return Err(ErrorCode::LiquidityZero);
+    }
+
+    (total_weighted_price / total_weight).try_into_u128()
+}
+
+/// List the initialized ticks within a position's range, including its bounds.
+///
+/// # Parameters
+/// - `tick_arrays` - Contiguous tick arrays sorted by ascending start tick index, covering the range
+/// - `tick_spacing` - A u16 integer of the tick spacing for this whirlpool
+/// - `tick_lower_index` - The lower tick index of the range
+/// - `tick_upper_index` - The upper tick index of the range
+///
+/// # Returns
+/// - `Ok`: The tick index & liquidity_net of every initialized tick in the range, in ascending order
+/// - `InvalidTickSpacing`: The provided tick spacing is 0
+/// - `InvalidTickIndex`: The lower tick index is not below the upper tick index
+/// - `InvalidTickArraySequence`: The tick arrays are empty, not contiguous or do not cover the range
+pub fn initialized_ticks_in_range(
+    tick_arrays: &[&TickArray],
+    tick_spacing: u16,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<Vec<(i32, i128)>, ErrorCode> {
+    if tick_spacing == 0 {
+        return Err(ErrorCode::InvalidTickSpacing);
+    }
+    if tick_lower_index >= tick_upper_index {
+        return Err(ErrorCode::InvalidTickIndex);
+    }
+
+    let (window_lower, window_upper) = tick_array_sequence_bounds(tick_arrays, tick_spacing)?;
+    if tick_lower_index < window_lower || tick_upper_index > window_upper {
+        return Err(ErrorCode::InvalidTickArraySequence);
+    }
+
+    let mut ticks = Vec::new();
+    for tick_array in tick_arrays {
+        let start_tick_index = tick_array.start_tick_index;
+        for offset in 0..TICK_ARRAY_SIZE_USIZE {
+            let tick = tick_array.ticks[offset];
+            let tick_index = start_tick_index + offset as i32 * tick_spacing as i32;
+            if tick.initialized && tick_index >= tick_lower_index && tick_index <= tick_upper_index
+            {
+                ticks.push((tick_index, tick.liquidity_net));
+            }
+        }
+    }
+
+    Ok(ticks)
+}
+
+#[derive(PartialEq, Debug)]
+pub struct PoolLiquidity {
+    // Liquidity of all positions, as if every position was in range
+    pub potential: u128,
+    // Liquidity of the positions in range at the current tick
+    pub active: u128,
+}
+
+/// Compare the liquidity a whirlpool would have if all positions were in range to its active liquidity.
+///
+/// Every position adds its liquidity to the gross liquidity of both of its bound ticks,
+/// so the potential liquidity is half of the gross liquidity summed over all initialized ticks.
+///
+/// # Parameters
+/// - `tick_arrays` - All initialized tick arrays of the whirlpool, without duplicates, in any order
+/// - `liquidity` - The liquidity of the whirlpool at the current tick index
+///
+/// # Returns
+/// - `Ok`: The potential & active liquidity of the whirlpool
+/// - `LiquidityOverflow`: The potential liquidity does not fit in a u128
+pub fn pool_liquidity(
+    tick_arrays: &[&TickArray],
+    liquidity: u128,
+) -> Result<PoolLiquidity, ErrorCode> {
+    let mut liquidity_gross_sum = U256::zero();
+    for tick_array in tick_arrays {
+        for offset in 0..TICK_ARRAY_SIZE_USIZE {
+            let tick = tick_array.ticks[offset];
+            if tick.initialized {
+                liquidity_gross_sum += U256::from(tick.liquidity_gross);
+            }
+        }
+    }
+
+    let potential = (liquidity_gross_sum / U256::from(2))
+        .try_into_u128()
+        .map_err(|_| ErrorCode::LiquidityOverflow)?;
+
+    Ok(PoolLiquidity {
+        potential,
+        active: liquidity,
+    })
+}
+
+// Validates that the tick arrays are contiguous and returns the tick range they cover,
+// clamped to the min & max tick index.
+fn tick_array_sequence_bounds(
+    tick_arrays: &[&TickArray],
+    tick_spacing: u16,
+) -> Result<(i32, i32), ErrorCode> {
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    let (first, last) = match (tick_arrays.first(), tick_arrays.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Err(ErrorCode::InvalidTickArraySequence),
+    };
+    for pair in tick_arrays.windows(2) {
+        if pair[1].start_tick_index != pair[0].start_tick_index + ticks_in_array {
+            return Err(ErrorCode::InvalidTickArraySequence);
+        }
+    }
+
+    Ok((
+        first.start_tick_index.max(MIN_TICK_INDEX),
+        (last.start_tick_index + ticks_in_array).min(MAX_TICK_INDEX),
+    ))
+}
+
+#[cfg(test)]
+mod test_fixtures {
+    use crate::state::{tick_builder::TickBuilder, TickArray};
+
+    pub const TS_64: u16 = 64;
+
+    // Build a tick array with the given (tick_index, liquidity_net) ticks initialized
+    pub fn build_tick_array(start_tick_index: i32, ticks: Vec<(i32, i128)>) -> TickArray {
+        let mut array = TickArray::default();
+        array.start_tick_index = start_tick_index;
+
+        for (tick_index, liquidity_net) in ticks {
+            let offset = array.tick_offset(tick_index, TS_64).unwrap() as usize;
+            array.ticks[offset] = TickBuilder::default()
+                .initialized(true)
+                .liquidity_net(liquidity_net)
+                .build();
+        }
+
+        array
+    }
+}
+
+#[cfg(test)]
+mod liquidity_weighted_price_tests {
+    use super::test_fixtures::*;
+    use super::*;
+    use crate::math::TO_Q64;
+
+    #[test]
+    fn test_symmetric_range_around_zero_tick() {
+        let ta0 = build_tick_array(-5632, vec![(-640, 1_000)]);
+        let ta1 = build_tick_array(0, vec![(640, -1_000)]);
+        let price = liquidity_weighted_price_x64(&[&ta0, &ta1], TS_64, 0, 1_000).unwrap();
+        assert_eq!(price, TO_Q64);
+    }
+
+    #[test]
+    fn test_ranges_on_both_sides_of_current_tick() {
+        // 100 liquidity on [-1280, -640), 300 liquidity on [640, 1280), nothing at the current tick
+        let ta0 = build_tick_array(-5632, vec![(-1280, 100), (-640, -100)]);
+        let ta1 = build_tick_array(0, vec![(640, 300), (1280, -300)]);
+        let price = liquidity_weighted_price_x64(&[&ta0, &ta1], TS_64, 0, 0).unwrap();
+
+        let expected = (100 * price_x64_from_tick_index(-960).unwrap()
+            + 300 * price_x64_from_tick_index(960).unwrap())
+            / 400;
+        assert_eq!(price, expected);
+        assert!(price > TO_Q64);
+    }
+
+    #[test]
+    fn test_liquidity_outside_window_is_ignored() {
+        let ta0 = build_tick_array(0, vec![(128, 500), (256, -500)]);
+        let ta1 = build_tick_array(5632, vec![(5632 + 64, 1_000_000)]);
+        let price = liquidity_weighted_price_x64(&[&ta0], TS_64, 0, 0).unwrap();
+        assert_eq!(price, price_x64_from_tick_index(192).unwrap());
+        assert_ne!(
+            liquidity_weighted_price_x64(&[&ta0, &ta1], TS_64, 0, 0).unwrap(),
+            price
+        );
+    }
+
+    #[test]
+    fn test_no_liquidity_in_window() {
+        let ta0 = build_tick_array(0, vec![]);
+        let result = liquidity_weighted_price_x64(&[&ta0], TS_64, 64, 0);
+        assert_eq!(result.unwrap_err(), ErrorCode::LiquidityZero);
+    }
+
+    #[test]
+    fn test_invalid_tick_array_sequence() {
+        let ta0 = build_tick_array(0, vec![]);
+        let ta2 = build_tick_array(11264, vec![]);
+        assert_eq!(
+            liquidity_weighted_price_x64(&[], TS_64, 0, 0).unwrap_err(),
+            ErrorCode::InvalidTickArraySequence
+        );
+        assert_eq!(
+            liquidity_weighted_price_x64(&[&ta0, &ta2], TS_64, 0, 0).unwrap_err(),
+            ErrorCode::InvalidTickArraySequence
+        );
+        assert_eq!(
+            liquidity_weighted_price_x64(&[&ta0], TS_64, -1, 0).unwrap_err(),
+            ErrorCode::InvalidTickArraySequence
+        );
+    }
+}
+
+#[cfg(test)]
+mod initialized_ticks_in_range_tests {
+    use super::test_fixtures::*;
+    use super::*;
+
+    #[test]
+    fn test_range_with_several_initialized_ticks() {
+        let ta0 = build_tick_array(-5632, vec![(-5632, 50), (-1280, 100), (-640, 200)]);
+        let ta1 = build_tick_array(0, vec![(0, -100), (640, -200), (5568, -50)]);
+        let ticks = initialized_ticks_in_range(&[&ta0, &ta1], TS_64, -1280, 640).unwrap();
+        assert_eq!(
+            ticks,
+            vec![(-1280, 100), (-640, 200), (0, -100), (640, -200)]
+        );
+    }
+
+    #[test]
+    fn test_range_bounds_are_inclusive() {
+        let ta0 = build_tick_array(0, vec![(0, 10), (64, 20), (128, -30)]);
+        let ticks = initialized_ticks_in_range(&[&ta0], TS_64, 64, 128).unwrap();
+        assert_eq!(ticks, vec![(64, 20), (128, -30)]);
+    }
+
+    #[test]
+    fn test_range_without_initialized_ticks() {
+        let ta0 = build_tick_array(0, vec![(0, 10), (1280, -10)]);
+        let ticks = initialized_ticks_in_range(&[&ta0], TS_64, 64, 1216).unwrap();
+        assert!(ticks.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_range() {
+        let ta0 = build_tick_array(0, vec![]);
+        assert_eq!(
+            initialized_ticks_in_range(&[&ta0], TS_64, 128, 128).unwrap_err(),
+            ErrorCode::InvalidTickIndex
+        );
+        assert_eq!(
+            initialized_ticks_in_range(&[&ta0], TS_64, -64, 128).unwrap_err(),
+            ErrorCode::InvalidTickArraySequence
+        );
+        assert_eq!(
+            initialized_ticks_in_range(&[&ta0], TS_64, 0, 5696).unwrap_err(),
+            ErrorCode::InvalidTickArraySequence
+        );
+    }
+}
+
+#[cfg(test)]
+mod pool_liquidity_tests {
+    use super::test_fixtures::*;
+    use super::*;
+
+    fn add_position(
+        tick_arrays: &mut [TickArray],
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        liquidity: u128,
+    ) {
+        for (tick_index, liquidity_net) in [
+            (tick_lower_index, liquidity as i128),
+            (tick_upper_index, -(liquidity as i128)),
+        ] {
+            let tick_array = tick_arrays
+                .iter_mut()
+                .find(|array| array.check_in_array_bounds(tick_index, TS_64))
+                .unwrap();
+            let offset = tick_array.tick_offset(tick_index, TS_64).unwrap() as usize;
+            let mut tick = tick_array.ticks[offset];
+            tick.initialized = true;
+            tick.liquidity_net += liquidity_net;
+            tick.liquidity_gross += liquidity;
+            tick_array.ticks[offset] = tick;
+        }
+    }
+
+    fn build_tick_arrays(start_tick_indexes: &[i32]) -> Vec<TickArray> {
+        start_tick_indexes
+            .iter()
+            .map(|start_tick_index| build_tick_array(*start_tick_index, vec![]))
+            .collect()
+    }
+
+    #[test]
+    fn test_positions_in_and_out_of_range() {
+        let mut tick_arrays = build_tick_arrays(&[-5632, 0, 11264]);
+        // In range at tick 0
+        add_position(&mut tick_arrays, -1280, 640, 100);
+        add_position(&mut tick_arrays, -1280, 640, 20);
+        // Out of range, in non-contiguous tick arrays
+        add_position(&mut tick_arrays, 1280, 11520, 300);
+        add_position(&mut tick_arrays, -5632, -640, 50);
+
+        let arrays: Vec<&TickArray> = tick_arrays.iter().collect();
+        assert_eq!(
+            pool_liquidity(&arrays, 120),
+            Ok(PoolLiquidity {
+                potential: 470,
+                active: 120,
+            })
+        );
+    }
+
+    #[test]
+    fn test_all_positions_in_range() {
+        let mut tick_arrays = build_tick_arrays(&[-5632, 0]);
+        add_position(&mut tick_arrays, -640, 640, 1_000);
+        add_position(&mut tick_arrays, -5632, 5568, 500);
+
+        let arrays: Vec<&TickArray> = tick_arrays.iter().collect();
+        let liquidity = pool_liquidity(&arrays, 1_500).unwrap();
+        assert_eq!(liquidity.potential, liquidity.active);
+    }
+
+    #[test]
+    fn test_no_positions() {
+        let tick_arrays = build_tick_arrays(&[0]);
+        assert_eq!(
+            pool_liquidity(&[&tick_arrays[0]], 0),
+            Ok(PoolLiquidity {
+                potential: 0,
+                active: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_potential_liquidity_overflow() {
+        let mut tick_arrays = build_tick_arrays(&[0]);
+        add_position(&mut tick_arrays, 0, 64, u128::MAX);
+        add_position(&mut tick_arrays, 128, 192, u128::MAX);
+
+        let arrays: Vec<&TickArray> = tick_arrays.iter().collect();
+        assert_eq!(
+            pool_liquidity(&arrays, 0),
+            Err(ErrorCode::LiquidityOverflow)
+        );
+    }
+}
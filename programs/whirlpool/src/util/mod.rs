@@ -1,7 +1,9 @@
+pub mod liquidity_distribution;
 pub mod swap_tick_sequence;
 pub mod token;
 pub mod util;
 
+pub use liquidity_distribution::*;
 pub use swap_tick_sequence::*;
 pub use token::*;
 pub use util::*;
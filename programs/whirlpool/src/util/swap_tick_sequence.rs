@@ -161,6 +161,46 @@ impl<'info> SwapTickSequence<'info> {
     }
 }
 
+/// Get the start tick indexes of the tick arrays a swap from the current tick index traverses, in swap order.
+///
+/// Mirrors the swap loop: the first tick array holds the current tick index, and each following array is the
+/// adjacent array in the direction of the swap. The sequence ends early at the min & max tick arrays.
+///
+/// # Parameters
+/// - `tick_current_index` - The current tick index of the whirlpool
+/// - `tick_spacing` - A u16 integer of the tick spacing for this whirlpool
+/// - `a_to_b` - The direction of the swap
+/// - `max_arrays` - The maximum number of start tick indexes to return
+///
+/// # Returns
+/// - `Vec<i32>`: The start tick indexes, empty if the tick spacing is 0
+pub fn get_tick_array_start_indexes(
+    tick_current_index: i32,
+    tick_spacing: u16,
+    a_to_b: bool,
+    max_arrays: usize,
+) -> Vec<i32> {
+    let mut start_indexes = Vec::new();
+    if tick_spacing == 0 {
+        return start_indexes;
+    }
+
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    let mut start_tick_index = tick_current_index.div_euclid(ticks_in_array) * ticks_in_array;
+    while start_indexes.len() < max_arrays
+        && Tick::check_is_valid_start_tick(start_tick_index, tick_spacing)
+    {
+        start_indexes.push(start_tick_index);
+        start_tick_index = if a_to_b {
+            start_tick_index - ticks_in_array
+        } else {
+            start_tick_index + ticks_in_array
+        };
+    }
+
+    start_indexes
+}
+
 #[cfg(test)]
 mod swap_tick_sequence_tests {
     use super::*;
@@ -733,3 +773,109 @@ mod swap_tick_sequence_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod get_tick_array_start_indexes_tests {
+    use super::*;
+    use crate::manager::swap_manager::swap;
+    use crate::math::{sqrt_price_from_tick_index, MIN_SQRT_PRICE_X64};
+    use crate::state::whirlpool_builder::WhirlpoolBuilder;
+    use std::cell::RefCell;
+
+    const TS_8: u16 = 8;
+    const TS_128: u16 = 128;
+
+    #[test]
+    fn a_to_b_sequence() {
+        assert_eq!(
+            get_tick_array_start_indexes(1000, TS_8, true, 3),
+            vec![704, 0, -704]
+        );
+    }
+
+    #[test]
+    fn b_to_a_sequence() {
+        assert_eq!(
+            get_tick_array_start_indexes(1000, TS_8, false, 3),
+            vec![704, 1408, 2112]
+        );
+    }
+
+    #[test]
+    fn negative_tick_rounds_down() {
+        assert_eq!(
+            get_tick_array_start_indexes(-1, TS_128, true, 2),
+            vec![-11264, -22528]
+        );
+        assert_eq!(
+            get_tick_array_start_indexes(-11264, TS_128, false, 2),
+            vec![-11264, 0]
+        );
+    }
+
+    #[test]
+    fn sequence_ends_at_min_and_max_tick_arrays() {
+        assert_eq!(
+            get_tick_array_start_indexes(MIN_TICK_INDEX, TS_128, true, 3),
+            vec![-450560]
+        );
+        assert_eq!(
+            get_tick_array_start_indexes(MAX_TICK_INDEX, TS_128, false, 3),
+            vec![439296]
+        );
+        assert_eq!(
+            get_tick_array_start_indexes(MAX_TICK_INDEX, TS_128, true, 3),
+            vec![439296, 428032, 416768]
+        );
+    }
+
+    #[test]
+    fn max_arrays_and_zero_tick_spacing() {
+        assert!(get_tick_array_start_indexes(1000, TS_8, true, 0).is_empty());
+        assert!(get_tick_array_start_indexes(1000, 0, true, 3).is_empty());
+        // Only the arrays up to the max tick array are returned for an unbounded max_arrays
+        assert_eq!(
+            get_tick_array_start_indexes(MAX_TICK_INDEX, TS_128, true, usize::MAX).len(),
+            80
+        );
+    }
+
+    #[test]
+    fn start_indexes_support_swap_across_arrays() {
+        let tick_arrays: Vec<RefCell<TickArray>> =
+            get_tick_array_start_indexes(1000, TS_8, true, 3)
+                .into_iter()
+                .map(|start_tick_index| {
+                    RefCell::new(TickArray {
+                        start_tick_index,
+                        ..Default::default()
+                    })
+                })
+                .collect();
+        let whirlpool = WhirlpoolBuilder::new()
+            .liquidity(1_000_000_000)
+            .tick_spacing(TS_8)
+            .tick_current_index(1000)
+            .sqrt_price(sqrt_price_from_tick_index(1000))
+            .build();
+        let mut swap_tick_sequence = SwapTickSequence::new(
+            tick_arrays[0].borrow_mut(),
+            Some(tick_arrays[1].borrow_mut()),
+            Some(tick_arrays[2].borrow_mut()),
+        );
+
+        let post_swap = swap(
+            &whirlpool,
+            &mut swap_tick_sequence,
+            70_000_000,
+            MIN_SQRT_PRICE_X64,
+            true,
+            true,
+            0,
+        )
+        .unwrap();
+        // The swap moves from the first array, across the second array into the third
+        assert!(post_swap.next_tick_index < 0);
+        assert!(post_swap.next_tick_index >= -704);
+    }
+}
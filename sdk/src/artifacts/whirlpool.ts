@@ -599,6 +599,22 @@ export type Whirlpool = {
       ],
       "args": []
     },
+    {
+      "name": "updateFeesAndRewardsBatch",
+      "accounts": [
+        {
+          "name": "whirlpool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "numTickArrays",
+          "type": "u8"
+        }
+      ]
+    },
     {
       "name": "collectFees",
       "accounts": [
@@ -696,6 +712,67 @@ export type Whirlpool = {
         }
       ]
     },
+    {
+      "name": "collectFeesAndRewards",
+      "accounts": [
+        {
+          "name": "whirlpool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "positionAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "positionTokenAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tickArrayLower",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tickArrayUpper",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenOwnerAccountA",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenVaultA",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenOwnerAccountB",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenVaultB",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
       "name": "collectProtocolFees",
       "accounts": [
@@ -1078,6 +1155,47 @@ export type Whirlpool = {
         }
       ],
       "args": []
+    },
+    {
+      "name": "setRewardVaultBySuperAuthority",
+      "accounts": [
+        {
+          "name": "whirlpoolsConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "whirlpool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardEmissionsSuperAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "oldRewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newRewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "rewardIndex",
+          "type": "u8"
+        }
+      ]
     }
   ],
   "accounts": [
@@ -1682,6 +1800,26 @@ export type Whirlpool = {
       "code": 6040,
       "name": "AmountRemainingOverflow",
       "msg": "Amount remaining overflows"
+    },
+    {
+      "code": 6041,
+      "name": "RewardVaultMintMismatch",
+      "msg": "Reward vault mint does not match the reward mint"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidRewardAccounts",
+      "msg": "Remaining accounts do not match the initialized rewards of the whirlpool"
+    },
+    {
+      "code": 6043,
+      "name": "PositionWhirlpoolMismatch",
+      "msg": "Position does not belong to the whirlpool"
+    },
+    {
+      "code": 6044,
+      "name": "RewardVaultInUse",
+      "msg": "Reward vault is a token vault of the whirlpool or the vault of another reward"
     }
   ]
 };
@@ -2287,6 +2425,22 @@ export const IDL: Whirlpool = {
       ],
       "args": []
     },
+    {
+      "name": "updateFeesAndRewardsBatch",
+      "accounts": [
+        {
+          "name": "whirlpool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "numTickArrays",
+          "type": "u8"
+        }
+      ]
+    },
     {
       "name": "collectFees",
       "accounts": [
@@ -2384,6 +2538,67 @@ export const IDL: Whirlpool = {
         }
       ]
     },
+    {
+      "name": "collectFeesAndRewards",
+      "accounts": [
+        {
+          "name": "whirlpool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "positionAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "positionTokenAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tickArrayLower",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tickArrayUpper",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenOwnerAccountA",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenVaultA",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenOwnerAccountB",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenVaultB",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
       "name": "collectProtocolFees",
       "accounts": [
@@ -2766,6 +2981,47 @@ export const IDL: Whirlpool = {
         }
       ],
       "args": []
+    },
+    {
+      "name": "setRewardVaultBySuperAuthority",
+      "accounts": [
+        {
+          "name": "whirlpoolsConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "whirlpool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardEmissionsSuperAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "oldRewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newRewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "rewardIndex",
+          "type": "u8"
+        }
+      ]
     }
   ],
   "accounts": [
@@ -3370,6 +3626,26 @@ export const IDL: Whirlpool = {
       "code": 6040,
       "name": "AmountRemainingOverflow",
       "msg": "Amount remaining overflows"
+    },
+    {
+      "code": 6041,
+      "name": "RewardVaultMintMismatch",
+      "msg": "Reward vault mint does not match the reward mint"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidRewardAccounts",
+      "msg": "Remaining accounts do not match the initialized rewards of the whirlpool"
+    },
+    {
+      "code": 6043,
+      "name": "PositionWhirlpoolMismatch",
+      "msg": "Position does not belong to the whirlpool"
+    },
+    {
+      "code": 6044,
+      "name": "RewardVaultInUse",
+      "msg": "Reward vault is a token vault of the whirlpool or the vault of another reward"
     }
   ]
 };
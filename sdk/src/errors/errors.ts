@@ -19,6 +19,8 @@ export enum SwapErrorCode {
   TickArrayCrossingAboveMax = `TickArrayCrossingAboveMax`,
   TickArrayIndexNotInitialized = `TickArrayIndexNotInitialized`,
   TickArraySequenceInvalid = `TickArraySequenceInvalid`,
+  TokenVaultMismatch = `TokenVaultMismatch`,
+  OracleMismatch = `OracleMismatch`,
 }
 
 export type WhirlpoolsErrorCode = TokenErrorCode | SwapErrorCode | MathErrorCode;
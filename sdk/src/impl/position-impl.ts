@@ -1,6 +1,7 @@
 import {
   AddressUtil,
   deriveATA,
+  Percentage,
   resolveOrCreateATAs,
   TransactionBuilder,
 } from "@orca-so/common-sdk";
@@ -12,9 +13,15 @@ import {
   increaseLiquidityIx,
   decreaseLiquidityIx,
 } from "../instructions";
+import {
+  DecreaseLiquidityFractionInput,
+  decreaseLiquidityQuoteByFractionWithParams,
+  increaseLiquidityQuoteByTokenAmountsWithParams,
+} from "../quotes/public";
 import { PositionData } from "../types/public";
 import { Position } from "../whirlpool-client";
 import { PublicKey } from "@solana/web3.js";
+import { u64 } from "@solana/spl-token";
 import { AccountFetcher } from "../network/public";
 import { PDAUtil, TickUtil, toTx } from "../utils/public";
 
@@ -118,8 +125,41 @@ export class PositionImpl implements Position {
     return txBuilder;
   }
 
+  async increaseLiquidityByTokenAmounts(
+    tokenAmountA: u64,
+    tokenAmountB: u64,
+    slippageTolerance: Percentage,
+    resolveATA = true,
+    sourceWallet?: Address,
+    positionWallet?: Address,
+    ataPayer?: Address
+  ) {
+    const whirlpool = await this.fetcher.getPool(this.data.whirlpool, true);
+    if (!whirlpool) {
+      throw new Error("Unable to fetch whirlpool for this position.");
+    }
+
+    const quote = increaseLiquidityQuoteByTokenAmountsWithParams({
+      tokenAmountA,
+      tokenAmountB,
+      tickLowerIndex: this.data.tickLowerIndex,
+      tickUpperIndex: this.data.tickUpperIndex,
+      sqrtPrice: whirlpool.sqrtPrice,
+      tickCurrentIndex: whirlpool.tickCurrentIndex,
+      slippageTolerance,
+    });
+    const tx = await this.increaseLiquidity(
+      quote,
+      resolveATA,
+      sourceWallet,
+      positionWallet,
+      ataPayer
+    );
+    return { tx, quote };
+  }
+
   async decreaseLiquidity(
-    liquidityInput: DecreaseLiquidityInput,
+    liquidityInput: DecreaseLiquidityInput | DecreaseLiquidityFractionInput,
     resolveATA = true,
     sourceWallet?: Address,
     positionWallet?: Address,
@@ -164,8 +204,22 @@ export class PositionImpl implements Position {
       tokenOwnerAccountB = await deriveATA(sourceWalletKey, whirlpool.tokenMintB);
     }
 
+    const { liquidityAmount, tokenMinA, tokenMinB } =
+      "fraction" in liquidityInput
+        ? decreaseLiquidityQuoteByFractionWithParams({
+            ...liquidityInput,
+            positionLiquidity: this.data.liquidity,
+            tickLowerIndex: this.data.tickLowerIndex,
+            tickUpperIndex: this.data.tickUpperIndex,
+            sqrtPrice: whirlpool.sqrtPrice,
+            tickCurrentIndex: whirlpool.tickCurrentIndex,
+          })
+        : liquidityInput;
+
     const decreaseIx = decreaseLiquidityIx(this.ctx.program, {
-      ...liquidityInput,
+      liquidityAmount,
+      tokenMinA,
+      tokenMinB,
       whirlpool: this.data.whirlpool,
       position: this.address,
       positionTokenAccount: await deriveATA(positionWalletKey, this.data.positionMint),
@@ -1,9 +1,21 @@
-import { AddressUtil } from "@orca-so/common-sdk";
+import { AddressUtil, deriveATA, Instruction, TransactionBuilder } from "@orca-so/common-sdk";
 import { Address } from "@project-serum/anchor";
+import { ASSOCIATED_TOKEN_PROGRAM_ID, MintInfo, Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PACKET_DATA_SIZE, PublicKey, Transaction } from "@solana/web3.js";
+import Decimal from "decimal.js";
+import invariant from "tiny-invariant";
 import { WhirlpoolContext } from "../context";
+import {
+  collectFeesIx,
+  collectRewardIx,
+  initializePoolIx,
+  initTickArrayIx,
+  updateFeesAndRewardsIx,
+} from "../instructions";
 import { AccountFetcher } from "../network/public";
-import { WhirlpoolData, TokenInfo } from "../types/public";
-import { WhirlpoolClient, Whirlpool, Position } from "../whirlpool-client";
+import { PositionData, WhirlpoolData, TokenInfo } from "../types/public";
+import { PDAUtil, PoolUtil, PriceMath, TickUtil } from "../utils/public";
+import { WhirlpoolClient, Whirlpool, Position, PositionWithPool } from "../whirlpool-client";
 import { PositionImpl } from "./position-impl";
 import { WhirlpoolImpl } from "./whirlpool-impl";
 
@@ -46,6 +58,337 @@ export class WhirlpoolClientImpl implements WhirlpoolClient {
       account
     );
   }
+
+  public async getPositionsForOwner(owner: Address, refresh = false): Promise<PositionWithPool[]> {
+    const tokenAccounts = await this.ctx.connection.getParsedTokenAccountsByOwner(
+      AddressUtil.toPubKey(owner),
+      { programId: TOKEN_PROGRAM_ID }
+    );
+    // Position tokens are NFTs, skip every token account that cannot hold one.
+    const positionAddresses = tokenAccounts.value
+      .map(({ account }) => account.data.parsed.info)
+      .filter(({ tokenAmount }) => tokenAmount.decimals === 0 && tokenAmount.amount === "1")
+      .map(({ mint }) => PDAUtil.getPosition(this.ctx.program.programId, mint).publicKey);
+
+    const positions = await this.ctx.fetcher.listPositions(positionAddresses, refresh);
+    const ownedPositions = positionAddresses
+      .map((address, index) => ({ address, data: positions[index] }))
+      .filter(({ data }) => !!data) as { address: PublicKey; data: PositionData }[];
+    if (!ownedPositions.length) {
+      return [];
+    }
+
+    const poolAddresses = uniqueAddresses(ownedPositions.map(({ data }) => data.whirlpool));
+    const pools = await this.ctx.fetcher.listPools(poolAddresses, refresh);
+    const mintAddresses = uniqueAddresses(
+      pools.reduce<PublicKey[]>(
+        (mints, pool) => (pool ? [...mints, pool.tokenMintA, pool.tokenMintB] : mints),
+        []
+      )
+    );
+    const mintInfos = await this.ctx.fetcher.listMintInfos(mintAddresses, refresh);
+
+    const mintInfoMap: Record<string, MintInfo> = {};
+    mintAddresses.forEach((mint, index) => {
+      const info = mintInfos[index];
+      if (!info) {
+        throw new Error(`Unable to fetch MintInfo for mint - ${mint}`);
+      }
+      mintInfoMap[mint.toBase58()] = info;
+    });
+
+    const poolMap: Record<string, Whirlpool> = {};
+    poolAddresses.forEach((address, index) => {
+      const data = pools[index];
+      if (!data) {
+        throw new Error(`Unable to fetch Whirlpool at address at ${address}`);
+      }
+      poolMap[address.toBase58()] = new WhirlpoolImpl(
+        this.ctx,
+        this.ctx.fetcher,
+        address,
+        { mint: data.tokenMintA, ...mintInfoMap[data.tokenMintA.toBase58()] },
+        { mint: data.tokenMintB, ...mintInfoMap[data.tokenMintB.toBase58()] },
+        data
+      );
+    });
+
+    return ownedPositions.map(({ address, data }) => ({
+      position: new PositionImpl(this.ctx, this.ctx.fetcher, address, data),
+      pool: poolMap[data.whirlpool.toBase58()],
+    }));
+  }
+
+  public async createPool(
+    whirlpoolsConfig: Address,
+    tokenMintA: Address,
+    tokenMintB: Address,
+    tickSpacing: number,
+    initialPrice: Decimal,
+    funder?: Address
+  ): Promise<{ poolKey: PublicKey; tx: TransactionBuilder }> {
+    const configKey = AddressUtil.toPubKey(whirlpoolsConfig);
+    const [mintA, mintB] = AddressUtil.toPubKeys([tokenMintA, tokenMintB]);
+    const [orderedMintA] = PoolUtil.orderMints(mintA, mintB);
+    invariant(
+      AddressUtil.toPubKey(orderedMintA).equals(mintA),
+      "Token mints are not in order - InvalidTokenMintOrder"
+    );
+
+    const [mintInfoA, mintInfoB] = await this.ctx.fetcher.listMintInfos([mintA, mintB], false);
+    if (!mintInfoA) {
+      throw new Error(`Unable to fetch MintInfo for mint - ${mintA}`);
+    }
+    if (!mintInfoB) {
+      throw new Error(`Unable to fetch MintInfo for mint - ${mintB}`);
+    }
+
+    const programId = this.ctx.program.programId;
+    const funderKey = !!funder ? AddressUtil.toPubKey(funder) : this.ctx.provider.wallet.publicKey;
+    const whirlpoolPda = PDAUtil.getWhirlpool(programId, configKey, mintA, mintB, tickSpacing);
+    const feeTierKey = PDAUtil.getFeeTier(programId, configKey, tickSpacing).publicKey;
+    const initSqrtPrice = PriceMath.priceToSqrtPriceX64(
+      initialPrice,
+      mintInfoA.decimals,
+      mintInfoB.decimals
+    );
+    const startTick = TickUtil.getStartTickIndex(
+      PriceMath.sqrtPriceX64ToTickIndex(initSqrtPrice),
+      tickSpacing
+    );
+
+    const txBuilder = new TransactionBuilder(
+      this.ctx.provider.connection,
+      this.ctx.provider.wallet
+    );
+    txBuilder.addInstruction(
+      initializePoolIx(this.ctx.program, {
+        initSqrtPrice,
+        whirlpoolsConfig: configKey,
+        whirlpoolPda,
+        tokenMintA: mintA,
+        tokenMintB: mintB,
+        tokenVaultAKeypair: Keypair.generate(),
+        tokenVaultBKeypair: Keypair.generate(),
+        feeTierKey,
+        tickSpacing,
+        funder: funderKey,
+      })
+    );
+    txBuilder.addInstruction(
+      initTickArrayIx(this.ctx.program, {
+        startTick,
+        tickArrayPda: PDAUtil.getTickArray(programId, whirlpoolPda.publicKey, startTick),
+        whirlpool: whirlpoolPda.publicKey,
+        funder: funderKey,
+      })
+    );
+
+    return { poolKey: whirlpoolPda.publicKey, tx: txBuilder };
+  }
+
+  public async getHarvestTransactionCount(
+    owner?: Address,
+    maxAccountsPerTransaction?: number,
+    refresh = false
+  ): Promise<number> {
+    return (await this.harvestAllPositions(owner, refresh, maxAccountsPerTransaction)).length;
+  }
+
+  public async harvestAllPositions(
+    owner?: Address,
+    refresh = false,
+    maxAccountsPerTransaction?: number
+  ): Promise<TransactionBuilder[]> {
+    const ownerKey = !!owner ? AddressUtil.toPubKey(owner) : this.ctx.provider.wallet.publicKey;
+    const payerKey = this.ctx.provider.wallet.publicKey;
+    const programId = this.ctx.program.programId;
+
+    // A position without liquidity accrues nothing, it only needs collecting if it still owes tokens.
+    const harvestable = (await this.getPositionsForOwner(ownerKey, refresh)).filter(
+      ({ position }) => {
+        const data = position.getData();
+        return (
+          !data.liquidity.isZero() ||
+          !data.feeOwedA.isZero() ||
+          !data.feeOwedB.isZero() ||
+          data.rewardInfos.some(({ amountOwed }) => !amountOwed.isZero())
+        );
+      }
+    );
+    if (!harvestable.length) {
+      return [];
+    }
+
+    const harvests = await Promise.all(
+      harvestable.map(async ({ position, pool }) => {
+        const positionData = position.getData();
+        const poolData = pool.getData();
+        const positionAddress = position.getAddress();
+        const positionTokenAccount = await deriveATA(ownerKey, positionData.positionMint);
+        const rewardInfos = poolData.rewardInfos
+          .map((rewardInfo, rewardIndex) => ({ rewardInfo, rewardIndex }))
+          .filter(({ rewardInfo }) => PoolUtil.isRewardInitialized(rewardInfo));
+        const mints = uniqueAddresses([
+          poolData.tokenMintA,
+          poolData.tokenMintB,
+          ...rewardInfos.map(({ rewardInfo }) => rewardInfo.mint),
+        ]);
+        const atas: Record<string, PublicKey> = {};
+        for (const mint of mints) {
+          atas[mint.toBase58()] = await deriveATA(ownerKey, mint);
+        }
+
+        const instructions: Instruction[] = [];
+        if (!positionData.liquidity.isZero()) {
+          instructions.push(
+            updateFeesAndRewardsIx(this.ctx.program, {
+              whirlpool: positionData.whirlpool,
+              position: positionAddress,
+              tickArrayLower: PDAUtil.getTickArrayFromTickIndex(
+                positionData.tickLowerIndex,
+                poolData.tickSpacing,
+                positionData.whirlpool,
+                programId
+              ).publicKey,
+              tickArrayUpper: PDAUtil.getTickArrayFromTickIndex(
+                positionData.tickUpperIndex,
+                poolData.tickSpacing,
+                positionData.whirlpool,
+                programId
+              ).publicKey,
+            })
+          );
+        }
+        instructions.push(
+          collectFeesIx(this.ctx.program, {
+            whirlpool: positionData.whirlpool,
+            positionAuthority: ownerKey,
+            position: positionAddress,
+            positionTokenAccount,
+            tokenOwnerAccountA: atas[poolData.tokenMintA.toBase58()],
+            tokenOwnerAccountB: atas[poolData.tokenMintB.toBase58()],
+            tokenVaultA: poolData.tokenVaultA,
+            tokenVaultB: poolData.tokenVaultB,
+          })
+        );
+        rewardInfos.forEach(({ rewardInfo, rewardIndex }) => {
+          instructions.push(
+            collectRewardIx(this.ctx.program, {
+              whirlpool: positionData.whirlpool,
+              positionAuthority: ownerKey,
+              position: positionAddress,
+              positionTokenAccount,
+              rewardIndex,
+              rewardOwnerAccount: atas[rewardInfo.mint.toBase58()],
+              rewardVault: rewardInfo.vault,
+            })
+          );
+        });
+        return { mints, atas, instructions };
+      })
+    );
+
+    const allMints = uniqueAddresses(
+      harvests.reduce<PublicKey[]>((mints, harvest) => [...mints, ...harvest.mints], [])
+    );
+    const allAtas = await Promise.all(allMints.map((mint) => deriveATA(ownerKey, mint)));
+    const existingAtas = await this.ctx.fetcher.listTokenInfos(allAtas, true);
+    const createdMints = new Set(
+      allMints.filter((_, index) => !!existingAtas[index]).map((mint) => mint.toBase58())
+    );
+
+    // Create each missing associated token account in the first transaction collecting into it.
+    const withCreateAtaIxs = (
+      { mints, atas, instructions }: typeof harvests[number],
+      created: Set<string>
+    ): Instruction[] => [
+      ...mints
+        .filter((mint) => !created.has(mint.toBase58()))
+        .map((mint) => ({
+          instructions: [
+            Token.createAssociatedTokenAccountInstruction(
+              ASSOCIATED_TOKEN_PROGRAM_ID,
+              TOKEN_PROGRAM_ID,
+              mint,
+              atas[mint.toBase58()],
+              ownerKey,
+              payerKey
+            ),
+          ],
+          cleanupInstructions: [],
+          signers: [],
+        })),
+      ...instructions,
+    ];
+
+    const txBuilders: TransactionBuilder[] = [];
+    let pending: Instruction[] = [];
+    let pendingMints = new Set<string>();
+    for (const harvest of harvests) {
+      const appended = [
+        ...pending,
+        ...withCreateAtaIxs(harvest, new Set([...createdMints, ...pendingMints])),
+      ];
+      const exceedsAccountLimit =
+        maxAccountsPerTransaction !== undefined &&
+        getTransactionAccountCount(appended, payerKey) > maxAccountsPerTransaction;
+      if (
+        pending.length &&
+        (exceedsAccountLimit || getTransactionSize(appended, payerKey) > PACKET_DATA_SIZE)
+      ) {
+        txBuilders.push(this.toTransactionBuilder(pending));
+        pendingMints.forEach((mint) => createdMints.add(mint));
+        pendingMints = new Set();
+        pending = withCreateAtaIxs(harvest, createdMints);
+      } else {
+        pending = appended;
+      }
+      harvest.mints
+        .map((mint) => mint.toBase58())
+        .filter((mint) => !createdMints.has(mint))
+        .forEach((mint) => pendingMints.add(mint));
+    }
+    txBuilders.push(this.toTransactionBuilder(pending));
+
+    return txBuilders;
+  }
+
+  private toTransactionBuilder(instructions: Instruction[]): TransactionBuilder {
+    const txBuilder = new TransactionBuilder(
+      this.ctx.provider.connection,
+      this.ctx.provider.wallet
+    );
+    instructions.forEach((ix) => txBuilder.addInstruction(ix));
+    return txBuilder;
+  }
+}
+
+/**
+ * Size in bytes of a signed transaction made of the given instructions.
+ */
+function getTransactionSize(instructions: Instruction[], feePayer: PublicKey): number {
+  const tx = new Transaction({ feePayer, recentBlockhash: PublicKey.default.toBase58() });
+  instructions.forEach((ix) => tx.add(...ix.instructions, ...ix.cleanupInstructions));
+  const message = tx.compileMessage();
+  // compact-array length prefix (1 byte) and one 64 byte signature per required signer
+  return 1 + 64 * message.header.numRequiredSignatures + message.serialize().length;
+}
+
+/**
+ * Number of unique accounts, incl. programs and the fee payer, referenced by a transaction made
+ * of the given instructions.
+ */
+function getTransactionAccountCount(instructions: Instruction[], feePayer: PublicKey): number {
+  const tx = new Transaction({ feePayer, recentBlockhash: PublicKey.default.toBase58() });
+  instructions.forEach((ix) => tx.add(...ix.instructions, ...ix.cleanupInstructions));
+  return tx.compileMessage().accountKeys.length;
+}
+
+function uniqueAddresses(addresses: PublicKey[]): PublicKey[] {
+  return [...new Set(addresses.map((address) => address.toBase58()))].map(
+    (address) => new PublicKey(address)
+  );
 }
 
 async function getTokenInfos(
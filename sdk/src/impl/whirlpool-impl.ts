@@ -16,17 +16,24 @@ import {
   increaseLiquidityIx,
   decreaseLiquidityIx,
   closePositionIx,
+  collectFeesIx,
+  collectRewardIx,
   swapIx,
   SwapInput,
 } from "../instructions";
 import { TokenInfo, WhirlpoolData } from "../types/public";
-import { Whirlpool } from "../whirlpool-client";
+import { SwapBuildResult, Whirlpool } from "../whirlpool-client";
 import { PublicKey, Keypair } from "@solana/web3.js";
 import { u64 } from "@solana/spl-token";
-import { AccountFetcher } from "../network/public";
+import { AccountFetcher, ParsableWhirlpool } from "../network/public";
 import invariant from "tiny-invariant";
-import { PDAUtil, PriceMath, TickArrayUtil, TickUtil } from "../utils/public";
-import { decreaseLiquidityQuoteByLiquidityWithParams, SwapQuote } from "../quotes/public";
+import { PDAUtil, PoolUtil, PriceMath, TickArrayUtil, TickUtil } from "../utils/public";
+import {
+  decreaseLiquidityQuoteByLiquidityWithParams,
+  increaseLiquidityQuoteByInputToken,
+  SwapQuote,
+} from "../quotes/public";
+import Decimal from "decimal.js";
 
 export class WhirlpoolImpl implements Whirlpool {
   private data: WhirlpoolData;
@@ -67,7 +74,8 @@ export class WhirlpoolImpl implements Whirlpool {
     tickUpper: number,
     liquidityInput: IncreaseLiquidityInput,
     wallet?: Address,
-    funder?: Address
+    funder?: Address,
+    initTickArrays = false
   ) {
     await this.refresh();
     return this.getOpenPositionWithOptMetadataTx(
@@ -75,7 +83,9 @@ export class WhirlpoolImpl implements Whirlpool {
       tickUpper,
       liquidityInput,
       !!wallet ? AddressUtil.toPubKey(wallet) : this.ctx.wallet.publicKey,
-      !!funder ? AddressUtil.toPubKey(funder) : this.ctx.wallet.publicKey
+      !!funder ? AddressUtil.toPubKey(funder) : this.ctx.wallet.publicKey,
+      false,
+      initTickArrays
     );
   }
 
@@ -83,21 +93,80 @@ export class WhirlpoolImpl implements Whirlpool {
     tickLower: number,
     tickUpper: number,
     liquidityInput: IncreaseLiquidityInput,
-    sourceWallet?: Address,
-    positionWallet?: Address,
-    funder?: Address
+    wallet?: Address,
+    funder?: Address,
+    initTickArrays = false
   ) {
     await this.refresh();
     return this.getOpenPositionWithOptMetadataTx(
       tickLower,
       tickUpper,
       liquidityInput,
-      !!sourceWallet ? AddressUtil.toPubKey(sourceWallet) : this.ctx.wallet.publicKey,
+      !!wallet ? AddressUtil.toPubKey(wallet) : this.ctx.wallet.publicKey,
       !!funder ? AddressUtil.toPubKey(funder) : this.ctx.wallet.publicKey,
-      true
+      true,
+      initTickArrays
     );
   }
 
+  async openPositionByPriceRange(
+    priceLower: Decimal,
+    priceUpper: Decimal,
+    inputTokenMint: Address,
+    inputTokenAmount: Decimal,
+    slippageTolerance: Percentage,
+    wallet?: Address,
+    funder?: Address,
+    initTickArrays = false
+  ) {
+    await this.refresh();
+    const { tickSpacing } = this.data;
+    const decimalsA = this.tokenAInfo.decimals;
+    const decimalsB = this.tokenBInfo.decimals;
+
+    const tickLowerIndex = PriceMath.priceToInitializableTickIndex(
+      priceLower,
+      decimalsA,
+      decimalsB,
+      tickSpacing
+    );
+    const tickUpperIndex = PriceMath.priceToInitializableTickIndex(
+      priceUpper,
+      decimalsA,
+      decimalsB,
+      tickSpacing
+    );
+    invariant(
+      tickLowerIndex < tickUpperIndex,
+      `price range [${priceLower}, ${priceUpper}] does not span an initializable tick range`
+    );
+
+    const liquidityQuote = increaseLiquidityQuoteByInputToken(
+      inputTokenMint,
+      inputTokenAmount,
+      tickLowerIndex,
+      tickUpperIndex,
+      slippageTolerance,
+      this
+    );
+
+    const { positionMint, tx } = await this.getOpenPositionWithOptMetadataTx(
+      tickLowerIndex,
+      tickUpperIndex,
+      liquidityQuote,
+      !!wallet ? AddressUtil.toPubKey(wallet) : this.ctx.wallet.publicKey,
+      !!funder ? AddressUtil.toPubKey(funder) : this.ctx.wallet.publicKey,
+      false,
+      initTickArrays
+    );
+
+    return {
+      positionMint,
+      tx,
+      quote: { ...liquidityQuote, tickLowerIndex, tickUpperIndex },
+    };
+  }
+
   async initTickArrayForTicks(ticks: number[], funder?: Address, refresh = true) {
     const initTickArrayStartPdas = await TickArrayUtil.getUninitializedArraysPDAs(
       ticks,
@@ -160,8 +229,29 @@ export class WhirlpoolImpl implements Whirlpool {
     return this.getSwapTx(quote, sourceWalletKey);
   }
 
+  async buildSwap(quote: SwapQuote, sourceWallet?: Address): Promise<SwapBuildResult> {
+    const { context, value } = await this.ctx.connection.getAccountInfoAndContext(this.address);
+    const poolSnapshot = ParsableWhirlpool.parse(value?.data);
+    if (!poolSnapshot) {
+      throw new Error(`Unable to fetch Whirlpool at address at ${this.address}`);
+    }
+    this.data = poolSnapshot;
+
+    const tx = await this.swap(quote, sourceWallet);
+    const tickArraysUsed = [quote.tickArray0, quote.tickArray1, quote.tickArray2].filter(
+      (address, index, addresses) => addresses.findIndex((a) => a.equals(address)) === index
+    );
+    return { tx, quote, tickArraysUsed, poolSnapshot, poolSnapshotSlot: context.slot };
+  }
+
   /**
-   * Construct a transaction for opening an new position with optional metadata
+   * Construct a transaction for opening an new position with optional metadata.
+   * If `initTickArrays` is true, the tick arrays of the position's range that are not initialized
+   * yet are initialized in the same transaction.
+   *
+   * The tick arrays are checked when the transaction is constructed. initialize_tick_array fails
+   * for an existing tick array, so the transaction fails if another transaction initializes them
+   * before it lands. Rebuild the transaction to retry.
    */
   async getOpenPositionWithOptMetadataTx(
     tickLower: number,
@@ -169,7 +259,8 @@ export class WhirlpoolImpl implements Whirlpool {
     liquidityInput: IncreaseLiquidityInput,
     wallet: PublicKey,
     funder: PublicKey,
-    withMetadata: boolean = false
+    withMetadata: boolean = false,
+    initTickArrays: boolean = false
   ): Promise<{ positionMint: PublicKey; tx: TransactionBuilder }> {
     invariant(TickUtil.checkTickInBounds(tickLower), "tickLower is out of bounds.");
     invariant(TickUtil.checkTickInBounds(tickUpper), "tickUpper is out of bounds.");
@@ -205,6 +296,27 @@ export class WhirlpoolImpl implements Whirlpool {
       this.ctx.provider.wallet
     );
 
+    if (initTickArrays) {
+      const uninitializedTickArrays = await TickArrayUtil.getUninitializedArraysPDAs(
+        [tickLower, tickUpper],
+        this.ctx.program.programId,
+        this.address,
+        whirlpool.tickSpacing,
+        this.fetcher,
+        true
+      );
+      uninitializedTickArrays.forEach(({ startIndex, pda }) => {
+        txBuilder.addInstruction(
+          initTickArrayIx(this.ctx.program, {
+            startTick: startIndex,
+            tickArrayPda: pda,
+            whirlpool: this.address,
+            funder,
+          })
+        );
+      });
+    }
+
     const positionIx = (withMetadata ? openPositionWithMetadataIx : openPositionIx)(
       this.ctx.program,
       {
@@ -327,14 +439,29 @@ export class WhirlpoolImpl implements Whirlpool {
     resolvedAssociatedTokenAddresses[whirlpool.tokenMintA.toBase58()] = tokenOwnerAccountA;
     resolvedAssociatedTokenAddresses[whirlpool.tokenMintB.toBase58()] = tokenOwnerAccountB;
 
-    // TODO: Collect all Fees and rewards for the position.
-    // TODO: Optimize - no need to call updateFee if we call decreaseLiquidity first.
-    // const collectTx = await buildCollectFeesAndRewardsTx(this.dal, {
-    //   provider,
-    //   positionAddress: positionAddress,
-    //   resolvedAssociatedTokenAddresses,
-    // });
-    // txBuilder.addInstruction(collectTx.compressIx(false));
+    const initializedRewardInfos = whirlpool.rewardInfos
+      .map((rewardInfo, rewardIndex) => ({ rewardInfo, rewardIndex }))
+      .filter(({ rewardInfo }) => PoolUtil.isRewardInitialized(rewardInfo));
+    const rewardMintsToResolve = initializedRewardInfos
+      .map(({ rewardInfo }) => rewardInfo.mint)
+      .filter(
+        (mint, index, mints) =>
+          !resolvedAssociatedTokenAddresses[mint.toBase58()] &&
+          mints.findIndex((other) => other.equals(mint)) === index
+      );
+    if (rewardMintsToResolve.length > 0) {
+      const rewardAtas = await resolveOrCreateATAs(
+        this.ctx.connection,
+        destinationWallet,
+        rewardMintsToResolve.map((tokenMint) => ({ tokenMint })),
+        () => this.fetcher.getAccountRentExempt(),
+        payerKey
+      );
+      rewardAtas.forEach(({ address, ...createRewardOwnerAccountIx }, index) => {
+        txBuilder.addInstruction(createRewardOwnerAccountIx);
+        resolvedAssociatedTokenAddresses[rewardMintsToResolve[index].toBase58()] = address;
+      });
+    }
 
     /* Remove all liquidity remaining in the position */
     if (position.liquidity.gt(new u64(0))) {
@@ -365,6 +492,33 @@ export class WhirlpoolImpl implements Whirlpool {
       txBuilder.addInstruction(liquidityIx);
     }
 
+    /* Collect all fees and rewards, decreaseLiquidity has already updated the amounts owed */
+    txBuilder.addInstruction(
+      collectFeesIx(this.ctx.program, {
+        whirlpool: position.whirlpool,
+        positionAuthority: positionWallet,
+        position: positionAddress,
+        positionTokenAccount,
+        tokenOwnerAccountA,
+        tokenOwnerAccountB,
+        tokenVaultA: whirlpool.tokenVaultA,
+        tokenVaultB: whirlpool.tokenVaultB,
+      })
+    );
+    initializedRewardInfos.forEach(({ rewardInfo, rewardIndex }) => {
+      txBuilder.addInstruction(
+        collectRewardIx(this.ctx.program, {
+          whirlpool: position.whirlpool,
+          positionAuthority: positionWallet,
+          position: positionAddress,
+          positionTokenAccount,
+          rewardIndex,
+          rewardOwnerAccount: resolvedAssociatedTokenAddresses[rewardInfo.mint.toBase58()],
+          rewardVault: rewardInfo.vault,
+        })
+      );
+    });
+
     /* Close position */
     const positionIx = closePositionIx(this.ctx.program, {
       positionAuthority: positionWallet,
@@ -0,0 +1,98 @@
+import { Program } from "@project-serum/anchor";
+import { Whirlpool } from "../artifacts/whirlpool";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Instruction } from "@orca-so/common-sdk";
+import { PublicKey } from "@solana/web3.js";
+
+/**
+ * Parameters to update and collect all fees and rewards from a position.
+ *
+ * @category Instruction Types
+ * @param whirlpool - PublicKey for the whirlpool that the position belongs to.
+ * @param position - PublicKey for the position that fees and rewards are collected from.
+ * @param positionTokenAccount - PublicKey for the position token's associated token address.
+ * @param tickArrayLower - PublicKey for the tick-array account that hosts the tick at the lower tick index.
+ * @param tickArrayUpper - PublicKey for the tick-array account that hosts the tick at the upper tick index.
+ * @param tokenOwnerAccountA - PublicKey for the token A account that will receive the collected fees.
+ * @param tokenOwnerAccountB - PublicKey for the token B account that will receive the collected fees.
+ * @param tokenVaultA - PublicKey for the tokenA vault for this whirlpool.
+ * @param tokenVaultB - PublicKey for the tokenB vault for this whirlpool.
+ * @param rewardAccounts - The reward owner account and reward vault of every initialized reward of the whirlpool, in reward index order.
+ * @param positionAuthority - authority that owns the token corresponding to this desired position.
+ */
+export type CollectFeesAndRewardsParams = {
+  whirlpool: PublicKey;
+  position: PublicKey;
+  positionTokenAccount: PublicKey;
+  tickArrayLower: PublicKey;
+  tickArrayUpper: PublicKey;
+  tokenOwnerAccountA: PublicKey;
+  tokenOwnerAccountB: PublicKey;
+  tokenVaultA: PublicKey;
+  tokenVaultB: PublicKey;
+  rewardAccounts: { rewardOwnerAccount: PublicKey; rewardVault: PublicKey }[];
+  positionAuthority: PublicKey;
+};
+
+/**
+ * Update the accrued fees and rewards of a position and collect all of them.
+ *
+ * #### Special Errors
+ * `TickNotFound` - Provided tick array account does not contain the tick for this position.
+ * `InvalidRewardAccounts` - The reward accounts do not match the initialized rewards of the whirlpool.
+ *
+ * @category Instructions
+ * @param context - Context object containing services required to generate the instruction
+ * @param params - CollectFeesAndRewardsParams object
+ * @returns - Instruction to perform the action.
+ */
+export function collectFeesAndRewardsIx(
+  program: Program<Whirlpool>,
+  params: CollectFeesAndRewardsParams
+): Instruction {
+  const {
+    whirlpool,
+    positionAuthority,
+    position,
+    positionTokenAccount,
+    tickArrayLower,
+    tickArrayUpper,
+    tokenOwnerAccountA,
+    tokenOwnerAccountB,
+    tokenVaultA,
+    tokenVaultB,
+    rewardAccounts,
+  } = params;
+
+  const ix = program.instruction.collectFeesAndRewards({
+    accounts: {
+      whirlpool,
+      positionAuthority,
+      position,
+      positionTokenAccount,
+      tickArrayLower,
+      tickArrayUpper,
+      tokenOwnerAccountA,
+      tokenVaultA,
+      tokenOwnerAccountB,
+      tokenVaultB,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    },
+    remainingAccounts: rewardAccounts.reduce<
+      { pubkey: PublicKey; isWritable: boolean; isSigner: boolean }[]
+    >(
+      (accounts, { rewardOwnerAccount, rewardVault }) => [
+        ...accounts,
+        { pubkey: rewardOwnerAccount, isWritable: true, isSigner: false },
+        { pubkey: rewardVault, isWritable: true, isSigner: false },
+      ],
+      []
+    ),
+  });
+
+  return {
+    instructions: [ix],
+    cleanupInstructions: [],
+    signers: [],
+  };
+}
@@ -1,5 +1,6 @@
 export * from "./close-position-ix";
 export * from "./collect-fees-ix";
+export * from "./collect-fees-and-rewards-ix";
 export * from "./collect-protocol-fees-ix";
 export * from "./collect-reward-ix";
 export * from "./decrease-liquidity-ix";
@@ -20,5 +21,7 @@ export * from "./set-reward-authority-by-super-authority-ix";
 export * from "./set-reward-authority-ix";
 export * from "./set-reward-emissions-ix";
 export * from "./set-reward-emissions-super-authority-ix";
+export * from "./set-reward-vault-by-super-authority-ix";
 export * from "./swap-ix";
 export * from "./update-fees-and-rewards-ix";
+export * from "./update-fees-and-rewards-batch-ix";
@@ -0,0 +1,71 @@
+import { Program } from "@project-serum/anchor";
+import { Whirlpool } from "../artifacts/whirlpool";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Instruction } from "@orca-so/common-sdk";
+import { PublicKey } from "@solana/web3.js";
+
+/**
+ * Parameters to update the reward vault at a particular rewardIndex on a Whirlpool.
+ *
+ * @category Instruction Types
+ * @param whirlpool - PublicKey for the whirlpool to update. This whirlpool has to be part of the provided WhirlpoolsConfig space.
+ * @param whirlpoolsConfig - The public key for the WhirlpoolsConfig this pool is initialized in
+ * @param rewardIndex - The reward index that we'd like to update. (0 <= index <= NUM_REWARDS).
+ * @param rewardEmissionsSuperAuthority - The current rewardEmissionsSuperAuthority in the WhirlpoolsConfig
+ * @param oldRewardVault - The current reward vault at rewardIndex. Its balance is moved to the new reward vault.
+ * @param newRewardVault - The new reward vault token account. It must hold the reward mint and be owned by the whirlpool.
+ */
+export type SetRewardVaultBySuperAuthorityParams = {
+  whirlpool: PublicKey;
+  whirlpoolsConfig: PublicKey;
+  rewardIndex: number;
+  rewardEmissionsSuperAuthority: PublicKey;
+  oldRewardVault: PublicKey;
+  newRewardVault: PublicKey;
+};
+
+/**
+ * Set the whirlpool reward vault at the provided `reward_index`, to re-point a misconfigured vault.
+ * The balance of the old reward vault is moved to the new reward vault.
+ * Only the current reward super authority has permission to invoke this instruction.
+ *
+ * #### Special Errors
+ * - `InvalidRewardIndex` - If the provided reward index exceeds NUM_REWARDS or the reward is not initialized.
+ * - `RewardVaultMintMismatch` - If the new reward vault does not hold the reward mint.
+ * - `RewardVaultInUse` - If the new reward vault is a token vault of the whirlpool or the vault of another reward.
+ *
+ * @category Instructions
+ * @param context - Context object containing services required to generate the instruction
+ * @param params - SetRewardVaultBySuperAuthorityParams object
+ * @returns - Instruction to perform the action.
+ */
+export function setRewardVaultBySuperAuthorityIx(
+  program: Program<Whirlpool>,
+  params: SetRewardVaultBySuperAuthorityParams
+): Instruction {
+  const {
+    whirlpoolsConfig,
+    whirlpool,
+    rewardEmissionsSuperAuthority,
+    oldRewardVault,
+    newRewardVault,
+    rewardIndex,
+  } = params;
+
+  const ix = program.instruction.setRewardVaultBySuperAuthority(rewardIndex, {
+    accounts: {
+      whirlpoolsConfig,
+      whirlpool,
+      rewardEmissionsSuperAuthority,
+      oldRewardVault,
+      newRewardVault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    },
+  });
+
+  return {
+    instructions: [ix],
+    cleanupInstructions: [],
+    signers: [],
+  };
+}
@@ -0,0 +1,55 @@
+import { Program } from "@project-serum/anchor";
+import { Whirlpool } from "../artifacts/whirlpool";
+import { PublicKey } from "@solana/web3.js";
+
+import { Instruction } from "@orca-so/common-sdk";
+
+/**
+ * Parameters to update fees and reward values for a batch of positions.
+ *
+ * @category Instruction Types
+ * @param whirlpool - PublicKey for the whirlpool that the positions belong to.
+ * @param positions - PublicKeys for the positions to update.
+ * @param tickArrays - PublicKeys for the tick-array accounts that host the lower and upper ticks of the positions.
+ */
+export type UpdateFeesAndRewardsBatchParams = {
+  whirlpool: PublicKey;
+  positions: PublicKey[];
+  tickArrays: PublicKey[];
+};
+
+/**
+ * Update the accrued fees and rewards for a batch of positions of the same whirlpool.
+ * Positions with zero liquidity are skipped.
+ *
+ * #### Special Errors
+ * `TickNotFound` - None of the provided tick array accounts contains a tick of a position.
+ * `PositionWhirlpoolMismatch` - A position does not belong to the whirlpool.
+ *
+ * @category Instructions
+ * @param context - Context object containing services required to generate the instruction
+ * @param params - UpdateFeesAndRewardsBatchParams object
+ * @returns - Instruction to perform the action.
+ */
+export function updateFeesAndRewardsBatchIx(
+  program: Program<Whirlpool>,
+  params: UpdateFeesAndRewardsBatchParams
+): Instruction {
+  const { whirlpool, positions, tickArrays } = params;
+
+  const ix = program.instruction.updateFeesAndRewardsBatch(tickArrays.length, {
+    accounts: {
+      whirlpool,
+    },
+    remainingAccounts: [
+      ...tickArrays.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false })),
+      ...positions.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })),
+    ],
+  });
+
+  return {
+    instructions: [ix],
+    cleanupInstructions: [],
+    signers: [],
+  };
+}
@@ -206,6 +206,24 @@ export class WhirlpoolIx {
     return ix.updateFeesAndRewardsIx(program, params);
   }
 
+  /**
+   * Update the accrued fees and rewards for a batch of positions of the same whirlpool.
+   *
+   * #### Special Errors
+   * `TickNotFound` - None of the provided tick array accounts contains a tick of a position.
+   * `PositionWhirlpoolMismatch` - A position does not belong to the whirlpool.
+   *
+   * @param program - program object containing services required to generate the instruction
+   * @param params - UpdateFeesAndRewardsBatchParams object
+   * @returns - Instruction to perform the action.
+   */
+  public static updateFeesAndRewardsBatchIx(
+    program: Program<Whirlpool>,
+    params: ix.UpdateFeesAndRewardsBatchParams
+  ) {
+    return ix.updateFeesAndRewardsBatchIx(program, params);
+  }
+
   /**
    * Collect fees accrued for this position.
    * Call updateFeesAndRewards before this to update the position to the newest accrued values.
@@ -245,6 +263,20 @@ export class WhirlpoolIx {
     return ix.collectRewardIx(program, params);
   }
 
+  /**
+   * Update the accrued fees and rewards of a position and collect all of them in a single instruction.
+   *
+   * @param program - program object containing services required to generate the instruction
+   * @param params - CollectFeesAndRewardsParams object
+   * @returns - Instruction to perform the action.
+   */
+  public static collectFeesAndRewardsIx(
+    program: Program<Whirlpool>,
+    params: ix.CollectFeesAndRewardsParams
+  ) {
+    return ix.collectFeesAndRewardsIx(program, params);
+  }
+
   /**
    * Sets the fee authority to collect protocol fees for a WhirlpoolsConfig.
    * Only the current collect protocol fee authority has permission to invoke this instruction.
@@ -415,4 +447,25 @@ export class WhirlpoolIx {
   ) {
     return ix.setRewardEmissionsSuperAuthorityIx(program, params);
   }
+
+  /**
+   * Set the whirlpool reward vault at the provided `reward_index`, to re-point a misconfigured vault.
+   * The balance of the old reward vault is moved to the new reward vault.
+   * Only the current reward super authority has permission to invoke this instruction.
+   *
+   * #### Special Errors
+   * - `InvalidRewardIndex` - If the provided reward index exceeds NUM_REWARDS or the reward is not initialized.
+   * - `RewardVaultMintMismatch` - If the new reward vault does not hold the reward mint.
+   * - `RewardVaultInUse` - If the new reward vault is a token vault of the whirlpool or the vault of another reward.
+   *
+   * @param program - program object containing services required to generate the instruction
+   * @param params - SetRewardVaultBySuperAuthorityParams object
+   * @returns - Instruction to perform the action.
+   */
+  public static setRewardVaultBySuperAuthorityIx(
+    program: Program<Whirlpool>,
+    params: ix.SetRewardVaultBySuperAuthorityParams
+  ) {
+    return ix.setRewardVaultBySuperAuthorityIx(program, params);
+  }
 }
@@ -184,6 +184,58 @@ export class ParsableMintInfo {
   }
 }
 
+/**
+ * A Whirlpool program account, tagged with the name of the account type it was decoded as.
+ * @category Parsables
+ */
+export type WhirlpoolAccount =
+  | { accountName: AccountName.WhirlpoolsConfig; data: WhirlpoolsConfigData }
+  | { accountName: AccountName.Whirlpool; data: WhirlpoolData }
+  | { accountName: AccountName.Position; data: PositionData }
+  | { accountName: AccountName.TickArray; data: TickArrayData }
+  | { accountName: AccountName.FeeTier; data: FeeTierData };
+
+/**
+ * Parse the data of a Whirlpool program account whose type is not known ahead of time,
+ * by matching its 8-byte discriminator against every known account type.
+ * @category Parsables
+ */
+@staticImplements<ParsableEntity<WhirlpoolAccount>>()
+export class ParsableWhirlpoolAccount {
+  private constructor() {}
+
+  public static parse(data: Buffer | undefined | null): WhirlpoolAccount | null {
+    if (!data || data.length < 8) {
+      return null;
+    }
+
+    const accountName = Object.values(AccountName).find(
+      (name) => !getAccountDiscriminator(name).compare(data.slice(0, 8))
+    );
+    if (!accountName) {
+      return null;
+    }
+
+    try {
+      const parsed = parseAnchorAccount(accountName, data);
+      return parsed ? ({ accountName, data: parsed } as WhirlpoolAccount) : null;
+    } catch (e) {
+      console.error(`error while parsing ${accountName}: ${e}`);
+      return null;
+    }
+  }
+}
+
+/**
+ * Get the 8-byte Anchor discriminator that prefixes the data of a Whirlpool program account.
+ * @category Parsables
+ * @param accountName - The account type
+ * @returns the discriminator of the account type
+ */
+export function getAccountDiscriminator(accountName: AccountName): Buffer {
+  return BorshAccountsCoder.accountDiscriminator(accountName);
+}
+
 /**
  * Class decorator to define an interface with static methods
  * Reference: https://github.com/Microsoft/TypeScript/issues/13462#issuecomment-295685298
@@ -197,7 +249,7 @@ function staticImplements<T>() {
 const WhirlpoolCoder = new BorshAccountsCoder(WhirlpoolIDL as Idl);
 
 function parseAnchorAccount(accountName: AccountName, data: Buffer) {
-  const discriminator = BorshAccountsCoder.accountDiscriminator(accountName);
+  const discriminator = getAccountDiscriminator(accountName);
   if (discriminator.compare(data.slice(0, 8))) {
     console.error("incorrect account name during parsing");
     return null;
@@ -1,5 +1,7 @@
 import { MathUtil } from "@orca-so/common-sdk";
 import { BN } from "@project-serum/anchor";
+import Decimal from "decimal.js";
+import invariant from "tiny-invariant";
 import { PositionData, TickData, WhirlpoolData } from "../../types/public";
 
 /**
@@ -20,6 +22,20 @@ export type CollectFeesQuote = {
   feeOwedB: BN;
 };
 
+/**
+ * A snapshot of the fee growth inside a position's range, e.g. the `liquidity` and
+ * `feeGrowthCheckpointA/B` of a position right after its fees were updated.
+ *
+ * @category Quotes
+ */
+export type PositionFeeSnapshot = {
+  liquidity: BN;
+  feeGrowthInsideA: BN;
+  feeGrowthInsideB: BN;
+};
+
+const SECONDS_PER_YEAR = 60 * 60 * 24 * 365;
+
 /**
  * Get a quote on the outstanding fees owed to a position.
  *
@@ -114,3 +130,121 @@ export function collectFeesQuote(param: CollectFeesQuoteParam): CollectFeesQuote
     feeOwedB: updatedFeeOwedB,
   };
 }
+
+/**
+ * Get the total value of the fees in a quote, in the unit both token prices are expressed in
+ * (e.g. USD). Use this to rank positions by the value of the fees they earned.
+ *
+ * @category Quotes
+ * @param quote - A quote on the outstanding fees owed to a position
+ * @param decimalsA - The decimals of token A
+ * @param decimalsB - The decimals of token B
+ * @param priceA - The price of one token A
+ * @param priceB - The price of one token B
+ * @returns The total value of the fees owed in token A & B
+ */
+export function collectFeesQuoteValue(
+  quote: CollectFeesQuote,
+  decimalsA: number,
+  decimalsB: number,
+  priceA: Decimal,
+  priceB: Decimal
+): Decimal {
+  const feeA = new Decimal(quote.feeOwedA.toString()).div(Decimal.pow(10, decimalsA));
+  const feeB = new Decimal(quote.feeOwedB.toString()).div(Decimal.pow(10, decimalsB));
+  return feeA.mul(priceA).add(feeB.mul(priceB));
+}
+
+/**
+ * Get the fees a position earned between two snapshots of the fee growth inside its range.
+ *
+ * @category Quotes
+ * @param snapshotA - The earlier snapshot of the position
+ * @param snapshotB - The later snapshot of the position
+ * @returns A quote object containing the fees earned for each token in the pool between the snapshots.
+ */
+export function collectFeesQuoteFromSnapshots(
+  snapshotA: PositionFeeSnapshot,
+  snapshotB: PositionFeeSnapshot
+): CollectFeesQuote {
+  invariant(
+    snapshotA.liquidity.eq(snapshotB.liquidity),
+    "The liquidity of the position changed between the snapshots."
+  );
+
+  const { liquidity } = snapshotA;
+  return {
+    feeOwedA: MathUtil.subUnderflowU128(snapshotB.feeGrowthInsideA, snapshotA.feeGrowthInsideA)
+      .mul(liquidity)
+      .shrn(64),
+    feeOwedB: MathUtil.subUnderflowU128(snapshotB.feeGrowthInsideB, snapshotA.feeGrowthInsideB)
+      .mul(liquidity)
+      .shrn(64),
+  };
+}
+
+/**
+ * Get the annualized fee yield a position realized between two snapshots of the fee growth inside
+ * its range. The fees and the position are valued in the unit both token prices are expressed in.
+ *
+ * @category Quotes
+ * @param snapshotA - The earlier snapshot of the position
+ * @param snapshotB - The later snapshot of the position
+ * @param elapsedSeconds - The number of seconds between the snapshots
+ * @param positionValue - The value of the position
+ * @param decimalsA - The decimals of token A
+ * @param decimalsB - The decimals of token B
+ * @param priceA - The price of one token A
+ * @param priceB - The price of one token B
+ * @returns The fee APR as a fraction, e.g. 0.12 for 12%
+ */
+export function feeAprFromSnapshots(
+  snapshotA: PositionFeeSnapshot,
+  snapshotB: PositionFeeSnapshot,
+  elapsedSeconds: number,
+  positionValue: Decimal,
+  decimalsA: number,
+  decimalsB: number,
+  priceA: Decimal,
+  priceB: Decimal
+): Decimal {
+  invariant(elapsedSeconds > 0, "elapsedSeconds must be greater than 0.");
+  invariant(positionValue.gt(0), "positionValue must be greater than 0.");
+
+  const feesValue = collectFeesQuoteValue(
+    collectFeesQuoteFromSnapshots(snapshotA, snapshotB),
+    decimalsA,
+    decimalsB,
+    priceA,
+    priceB
+  );
+  return feesValue.div(positionValue).mul(SECONDS_PER_YEAR).div(elapsedSeconds);
+}
+
+/**
+ * Get the compounded yield of a position from its fee and reward APRs.
+ *
+ * The model assumes the fees and rewards are harvested and redeposited into the position
+ * `compoundsPerYear` times a year at evenly spaced intervals, and that the APRs, token prices and
+ * the position range stay constant: APY = (1 + (feeApr + rewardApr) / compoundsPerYear) ^ compoundsPerYear - 1.
+ * Transaction costs of compounding are not accounted for.
+ *
+ * @category Quotes
+ * @param feeApr - The fee APR as a fraction, e.g. 0.12 for 12%
+ * @param rewardApr - The reward APR as a fraction, e.g. 0.05 for 5%
+ * @param compoundsPerYear - The number of times the yield is compounded in a year, e.g. 365 for daily
+ * @returns The compounded APY as a fraction, e.g. 0.18 for 18%
+ */
+export function compoundedApy(
+  feeApr: Decimal,
+  rewardApr: Decimal,
+  compoundsPerYear: number
+): Decimal {
+  invariant(
+    Number.isInteger(compoundsPerYear) && compoundsPerYear > 0,
+    "compoundsPerYear must be a positive integer."
+  );
+
+  const apr = feeApr.add(rewardApr);
+  return apr.div(compoundsPerYear).add(1).pow(compoundsPerYear).sub(1);
+}
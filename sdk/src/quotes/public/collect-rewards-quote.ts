@@ -1,8 +1,18 @@
-import { MathUtil } from "@orca-so/common-sdk";
-import { BN } from "@project-serum/anchor";
+import { AddressUtil, MathUtil } from "@orca-so/common-sdk";
+import { Address, BN } from "@project-serum/anchor";
+import { PublicKey } from "@solana/web3.js";
 import invariant from "tiny-invariant";
-import { NUM_REWARDS, PositionData, TickData, WhirlpoolData } from "../../types/public";
+import { AccountFetcher } from "../../network/public";
+import {
+  NUM_REWARDS,
+  PositionData,
+  TickData,
+  WhirlpoolData,
+  WhirlpoolRewardInfoData,
+} from "../../types/public";
+import { PDAUtil } from "../../utils/public/pda-utils";
 import { PoolUtil } from "../../utils/public/pool-utils";
+import { TickArrayUtil } from "../../utils/public/tick-utils";
 
 /**
  * @category Quotes
@@ -19,6 +29,21 @@ export type CollectRewardsQuoteParam = {
  */
 export type CollectRewardsQuote = [BN | undefined, BN | undefined, BN | undefined];
 
+/**
+ * The outstanding amount of a reward owed to a position, labeled with the reward's token.
+ * @category Quotes
+ * @param index - The index of the reward in the Whirlpool's reward infos
+ * @param mint - The mint of the reward token
+ * @param decimals - The decimals of the reward token
+ * @param amount - The amount of reward token owed to the position
+ */
+export type RewardClaimable = {
+  index: number;
+  mint: PublicKey;
+  decimals: number;
+  amount: BN;
+};
+
 /**
  * Get a quote on the outstanding rewards owed to a position.
  *
@@ -123,3 +148,150 @@ export function collectRewardsQuote(param: CollectRewardsQuoteParam): CollectRew
 
   return [rewardOwedA, rewardOwedB, rewardOwedC];
 }
+
+/**
+ * Get a quote on the outstanding rewards owed to a position for every initialized reward of its Whirlpool.
+ *
+ * Unlike {@link collectRewardsQuote}, the Whirlpool's global reward growths are accrued up to the provided
+ * timestamp, so the quote includes rewards emitted since the Whirlpool was last updated.
+ *
+ * @category Quotes
+ * @param positionAddress - The address of the position to quote rewards for
+ * @param programId - PublicKey for the Whirlpool ProgramId
+ * @param fetcher - AccountFetcher object to fetch solana accounts
+ * @param refresh - If true, fetcher would default to fetching the latest accounts
+ * @param timestampInSeconds - The unix timestamp to accrue rewards up to. Defaults to the current time.
+ * @returns A list of the rewards owed to the position, one entry per initialized reward.
+ */
+export async function collectAllRewardsQuote(
+  positionAddress: Address,
+  programId: Address,
+  fetcher: AccountFetcher,
+  refresh: boolean,
+  timestampInSeconds: BN = new BN(Math.floor(Date.now() / 1000))
+): Promise<RewardClaimable[]> {
+  const position = await fetcher.getPosition(positionAddress, refresh);
+  invariant(!!position, `Position not found: ${AddressUtil.toPubKey(positionAddress).toBase58()}`);
+  const whirlpool = await fetcher.getPool(position.whirlpool, refresh);
+  invariant(!!whirlpool, `Whirlpool not found: ${position.whirlpool.toBase58()}`);
+
+  const [tickArrayLower, tickArrayUpper] = await fetcher.listTickArrays(
+    [position.tickLowerIndex, position.tickUpperIndex].map(
+      (tickIndex) =>
+        PDAUtil.getTickArrayFromTickIndex(
+          tickIndex,
+          whirlpool.tickSpacing,
+          position.whirlpool,
+          AddressUtil.toPubKey(programId)
+        ).publicKey
+    ),
+    refresh
+  );
+  invariant(!!tickArrayLower, "tickArrayLower cannot be null");
+  invariant(!!tickArrayUpper, "tickArrayUpper cannot be null");
+
+  const quote = collectRewardsQuote({
+    whirlpool: {
+      ...whirlpool,
+      rewardInfos: getNextRewardInfos(whirlpool, timestampInSeconds),
+    },
+    position,
+    tickLower: TickArrayUtil.getTickFromArray(
+      tickArrayLower,
+      position.tickLowerIndex,
+      whirlpool.tickSpacing
+    ),
+    tickUpper: TickArrayUtil.getTickFromArray(
+      tickArrayUpper,
+      position.tickUpperIndex,
+      whirlpool.tickSpacing
+    ),
+  });
+
+  const initializedIndices = [...Array(NUM_REWARDS).keys()].filter(
+    (index) => quote[index] !== undefined
+  );
+  const mintInfos = await fetcher.listMintInfos(
+    initializedIndices.map((index) => whirlpool.rewardInfos[index].mint),
+    refresh
+  );
+
+  return initializedIndices.map((index, i) => {
+    const mintInfo = mintInfos[i];
+    const mint = whirlpool.rewardInfos[index].mint;
+    invariant(!!mintInfo, `Reward mint not found: ${mint.toBase58()}`);
+    return {
+      index,
+      mint,
+      decimals: mintInfo.decimals,
+      amount: quote[index]!,
+    };
+  });
+}
+
+/**
+ * @category Quotes
+ * @param targetTimestamp - The unix timestamp to project the rewards owed to
+ * @param rewardVaultAmounts - The amounts held in the Whirlpool's reward vaults, indexed like the Whirlpool's reward infos.
+ *                             A reward without a vault amount is projected without a cap.
+ */
+export type ProjectedRewardsQuoteParam = CollectRewardsQuoteParam & {
+  targetTimestamp: BN;
+  rewardVaultAmounts?: (BN | undefined)[];
+};
+
+/**
+ * Get a quote on the rewards a position would be owed at a future timestamp, assuming the Whirlpool's
+ * liquidity, price and reward emissions stay unchanged until then.
+ *
+ * The projection of each reward stops when its vault would be exhausted at the current emissions rate.
+ * The vault also holds rewards already accrued to positions but not collected yet, so the cap is an upper bound.
+ *
+ * @category Quotes
+ * @param param A collection of fetched Whirlpool accounts & the reward vault amounts to faciliate the quote.
+ * @returns A quote object containing the projected rewards owed for each reward in the pool.
+ */
+export function projectedRewardsQuote(param: ProjectedRewardsQuoteParam): CollectRewardsQuote {
+  const { whirlpool, targetTimestamp, rewardVaultAmounts, ...rest } = param;
+
+  const rewardInfos = whirlpool.rewardInfos.map((rewardInfo, index) => {
+    const vaultAmount = rewardVaultAmounts?.[index];
+    const timestamp =
+      !!vaultAmount && !rewardInfo.emissionsPerSecondX64.isZero()
+        ? BN.min(
+            targetTimestamp,
+            whirlpool.rewardLastUpdatedTimestamp.add(
+              vaultAmount.shln(64).div(rewardInfo.emissionsPerSecondX64)
+            )
+          )
+        : targetTimestamp;
+    return getNextRewardInfos(whirlpool, timestamp)[index];
+  });
+
+  return collectRewardsQuote({ ...rest, whirlpool: { ...whirlpool, rewardInfos } });
+}
+
+/*** Private ***/
+
+// Mirrors next_whirlpool_reward_infos in the Whirlpool program
+function getNextRewardInfos(
+  whirlpool: WhirlpoolData,
+  nextTimestamp: BN
+): WhirlpoolRewardInfoData[] {
+  const { liquidity, rewardInfos, rewardLastUpdatedTimestamp } = whirlpool;
+  if (liquidity.isZero() || nextTimestamp.lte(rewardLastUpdatedTimestamp)) {
+    return rewardInfos;
+  }
+
+  const timeDelta = nextTimestamp.sub(rewardLastUpdatedTimestamp);
+  return rewardInfos.map((rewardInfo) => {
+    if (!PoolUtil.isRewardInitialized(rewardInfo)) {
+      return rewardInfo;
+    }
+    const rewardGrowthDelta = timeDelta.mul(rewardInfo.emissionsPerSecondX64).div(liquidity);
+    return {
+      ...rewardInfo,
+      growthGlobalX64: rewardInfo.growthGlobalX64.add(rewardGrowthDelta).maskn(128),
+    };
+  });
+}
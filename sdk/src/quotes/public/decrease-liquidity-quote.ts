@@ -71,6 +71,77 @@ export async function decreaseLiquidityQuoteByLiquidity(
   });
 }
 
+/**
+ * @category Quotes
+ * @param fraction - The fraction of the position's liquidity to withdraw, between 0 and 1.
+ * @param slippageTolerance - The maximum slippage allowed when calculating the minimum tokens received.
+ */
+export type DecreaseLiquidityFractionInput = {
+  fraction: Percentage;
+  slippageTolerance: Percentage;
+};
+
+/**
+ * @category Quotes
+ * @param positionLiquidity - The liquidity of the position that we are withdrawing from.
+ * @param fraction - The fraction of the position's liquidity to withdraw, between 0 and 1.
+ */
+export type DecreaseLiquidityFractionQuoteParam = Omit<DecreaseLiquidityQuoteParam, "liquidity"> & {
+  positionLiquidity: u64;
+  fraction: Percentage;
+};
+
+/**
+ * Get an estimated quote on the minimum tokens receivable when withdrawing a fraction of a position's liquidity.
+ *
+ * @category Quotes
+ * @param fraction - The fraction of the position's liquidity to withdraw, between 0 and 1.
+ * @param slippageTolerance - The maximum slippage allowed when calculating the minimum tokens received.
+ * @param position - A Position helper class to help interact with the Position account.
+ * @param whirlpool - A Whirlpool helper class to help interact with the Whirlpool account.
+ * @returns An DecreaseLiquidityQuote object detailing the tokenMin & liquidity values to use when calling decrease-liquidity-ix.
+ */
+export async function decreaseLiquidityQuoteByFraction(
+  fraction: Percentage,
+  slippageTolerance: Percentage,
+  position: Position,
+  whirlpool: Whirlpool
+) {
+  const positionData = position.getData();
+  const whirlpoolData = whirlpool.getData();
+
+  return decreaseLiquidityQuoteByFractionWithParams({
+    positionLiquidity: positionData.liquidity,
+    fraction,
+    slippageTolerance,
+    tickLowerIndex: positionData.tickLowerIndex,
+    tickUpperIndex: positionData.tickUpperIndex,
+    sqrtPrice: whirlpoolData.sqrtPrice,
+    tickCurrentIndex: whirlpoolData.tickCurrentIndex,
+  });
+}
+
+/**
+ * Get an estimated quote on the minimum tokens receivable when withdrawing a fraction of a position's liquidity.
+ * The withdrawn liquidity is rounded down, so a fraction of 1 withdraws the whole position.
+ *
+ * @category Quotes
+ * @param param DecreaseLiquidityFractionQuoteParam
+ * @returns An DecreaseLiquidityInput object detailing the tokenMin & liquidity values to use when calling decrease-liquidity-ix.
+ */
+export function decreaseLiquidityQuoteByFractionWithParams(
+  param: DecreaseLiquidityFractionQuoteParam
+): DecreaseLiquidityQuote {
+  const { positionLiquidity, fraction, ...rest } = param;
+  invariant(
+    !fraction.denominator.isZero() && fraction.numerator.lte(fraction.denominator),
+    "fraction must be between 0 and 1."
+  );
+
+  const liquidity = positionLiquidity.mul(fraction.numerator).div(fraction.denominator);
+  return decreaseLiquidityQuoteByLiquidityWithParams({ ...rest, liquidity });
+}
+
 /**
  * Get an estimated quote on the minimum tokens receivable based on the desired withdraw liquidity value.
  *
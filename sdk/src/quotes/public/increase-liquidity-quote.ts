@@ -119,6 +119,145 @@ export function increaseLiquidityQuoteByInputTokenWithParams(
   }
 }
 
+/**
+ * @category Quotes
+ * @param tokenAmountA - The maximum amount of tokenA to deposit.
+ * @param tokenAmountB - The maximum amount of tokenB to deposit.
+ * @param tickCurrentIndex - The Whirlpool's current tickIndex
+ * @param sqrtPrice - The Whirlpool's current sqrtPrice
+ * @param tickLowerIndex - The lower index of the position that we are depositing into.
+ * @param tickUpperIndex - The upper index of the position that we are depositing into.
+ * @param slippageTolerance - The maximum slippage allowed when calculating the maximum tokens deposited.
+ */
+export type IncreaseLiquidityTokenAmountsQuoteParam = {
+  tokenAmountA: u64;
+  tokenAmountB: u64;
+  tickCurrentIndex: number;
+  sqrtPrice: BN;
+  tickLowerIndex: number;
+  tickUpperIndex: number;
+  slippageTolerance: Percentage;
+};
+
+/**
+ * Get an estimated quote on the maximum liquidity that can be deposited with the specified token amounts.
+ *
+ * @category Quotes
+ * @param tokenAmountA - The maximum amount of tokenA to deposit.
+ * @param tokenAmountB - The maximum amount of tokenB to deposit.
+ * @param tickLower - The lower index of the position that we are depositing into.
+ * @param tickUpper - The upper index of the position that we are depositing into.
+ * @param slippageTolerance - The maximum slippage allowed when calculating the maximum tokens deposited.
+ * @param whirlpool - A Whirlpool helper class to help interact with the Whirlpool account.
+ * @returns An IncreaseLiquidityInput object detailing the required token amounts & liquidity values to use when calling increase-liquidity-ix.
+ */
+export function increaseLiquidityQuoteByTokenAmounts(
+  tokenAmountA: Decimal,
+  tokenAmountB: Decimal,
+  tickLower: number,
+  tickUpper: number,
+  slippageTolerance: Percentage,
+  whirlpool: Whirlpool
+) {
+  const data = whirlpool.getData();
+
+  return increaseLiquidityQuoteByTokenAmountsWithParams({
+    tokenAmountA: DecimalUtil.toU64(tokenAmountA, whirlpool.getTokenAInfo().decimals),
+    tokenAmountB: DecimalUtil.toU64(tokenAmountB, whirlpool.getTokenBInfo().decimals),
+    tickLowerIndex: TickUtil.getInitializableTickIndex(tickLower, data.tickSpacing),
+    tickUpperIndex: TickUtil.getInitializableTickIndex(tickUpper, data.tickSpacing),
+    slippageTolerance,
+    ...data,
+  });
+}
+
+/**
+ * Get an estimated quote on the maximum liquidity that can be deposited with the specified token amounts.
+ * The liquidity is limited by whichever token amount runs out first at the current price, so the
+ * estimated deposit of the other token is usually less than its specified amount.
+ *
+ * @category Quotes
+ * @param param IncreaseLiquidityTokenAmountsQuoteParam
+ * @returns An IncreaseLiquidityInput object detailing the required token amounts & liquidity values to use when calling increase-liquidity-ix.
+ */
+export function increaseLiquidityQuoteByTokenAmountsWithParams(
+  param: IncreaseLiquidityTokenAmountsQuoteParam
+): IncreaseLiquidityQuote {
+  const {
+    tokenAmountA,
+    tokenAmountB,
+    tickCurrentIndex,
+    sqrtPrice,
+    tickLowerIndex,
+    tickUpperIndex,
+    slippageTolerance,
+  } = param;
+  invariant(TickUtil.checkTickInBounds(tickLowerIndex), "tickLowerIndex is out of bounds.");
+  invariant(TickUtil.checkTickInBounds(tickUpperIndex), "tickUpperIndex is out of bounds.");
+
+  const sqrtPriceLowerX64 = PriceMath.tickIndexToSqrtPriceX64(tickLowerIndex);
+  const sqrtPriceUpperX64 = PriceMath.tickIndexToSqrtPriceX64(tickUpperIndex);
+
+  let liquidityAmount: BN;
+  let tokenEstA: BN = ZERO;
+  let tokenEstB: BN = ZERO;
+
+  const positionStatus = PositionUtil.getPositionStatus(
+    tickCurrentIndex,
+    tickLowerIndex,
+    tickUpperIndex
+  );
+
+  switch (positionStatus) {
+    case PositionStatus.BelowRange:
+      liquidityAmount = getLiquidityFromTokenA(
+        tokenAmountA,
+        sqrtPriceLowerX64,
+        sqrtPriceUpperX64,
+        false
+      );
+      tokenEstA = getTokenAFromLiquidity(
+        liquidityAmount,
+        sqrtPriceLowerX64,
+        sqrtPriceUpperX64,
+        true
+      );
+      break;
+    case PositionStatus.InRange:
+      liquidityAmount = BN.min(
+        getLiquidityFromTokenA(tokenAmountA, sqrtPrice, sqrtPriceUpperX64, false),
+        getLiquidityFromTokenB(tokenAmountB, sqrtPriceLowerX64, sqrtPrice, false)
+      );
+      tokenEstA = getTokenAFromLiquidity(liquidityAmount, sqrtPrice, sqrtPriceUpperX64, true);
+      tokenEstB = getTokenBFromLiquidity(liquidityAmount, sqrtPriceLowerX64, sqrtPrice, true);
+      break;
+    case PositionStatus.AboveRange:
+      liquidityAmount = getLiquidityFromTokenB(
+        tokenAmountB,
+        sqrtPriceLowerX64,
+        sqrtPriceUpperX64,
+        false
+      );
+      tokenEstB = getTokenBFromLiquidity(
+        liquidityAmount,
+        sqrtPriceLowerX64,
+        sqrtPriceUpperX64,
+        true
+      );
+      break;
+    default:
+      throw new Error(`type ${positionStatus} is an unknown PositionStatus`);
+  }
+
+  return {
+    tokenMaxA: adjustForSlippage(tokenEstA, slippageTolerance, true),
+    tokenMaxB: adjustForSlippage(tokenEstB, slippageTolerance, true),
+    tokenEstA,
+    tokenEstB,
+    liquidityAmount,
+  };
+}
+
 /*** Private ***/
 
 function quotePositionBelowRange(param: IncreaseLiquidityQuoteParam): IncreaseLiquidityQuote {
@@ -157,10 +157,13 @@ export class PDAUtil {
   }
 
   /**
+   * Derive the Oracle PDA of a Whirlpool, from the seeds ["oracle", whirlpool] that the swap instruction
+   * validates its oracle account against.
+   *
    * @category Program Derived Addresses
-   * @param programId
-   * @param whirlpoolAddress
-   * @returns
+   * @param programId - The Whirlpool program id
+   * @param whirlpoolAddress - The address of the Whirlpool the oracle belongs to
+   * @returns the oracle address and its bump
    */
   public static getOracle(programId: PublicKey, whirlpoolAddress: PublicKey) {
     return AddressUtil.findProgramAddress(
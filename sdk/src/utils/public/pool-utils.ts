@@ -1,9 +1,19 @@
 import { AddressUtil, MathUtil, Percentage } from "@orca-so/common-sdk";
-import { Address, BN } from "@project-serum/anchor";
+import { Address, BN, BorshAccountsCoder, utils } from "@project-serum/anchor";
 import { u64 } from "@solana/spl-token";
-import { PublicKey } from "@solana/web3.js";
+import { Connection, PublicKey } from "@solana/web3.js";
 import Decimal from "decimal.js";
-import { WhirlpoolData, WhirlpoolRewardInfoData } from "../../types/public";
+import invariant from "tiny-invariant";
+import { SwapErrorCode, WhirlpoolsError } from "../../errors/errors";
+import { AccountFetcher, ParsablePosition, ParsableWhirlpool } from "../../network/public";
+import {
+  AccountName,
+  FeeTierData,
+  PositionData,
+  WhirlpoolData,
+  WhirlpoolRewardInfoData,
+} from "../../types/public";
+import { PDAUtil } from "./pda-utils";
 import { PriceMath } from "./price-math";
 import { TokenType } from "./types";
 
@@ -35,6 +45,67 @@ export class PoolUtil {
     return undefined;
   }
 
+  /**
+   * Check that token vault accounts supplied for an instruction are the token vaults of a Whirlpool.
+   *
+   * Use this when assembling instructions such as {@link WhirlpoolIx.swapIx} from vault accounts that
+   * do not come from the fetched Whirlpool. {@link Whirlpool.swap} reads the vaults from the fetched
+   * Whirlpool and needs no validation.
+   *
+   * @param pool The Whirlpool the instruction is for
+   * @param tokenVaultA The supplied token A vault
+   * @param tokenVaultB The supplied token B vault
+   * @throws WhirlpoolsError with TokenVaultMismatch if either vault does not match the Whirlpool
+   */
+  public static validateTokenVaults(
+    pool: WhirlpoolData,
+    tokenVaultA: Address,
+    tokenVaultB: Address
+  ): void {
+    const expected = { tokenVaultA: pool.tokenVaultA, tokenVaultB: pool.tokenVaultB };
+    const supplied = {
+      tokenVaultA: AddressUtil.toPubKey(tokenVaultA),
+      tokenVaultB: AddressUtil.toPubKey(tokenVaultB),
+    };
+    (["tokenVaultA", "tokenVaultB"] as const).forEach((vault) => {
+      const expectedVault = expected[vault].toBase58();
+      const suppliedVault = supplied[vault].toBase58();
+      if (expectedVault !== suppliedVault) {
+        throw new WhirlpoolsError(
+          `${vault} mismatch - expected ${expectedVault}, received ${suppliedVault}`,
+          SwapErrorCode.TokenVaultMismatch
+        );
+      }
+    });
+  }
+
+  /**
+   * Check that an oracle account supplied for a swap is the oracle PDA of a Whirlpool.
+   * A mismatching oracle fails the seeds constraint of the swap instruction.
+   *
+   * Use this when assembling {@link WhirlpoolIx.swapIx} with an oracle account from elsewhere.
+   * {@link Whirlpool.swap} derives the oracle with {@link PDAUtil.getOracle} itself and needs no
+   * validation.
+   *
+   * @param programId The Whirlpool program id
+   * @param whirlpool The address of the Whirlpool the swap is for
+   * @param oracle The supplied oracle account
+   * @throws WhirlpoolsError with OracleMismatch if the oracle is not the oracle PDA of the Whirlpool
+   */
+  public static validateOracle(programId: Address, whirlpool: Address, oracle: Address): void {
+    const expectedOracle = PDAUtil.getOracle(
+      AddressUtil.toPubKey(programId),
+      AddressUtil.toPubKey(whirlpool)
+    ).publicKey.toBase58();
+    const suppliedOracle = AddressUtil.toPubKey(oracle).toBase58();
+    if (expectedOracle !== suppliedOracle) {
+      throw new WhirlpoolsError(
+        `oracle mismatch - expected ${expectedOracle}, received ${suppliedOracle}`,
+        SwapErrorCode.OracleMismatch
+      );
+    }
+  }
+
   public static getFeeRate(feeRate: number): Percentage {
     /**
      * Smart Contract comment: https://github.com/orca-so/whirlpool/blob/main/programs/whirlpool/src/state/whirlpool.rs#L9-L11
@@ -45,6 +116,132 @@ export class PoolUtil {
     return Percentage.fromFraction(feeRate, 1e6); // TODO
   }
 
+  /**
+   * Return the fee rate a new Whirlpool initialized with this fee tier would adopt.
+   *
+   * @param feeTier - The fee tier the Whirlpool would be initialized with
+   * @returns The default fee rate of the fee tier as a Percentage
+   */
+  public static getFeeTierFeeRate(feeTier: FeeTierData): Percentage {
+    return PoolUtil.getFeeRate(feeTier.defaultFeeRate);
+  }
+
+  /**
+   * Fetch the fee tier of a WhirlpoolsConfig & tick spacing and return the fee rate a new Whirlpool
+   * initialized with it would adopt. Use this to preview the fee rate before initializing a pool.
+   *
+   * @param fetcher - AccountFetcher object to fetch the FeeTier account
+   * @param programId - The Whirlpool program ID
+   * @param whirlpoolsConfig - The address of the WhirlpoolsConfig the Whirlpool would belong to
+   * @param tickSpacing - The tick spacing of the Whirlpool
+   * @param refresh - Force the fetcher to refresh the FeeTier account
+   * @returns The default fee rate of the fee tier as a Percentage. null if the fee tier does not exist.
+   */
+  public static async getInitialFeeRate(
+    fetcher: AccountFetcher,
+    programId: PublicKey,
+    whirlpoolsConfig: Address,
+    tickSpacing: number,
+    refresh = false
+  ): Promise<Percentage | null> {
+    const feeTierPda = PDAUtil.getFeeTier(
+      programId,
+      AddressUtil.toPubKey(whirlpoolsConfig),
+      tickSpacing
+    );
+    const feeTier = await fetcher.getFeeTier(feeTierPda.publicKey, refresh);
+    return feeTier ? PoolUtil.getFeeTierFeeRate(feeTier) : null;
+  }
+
+  /**
+   * Find all Whirlpools initialized with a fee tier, i.e. all Whirlpools in the fee tier's
+   * WhirlpoolsConfig with the same tick spacing.
+   *
+   * Note: The fee rate of a Whirlpool can be changed after initialization, so the returned
+   * Whirlpools may no longer use the default fee rate of the fee tier.
+   *
+   * @param connection - Connection object to query the Whirlpool program accounts
+   * @param programId - The Whirlpool program ID
+   * @param feeTier - The fee tier to find the Whirlpools of
+   * @returns The address & data of every Whirlpool using the fee tier
+   */
+  public static async getPoolsForFeeTier(
+    connection: Connection,
+    programId: PublicKey,
+    feeTier: FeeTierData
+  ): Promise<{ address: PublicKey; data: WhirlpoolData }[]> {
+    const tickSpacing = new BN(feeTier.tickSpacing).toArrayLike(Buffer, "le", 2);
+    const accounts = await connection.getProgramAccounts(programId, {
+      filters: [
+        {
+          memcmp: {
+            offset: 0,
+            bytes: utils.bytes.bs58.encode(
+              BorshAccountsCoder.accountDiscriminator(AccountName.Whirlpool)
+            ),
+          },
+        },
+        { memcmp: { offset: 8, bytes: feeTier.whirlpoolsConfig.toBase58() } },
+        { memcmp: { offset: 41, bytes: utils.bytes.bs58.encode(tickSpacing) } },
+      ],
+    });
+
+    const pools: { address: PublicKey; data: WhirlpoolData }[] = [];
+    accounts.forEach(({ pubkey, account }) => {
+      const data = ParsableWhirlpool.parse(account.data);
+      if (data) {
+        pools.push({ address: pubkey, data });
+      }
+    });
+    return pools;
+  }
+
+  /**
+   * Find all positions of a Whirlpool whose range overlaps the tick window [tickLowerIndex, tickUpperIndex).
+   *
+   * The positions of the Whirlpool are filtered on-chain with memcmp filters. RPC nodes can not filter
+   * on ranges of values, so the overlap with the tick window is checked after decoding the positions.
+   *
+   * @param connection - Connection object to query the Whirlpool program accounts
+   * @param programId - The Whirlpool program ID
+   * @param whirlpool - The address of the Whirlpool to find the positions of
+   * @param tickLowerIndex - The lower tick index of the window, inclusive
+   * @param tickUpperIndex - The upper tick index of the window, exclusive
+   * @returns The address & data of every position of the Whirlpool overlapping the tick window
+   */
+  public static async getPositionsInTickRange(
+    connection: Connection,
+    programId: PublicKey,
+    whirlpool: Address,
+    tickLowerIndex: number,
+    tickUpperIndex: number
+  ): Promise<{ address: PublicKey; data: PositionData }[]> {
+    invariant(tickLowerIndex < tickUpperIndex, "tickLowerIndex must be less than tickUpperIndex");
+
+    const accounts = await connection.getProgramAccounts(programId, {
+      filters: [
+        {
+          memcmp: {
+            offset: 0,
+            bytes: utils.bytes.bs58.encode(
+              BorshAccountsCoder.accountDiscriminator(AccountName.Position)
+            ),
+          },
+        },
+        { memcmp: { offset: 8, bytes: AddressUtil.toPubKey(whirlpool).toBase58() } },
+      ],
+    });
+
+    const positions: { address: PublicKey; data: PositionData }[] = [];
+    accounts.forEach(({ pubkey, account }) => {
+      const data = ParsablePosition.parse(account.data);
+      if (data && data.tickLowerIndex < tickUpperIndex && data.tickUpperIndex > tickLowerIndex) {
+        positions.push({ address: pubkey, data });
+      }
+    });
+    return positions;
+  }
+
   public static getProtocolFeeRate(protocolFeeRate: number): Percentage {
     /**
      * Smart Contract comment: https://github.com/orca-so/whirlpool/blob/main/programs/whirlpool/src/state/whirlpool.rs#L13-L14
@@ -188,6 +385,66 @@ export class PoolUtil {
     const pair: [PublicKey, PublicKey] = [tokenMintAKey, tokenMintBKey];
     return pair.sort(sortByQuotePriority);
   }
+
+  /**
+   * Check that a price stayed within the bounds expected when a swap was simulated.
+   * The bounds span the pre-swap & expected post-swap prices, widened by the tolerance on each side.
+   *
+   * @param preSwapSqrtPrice - The sqrt-price of the Whirlpool when the swap was simulated
+   * @param expectedPostSwapSqrtPrice - The sqrt-price the Whirlpool is expected to reach after the swap
+   * @param sqrtPrice - The sqrt-price to check
+   * @param tolerance - The allowed price drift outside of the expected range
+   * @returns true if the price is within bounds, false otherwise
+   */
+  public static isPriceWithinSwapBounds(
+    preSwapSqrtPrice: BN,
+    expectedPostSwapSqrtPrice: BN,
+    sqrtPrice: BN,
+    tolerance: Percentage
+  ): boolean {
+    // Compare squared sqrt-prices, which are proportional to the price
+    const preSwapPrice = preSwapSqrtPrice.sqr();
+    const postSwapPrice = expectedPostSwapSqrtPrice.sqr();
+    const price = sqrtPrice.sqr();
+
+    const { numerator, denominator } = tolerance;
+    const lowerBound = BN.min(preSwapPrice, postSwapPrice)
+      .mul(BN.max(denominator.sub(numerator), new BN(0)))
+      .div(denominator);
+    const upperBound = BN.max(preSwapPrice, postSwapPrice)
+      .mul(denominator.add(numerator))
+      .div(denominator);
+
+    return price.gte(lowerBound) && price.lte(upperBound);
+  }
+
+  /**
+   * Re-fetch a Whirlpool and check that its current price has not drifted from the pre-swap price
+   * a swap was simulated at. Use this before sending a swap to detect price movement since the quote.
+   *
+   * @param fetcher - AccountFetcher object to fetch the Whirlpool account
+   * @param poolAddress - The address of the Whirlpool
+   * @param preSwapSqrtPrice - The sqrt-price of the Whirlpool when the swap was simulated
+   * @param tolerance - The allowed price drift from the pre-swap price
+   * @returns true if the current price of the Whirlpool is within bounds, false otherwise
+   */
+  public static async isPoolPriceWithinTolerance(
+    fetcher: AccountFetcher,
+    poolAddress: Address,
+    preSwapSqrtPrice: BN,
+    tolerance: Percentage
+  ): Promise<boolean> {
+    const pool = await fetcher.getPool(poolAddress, true);
+    if (!pool) {
+      throw new Error(`Whirlpool not found: ${AddressUtil.toPubKey(poolAddress).toBase58()}`);
+    }
+    return PoolUtil.isPriceWithinSwapBounds(
+      preSwapSqrtPrice,
+      preSwapSqrtPrice,
+      pool.sqrtPrice,
+      tolerance
+    );
+  }
 }
 
 /**
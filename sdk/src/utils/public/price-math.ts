@@ -2,7 +2,7 @@ import { MathUtil } from "@orca-so/common-sdk";
 import { BN } from "@project-serum/anchor";
 import Decimal from "decimal.js";
 import { MAX_SQRT_PRICE, MIN_SQRT_PRICE } from "../../types/public";
-import { TickUtil } from "./tick-utils";
+import { TickRounding, TickUtil } from "./tick-utils";
 
 const BIT_PRECISION = 14;
 const LOG_B_2_X32 = "59543866431248";
@@ -15,10 +15,28 @@ const LOG_B_P_ERR_MARGIN_UPPER_X64 = "15793534762490258745";
  * @category Whirlpool Utils
  */
 export class PriceMath {
+  /**
+   * Convert the decimals-adjusted price of token A in terms of token B (i.e. how much token B one
+   * token A is worth) into the Whirlpool's sqrt-price.
+   *
+   * @param price - The price of 1 token A in token B, decimals adjusted
+   * @param decimalsA - The decimals of token A
+   * @param decimalsB - The decimals of token B
+   * @returns The sqrt-price of the price, as a Q64.64 number
+   */
   public static priceToSqrtPriceX64(price: Decimal, decimalsA: number, decimalsB: number): BN {
     return MathUtil.toX64(price.mul(Decimal.pow(10, decimalsB - decimalsA)).sqrt());
   }
 
+  /**
+   * Convert the Whirlpool's sqrt-price into the decimals-adjusted price of token A in terms of token B
+   * (i.e. how much token B one token A is worth).
+   *
+   * @param sqrtPriceX64 - The sqrt-price, as a Q64.64 number
+   * @param decimalsA - The decimals of token A
+   * @param decimalsB - The decimals of token B
+   * @returns The price of 1 token A in token B, decimals adjusted
+   */
   public static sqrtPriceX64ToPrice(
     sqrtPriceX64: BN,
     decimalsA: number,
@@ -98,6 +116,14 @@ export class PriceMath {
     }
   }
 
+  /**
+   * Convert a tick index into the decimals-adjusted price of token A in terms of token B.
+   *
+   * @param tickIndex - The tick index
+   * @param decimalsA - The decimals of token A
+   * @param decimalsB - The decimals of token B
+   * @returns The price of 1 token A in token B at the tick, decimals adjusted
+   */
   public static tickIndexToPrice(tickIndex: number, decimalsA: number, decimalsB: number): Decimal {
     return PriceMath.sqrtPriceX64ToPrice(
       PriceMath.tickIndexToSqrtPriceX64(tickIndex),
@@ -106,22 +132,72 @@ export class PriceMath {
     );
   }
 
+  /**
+   * Convert the decimals-adjusted price of token A in terms of token B into the tick index the price is in,
+   * rounded down.
+   *
+   * @param price - The price of 1 token A in token B, decimals adjusted
+   * @param decimalsA - The decimals of token A
+   * @param decimalsB - The decimals of token B
+   * @returns The tick index of the price
+   */
   public static priceToTickIndex(price: Decimal, decimalsA: number, decimalsB: number): number {
     return PriceMath.sqrtPriceX64ToTickIndex(
       PriceMath.priceToSqrtPriceX64(price, decimalsA, decimalsB)
     );
   }
 
+  /**
+   * Convert the decimals-adjusted price of token A in terms of token B into an initializable tick index.
+   *
+   * @param price - The price of 1 token A in token B, decimals adjusted
+   * @param decimalsA - The decimals of token A
+   * @param decimalsB - The decimals of token B
+   * @param tickSpacing - Tick spacing for the whirlpool
+   * @param round - The direction to round the tick index of the price in. If omitted, the tick index is rounded towards zero.
+   * @returns The initializable tick index of the price
+   * @throws if `round` is provided and the rounded tick index is out of bounds
+   */
   public static priceToInitializableTickIndex(
     price: Decimal,
     decimalsA: number,
     decimalsB: number,
-    tickSpacing: number
+    tickSpacing: number,
+    round?: TickRounding
   ): number {
-    return TickUtil.getInitializableTickIndex(
-      PriceMath.priceToTickIndex(price, decimalsA, decimalsB),
-      tickSpacing
-    );
+    const tickIndex = PriceMath.priceToTickIndex(price, decimalsA, decimalsB);
+    if (round === undefined) {
+      return TickUtil.getInitializableTickIndex(tickIndex, tickSpacing);
+    }
+    return TickUtil.getNearestInitializableTickIndex(tickIndex, tickSpacing, round);
+  }
+
+  /**
+   * Invert a price of token A in terms of token B into a price of token B in terms of token A.
+   * Inverting the returned price gives back the original price.
+   *
+   * @param price - The price to invert
+   * @returns The inverted price
+   */
+  public static invertPrice(price: Decimal): Decimal {
+    return new Decimal(1).div(price);
+  }
+
+  /**
+   * Invert a raw price of token A in terms of token B, expressed in the smallest units of both
+   * tokens, into the decimals-adjusted price of token B in terms of token A.
+   *
+   * @param rawPrice - The price of token A in terms of token B, not adjusted for decimals
+   * @param decimalsA - The decimals of token A
+   * @param decimalsB - The decimals of token B
+   * @returns The decimals-adjusted price of token B in terms of token A
+   */
+  public static invertPriceWithDecimals(
+    rawPrice: Decimal,
+    decimalsA: number,
+    decimalsB: number
+  ): Decimal {
+    return PriceMath.invertPrice(rawPrice.mul(Decimal.pow(10, decimalsA - decimalsB)));
   }
 }
 
@@ -1,6 +1,7 @@
-import { ZERO, U64_MAX, Percentage } from "@orca-so/common-sdk";
+import { ZERO, U64_MAX, MathUtil, Percentage } from "@orca-so/common-sdk";
 import { PublicKey } from "@solana/web3.js";
 import BN from "bn.js";
+import Decimal from "decimal.js";
 import { AccountFetcher } from "../../network/public";
 import {
   MIN_SQRT_PRICE,
@@ -164,4 +165,49 @@ export class SwapUtils {
       };
     }
   }
+
+  /**
+   * Estimate the liquidity required around the current price so that a swap of the given input
+   * amount moves the price by at most the target price impact.
+   *
+   * Note: This is an approximation that assumes the liquidity is constant over the whole price
+   * range the swap moves through, as if it was provided by a single position covering that range.
+   * Liquidity changing at initialized ticks within the range is not accounted for.
+   *
+   * @param sqrtPrice - The current sqrt-price of the Whirlpool
+   * @param amountIn - The input amount of the swap, excluding fees
+   * @param aToB - The direction of the swap
+   * @param priceImpact - The maximum price impact of the swap
+   * @returns The liquidity required for the swap to have at most the target price impact
+   */
+  public static getLiquidityForPriceImpact(
+    sqrtPrice: BN,
+    amountIn: BN,
+    aToB: boolean,
+    priceImpact: Percentage
+  ): BN {
+    const impact = new Decimal(priceImpact.numerator.toString()).div(
+      priceImpact.denominator.toString()
+    );
+    if (impact.lte(0) || (aToB && impact.gte(1))) {
+      throw new Error(`Invalid price impact: ${impact.toString()}`);
+    }
+
+    const currSqrtPrice = MathUtil.fromX64(sqrtPrice);
+    const amount = new Decimal(amountIn.toString());
+    let liquidity: Decimal;
+    if (aToB) {
+      // amountA = L * (1 / sqrt(P') - 1 / sqrt(P)), where P' = P * (1 - impact)
+      const nextSqrtPrice = currSqrtPrice.mul(new Decimal(1).sub(impact).sqrt());
+      liquidity = amount.div(
+        new Decimal(1).div(nextSqrtPrice).sub(new Decimal(1).div(currSqrtPrice))
+      );
+    } else {
+      // amountB = L * (sqrt(P') - sqrt(P)), where P' = P * (1 + impact)
+      const nextSqrtPrice = currSqrtPrice.mul(new Decimal(1).add(impact).sqrt());
+      liquidity = amount.div(nextSqrtPrice.sub(currSqrtPrice));
+    }
+
+    return new BN(liquidity.ceil().toFixed());
+  }
 }
@@ -16,6 +16,16 @@ enum TickSearchDirection {
   Right,
 }
 
+/**
+ * The direction to round a tick index in when snapping it onto the tick spacing grid.
+ * @category Whirlpool Utils
+ */
+export enum TickRounding {
+  Down,
+  Up,
+  Nearest,
+}
+
 /**
  * A collection of utility functions when interacting with Ticks.
  * @category Whirlpool Utils
@@ -50,6 +60,46 @@ export class TickUtil {
     return tickIndex - (tickIndex % tickSpacing);
   }
 
+  /**
+   * Get the initializable tick index for the tickIndex, rounded in the given direction.
+   * Ticks exactly halfway between two initializable ticks round up with TickRounding.Nearest.
+   *
+   * @param tickIndex - The tick index to round
+   * @param tickSpacing - Tick spacing for the whirlpool
+   * @param round - The direction to round tickIndex in
+   * @returns the rounded initializable tick index
+   * @throws if the rounded tick index is out of the [MIN_TICK_INDEX, MAX_TICK_INDEX] bounds
+   */
+  public static getNearestInitializableTickIndex(
+    tickIndex: number,
+    tickSpacing: number,
+    round: TickRounding
+  ): number {
+    const lower = Math.floor(tickIndex / tickSpacing) * tickSpacing;
+    const upper = lower === tickIndex ? lower : lower + tickSpacing;
+
+    let rounded: number;
+    switch (round) {
+      case TickRounding.Down:
+        rounded = lower;
+        break;
+      case TickRounding.Up:
+        rounded = upper;
+        break;
+      case TickRounding.Nearest:
+        rounded = tickIndex - lower < upper - tickIndex ? lower : upper;
+        break;
+      default:
+        throw new Error(`type ${round} is an unknown TickRounding`);
+    }
+
+    invariant(
+      TickUtil.checkTickInBounds(rounded),
+      `tick index ${tickIndex} rounds to ${rounded}, which is out of bounds`
+    );
+    return rounded;
+  }
+
   public static getNextInitializableTickIndex(tickIndex: number, tickSpacing: number) {
     return TickUtil.getInitializableTickIndex(tickIndex, tickSpacing) + tickSpacing;
   }
@@ -135,6 +185,62 @@ export class TickUtil {
     return null;
   }
 
+  /**
+   * Get the min and max initializable tick indexes for a given tick spacing.
+   * A full-range position spans the range between these two ticks.
+   *
+   * @param tickSpacing - Tick spacing for the whirlpool
+   * @returns a tuple of the [min, max] initializable tick indexes
+   */
+  public static getFullRangeTickIndex(tickSpacing: number): [number, number] {
+    return [
+      TickUtil.getInitializableTickIndex(MIN_TICK_INDEX, tickSpacing),
+      TickUtil.getInitializableTickIndex(MAX_TICK_INDEX, tickSpacing),
+    ];
+  }
+
+  /**
+   * Get the widest tick range centered on the current tick whose lower and upper ticks are
+   * within a maximum number of tick-arrays. Keeping both ticks of a position within a few
+   * tick-arrays keeps the transactions modifying it small.
+   *
+   * The range is symmetric around the initializable tick at or below the current tick.
+   *
+   * @param tickCurrentIndex - The current tick index of the whirlpool
+   * @param tickSpacing - Tick spacing for the whirlpool
+   * @param maxTickArrays - The maximum number of tick-arrays spanned by the range
+   * @returns a tuple of the [lower, upper] tick indexes. null if no range fits in the tick-arrays.
+   */
+  public static getMaxRangeInTickArrays(
+    tickCurrentIndex: number,
+    tickSpacing: number,
+    maxTickArrays: number
+  ): [number, number] | null {
+    invariant(maxTickArrays >= 1, "maxTickArrays must be at least 1");
+
+    const [minTickIndex, maxTickIndex] = TickUtil.getFullRangeTickIndex(tickSpacing);
+    const centerTickIndex = Math.floor(tickCurrentIndex / tickSpacing) * tickSpacing;
+    const spannedTickArrays = (lower: number, upper: number) =>
+      (TickUtil.getStartTickIndex(upper, tickSpacing) -
+        TickUtil.getStartTickIndex(lower, tickSpacing)) /
+        (TICK_ARRAY_SIZE * tickSpacing) +
+      1;
+
+    let range: [number, number] | null = null;
+    for (let distance = tickSpacing; ; distance += tickSpacing) {
+      const lower = centerTickIndex - distance;
+      const upper = centerTickIndex + distance;
+      if (lower < minTickIndex || upper > maxTickIndex) {
+        break;
+      }
+      if (spannedTickArrays(lower, upper) > maxTickArrays) {
+        break;
+      }
+      range = [lower, upper];
+    }
+    return range;
+  }
+
   public static checkTickInBounds(tick: number) {
     return tick <= MAX_TICK_INDEX && tick >= MIN_TICK_INDEX;
   }
@@ -193,6 +299,21 @@ export class TickArrayUtil {
     });
   }
 
+  /**
+   * Get the start tick indexes of the tick-arrays a full-range position requires.
+   * A full-range position only touches the tick-arrays containing the min and max initializable ticks.
+   *
+   * @param tickSpacing - Tick spacing for the whirlpool
+   * @returns a tuple of the [lower, upper] tick-array start indexes
+   */
+  public static getFullRangeTickArrayStartIndexes(tickSpacing: number): [number, number] {
+    const [minTickIndex, maxTickIndex] = TickUtil.getFullRangeTickIndex(tickSpacing);
+    return [
+      TickUtil.getStartTickIndex(minTickIndex, tickSpacing),
+      TickUtil.getStartTickIndex(maxTickIndex, tickSpacing),
+    ];
+  }
+
   public static async getUninitializedArraysPDAs(
     ticks: number[],
     programId: PublicKey,
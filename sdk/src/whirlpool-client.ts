@@ -1,10 +1,12 @@
 import { Percentage, TransactionBuilder } from "@orca-so/common-sdk";
 import { Address } from "@project-serum/anchor";
+import { u64 } from "@solana/spl-token";
 import { PublicKey } from "@solana/web3.js";
+import Decimal from "decimal.js";
 import { WhirlpoolContext } from "./context";
 import { WhirlpoolClientImpl } from "./impl/whirlpool-client-impl";
 import { AccountFetcher } from "./network/public";
-import { SwapQuote } from "./quotes/public";
+import { DecreaseLiquidityFractionInput, IncreaseLiquidityQuote, SwapQuote } from "./quotes/public";
 import {
   DecreaseLiquidityInput,
   IncreaseLiquidityInput,
@@ -44,8 +46,92 @@ export interface WhirlpoolClient {
    * @return a Position object to interact with
    */
   getPosition: (positionAddress: Address, refresh?: boolean) => Promise<Position>;
+
+  /**
+   * Get all Position objects owned by a wallet, each joined with the Whirlpool it belongs to.
+   *
+   * Positions are discovered from the position tokens held by the wallet. The Position, Whirlpool
+   * and Mint accounts are fetched in batched rpc requests rather than one request per position.
+   *
+   * @param owner the wallet that holds the position tokens
+   * @param refresh force a refresh of the cached Position, Whirlpool and Mint accounts
+   * @return a list of Position objects with the Whirlpool objects (incl. token decimals) they belong to
+   */
+  getPositionsForOwner: (owner: Address, refresh?: boolean) => Promise<PositionWithPool[]>;
+
+  /**
+   * Create a Whirlpool on an existing WhirlpoolsConfig and FeeTier, along with the TickArray
+   * that houses the initial price so the pool can be traded against right away.
+   *
+   * The FeeTier is resolved from the config & tick-spacing and the initial sqrt-price is derived
+   * from the decimals of the two mints.
+   *
+   * If `funder` is provided, the funder wallet has to sign this transaction.
+   *
+   * @param whirlpoolsConfig - the address of the WhirlpoolsConfig account the pool is created under
+   * @param tokenMintA - the mint of token A. Must be ordered before tokenMintB, see {@link PoolUtil.orderMints}.
+   * @param tokenMintB - the mint of token B
+   * @param tickSpacing - the tick-spacing of the pool, a FeeTier for it must exist on the config
+   * @param initialPrice - the initial price (token B per token A, decimals adjusted) of the pool
+   * @param funder - the wallet that will fund the cost needed to initialize the pool & tick array. If null, the WhirlpoolContext wallet is used.
+   * @return `poolKey` - the address of the pool to be created. `tx` - The transaction containing the instructions to perform the operation on chain.
+   */
+  createPool: (
+    whirlpoolsConfig: Address,
+    tokenMintA: Address,
+    tokenMintB: Address,
+    tickSpacing: number,
+    initialPrice: Decimal,
+    funder?: Address
+  ) => Promise<{ poolKey: PublicKey; tx: TransactionBuilder }>;
+
+  /**
+   * Collect the fees and rewards of every position held by a wallet.
+   *
+   * For each position the fees and rewards are brought up to date with update_fees_and_rewards and
+   * then collected with collect_fees & collect_reward. Positions with zero liquidity are only collected
+   * from if they still owe fees or rewards. The instructions are packed into as few transactions as the
+   * transaction size limit allows, and missing associated token accounts of `owner` are created in the
+   * first transaction that collects into them, so the transactions have to be executed in order.
+   *
+   * If `owner` is provided, the owner wallet has to sign these transactions.
+   *
+   * @param owner - the wallet that holds the position tokens and receives the collected tokens. If null, the WhirlpoolContext wallet is used.
+   * @param refresh - force a refresh of the cached Position, Whirlpool and Mint accounts
+   * @param maxAccountsPerTransaction - optional limit on the unique accounts referenced by each transaction. A position whose instructions exceed the limit on their own is still harvested in a transaction of its own.
+   * @return the transactions to execute in order to harvest all positions. Empty if there is nothing to harvest.
+   */
+  harvestAllPositions: (
+    owner?: Address,
+    refresh?: boolean,
+    maxAccountsPerTransaction?: number
+  ) => Promise<TransactionBuilder[]>;
+
+  /**
+   * Estimate the number of transactions {@link WhirlpoolClient.harvestAllPositions} needs to harvest
+   * every position held by a wallet, using the same batching.
+   *
+   * @param owner - the wallet that holds the position tokens. If null, the WhirlpoolContext wallet is used.
+   * @param maxAccountsPerTransaction - optional limit on the unique accounts referenced by each transaction
+   * @param refresh - force a refresh of the cached Position, Whirlpool and Mint accounts
+   * @return the number of transactions required. 0 if there is nothing to harvest.
+   */
+  getHarvestTransactionCount: (
+    owner?: Address,
+    maxAccountsPerTransaction?: number,
+    refresh?: boolean
+  ) => Promise<number>;
 }
 
+/**
+ * A Position object joined with the Whirlpool object it belongs to.
+ * @category WhirlpoolClient
+ */
+export type PositionWithPool = {
+  position: Position;
+  pool: Whirlpool;
+};
+
 /**
  * Construct a WhirlpoolClient instance to help interact with Whirlpools accounts with.
  *
@@ -111,7 +197,8 @@ export interface Whirlpool {
   /**
    * Open and fund a position on this Whirlpool.
    *
-   * User has to ensure the TickArray for tickLower and tickUpper has been initialized prior to calling this function.
+   * If `initTickArrays` is true and the TickArray for tickLower or tickUpper is not initialized, the instructions to initialize it
+   * are added to the transaction. The transaction fails if another transaction initializes the TickArray before it lands.
    *
    * If `wallet` or `funder` is provided, those wallets have to sign this transaction.
    *
@@ -119,7 +206,8 @@ export interface Whirlpool {
    * @param tickUpper - the tick index for the upper bound of this position
    * @param liquidityInput - an InputLiquidityInput type to define the desired liquidity amount to deposit
    * @param wallet - the wallet to withdraw tokens to deposit into the position and house the position token. If null, the WhirlpoolContext wallet is used.
-   * @param funder - the wallet that will fund the cost needed to initialize the position. If null, the WhirlpoolContext wallet is used.
+   * @param funder - the wallet that will fund the cost needed to initialize the position & tick arrays. If null, the WhirlpoolContext wallet is used.
+   * @param initTickArrays - if true, initialize the uninitialized TickArrays for tickLower and tickUpper in the same transaction. (RPC call required) Defaults to false.
    * @return `positionMint` - the position to be created. `tx` - The transaction containing the instructions to perform the operation on chain.
    */
   openPosition: (
@@ -127,13 +215,15 @@ export interface Whirlpool {
     tickUpper: number,
     liquidityInput: IncreaseLiquidityInput,
     wallet?: Address,
-    funder?: Address
+    funder?: Address,
+    initTickArrays?: boolean
   ) => Promise<{ positionMint: PublicKey; tx: TransactionBuilder }>;
 
   /**
    * Open and fund a position with meta-data on this Whirlpool.
    *
-   * User has to ensure the TickArray for tickLower and tickUpper has been initialized prior to calling this function.
+   * If `initTickArrays` is true and the TickArray for tickLower or tickUpper is not initialized, the instructions to initialize it
+   * are added to the transaction. The transaction fails if another transaction initializes the TickArray before it lands.
    *
    * If `wallet` or `funder` is provided, the wallet owners have to sign this transaction.
    *
@@ -141,7 +231,8 @@ export interface Whirlpool {
    * @param tickUpper - the tick index for the upper bound of this position
    * @param liquidityInput - input that defines the desired liquidity amount and maximum tokens willing to be to deposited.
    * @param wallet - the wallet to withdraw tokens to deposit into the position and house the position token. If null, the WhirlpoolContext wallet is used.
-   * @param funder - the wallet that will fund the cost needed to initialize the position. If null, the WhirlpoolContext wallet is used.
+   * @param funder - the wallet that will fund the cost needed to initialize the position & tick arrays. If null, the WhirlpoolContext wallet is used.
+   * @param initTickArrays - if true, initialize the uninitialized TickArrays for tickLower and tickUpper in the same transaction. (RPC call required) Defaults to false.
    * @return `positionMint` - the position to be created. `tx` - The transaction containing the instructions to perform the operation on chain.
    */
   openPositionWithMetadata: (
@@ -149,13 +240,52 @@ export interface Whirlpool {
     tickUpper: number,
     liquidityInput: IncreaseLiquidityInput,
     wallet?: Address,
-    funder?: Address
+    funder?: Address,
+    initTickArrays?: boolean
   ) => Promise<{ positionMint: PublicKey; tx: TransactionBuilder }>;
 
+  /**
+   * Open and fund a position on this Whirlpool from a price range and an input token amount.
+   *
+   * The prices are converted to the nearest initializable ticks for this pool's tick-spacing and the
+   * liquidity to deposit is derived from the input token amount with {@link increaseLiquidityQuoteByInputToken}.
+   *
+   * If `initTickArrays` is true and the TickArray for the derived tickLower or tickUpper is not initialized, the instructions to
+   * initialize it are added to the transaction. The transaction fails if another transaction initializes the TickArray before it lands.
+   *
+   * If `wallet` or `funder` is provided, those wallets have to sign this transaction.
+   *
+   * @param priceLower - the lower bound price (token B per token A, decimals adjusted) of this position
+   * @param priceUpper - the upper bound price (token B per token A, decimals adjusted) of this position
+   * @param inputTokenMint - the mint of the token the input amount is denominated in
+   * @param inputTokenAmount - the amount of the input token to deposit, decimals adjusted
+   * @param slippageTolerance - the maximum slippage allowed when calculating the maximum tokens deposited
+   * @param wallet - the wallet to withdraw tokens to deposit into the position and house the position token. If null, the WhirlpoolContext wallet is used.
+   * @param funder - the wallet that will fund the cost needed to initialize the position & tick arrays. If null, the WhirlpoolContext wallet is used.
+   * @param initTickArrays - if true, initialize the uninitialized TickArrays for the derived tick range in the same transaction. (RPC call required) Defaults to false.
+   * @return `positionMint` - the position to be created. `tx` - The transaction containing the instructions to perform the operation on chain.
+   *         `quote` - the increase liquidity quote used to fund the position, including the derived tick range.
+   */
+  openPositionByPriceRange: (
+    priceLower: Decimal,
+    priceUpper: Decimal,
+    inputTokenMint: Address,
+    inputTokenAmount: Decimal,
+    slippageTolerance: Percentage,
+    wallet?: Address,
+    funder?: Address,
+    initTickArrays?: boolean
+  ) => Promise<{
+    positionMint: PublicKey;
+    tx: TransactionBuilder;
+    quote: IncreaseLiquidityQuote & { tickLowerIndex: number; tickUpperIndex: number };
+  }>;
+
   /**
    * Withdraw all tokens from a position, close the account and burn the position token.
    *
-   * Users have to collect all fees and rewards from this position prior to closing the account.
+   * All fees and rewards owed to this position are collected before closing the account,
+   * so a position with uncollected fees or rewards can be closed in a single transaction.
    *
    * If `positionWallet`, `payer` is provided, the wallet owner has to sign this transaction.
    *
@@ -181,8 +311,37 @@ export interface Whirlpool {
    * @return a transaction that will perform the swap once executed.
    */
   swap: (quote: SwapQuote, wallet?: PublicKey) => Promise<TransactionBuilder>;
+
+  /**
+   * Perform a swap between tokenA and tokenB on this pool and return the transaction together with
+   * the quote and pool state it was built against, so the executed result can be verified afterwards.
+   * The pool is re-fetched to take the snapshot, which also refreshes the data of this object.
+   *
+   * @param quote - A quote on the desired tokenIn and tokenOut for this swap. Use @link {swapQuote} to generate this object.
+   * @param wallet - The wallet that tokens will be withdrawn and deposit into. If null, the WhirlpoolContext wallet is used.
+   * @return a SwapBuildResult containing the swap transaction and the state it was built on.
+   */
+  buildSwap: (quote: SwapQuote, wallet?: PublicKey) => Promise<SwapBuildResult>;
 }
 
+/**
+ * The result of building a swap transaction with {@link Whirlpool.buildSwap}.
+ *
+ * @category WhirlpoolClient
+ * @param tx - The transaction that will perform the swap once executed.
+ * @param quote - The quote the swap instruction was built from.
+ * @param tickArraysUsed - The distinct tick-array addresses passed to the swap instruction, in traversal order.
+ * @param poolSnapshot - The Whirlpool account data fetched when the swap was built.
+ * @param poolSnapshotSlot - The slot at which poolSnapshot was fetched.
+ */
+export type SwapBuildResult = {
+  tx: TransactionBuilder;
+  quote: SwapQuote;
+  tickArraysUsed: PublicKey[];
+  poolSnapshot: WhirlpoolData;
+  poolSnapshotSlot: number;
+};
+
 /**
  * Helper class to interact with a Position account and build complex transactions.
  * @category WhirlpoolClient
@@ -226,12 +385,38 @@ export interface Position {
     ataPayer?: Address
   ) => Promise<TransactionBuilder>;
 
+  /**
+   * Deposit as much liquidity as the given token amounts allow into this position.
+   * The liquidity is quoted with {@link increaseLiquidityQuoteByTokenAmountsWithParams} against the freshly fetched Whirlpool.
+   * If  `positionWallet` and `wallet` is provided, the wallet owners have to sign this transaction.
+   *
+   * @param tokenAmountA - the maximum amount of tokenA to deposit, before slippage is applied.
+   * @param tokenAmountB - the maximum amount of tokenB to deposit, before slippage is applied.
+   * @param slippageTolerance - the maximum slippage allowed when calculating the maximum tokens deposited
+   * @param resolveATA - if true, add instructions to create associated token accounts for tokenA,B for the destinationWallet if necessary. (RPC call required)
+   * @param wallet - to withdraw tokens to deposit into the position. If null, the WhirlpoolContext wallet is used.
+   * @param positionWallet - the wallet to that houses the position token. If null, the WhirlpoolContext wallet is used.
+   * @param ataPayer - wallet that will fund the creation of the new associated token accounts
+   * @return `tx` - the transaction that will deposit the tokens into the position when executed.
+   *         `quote` - the increase liquidity quote used, including the estimated token amounts deposited.
+   */
+  increaseLiquidityByTokenAmounts: (
+    tokenAmountA: u64,
+    tokenAmountB: u64,
+    slippageTolerance: Percentage,
+    resolveATA?: boolean,
+    wallet?: Address,
+    positionWallet?: Address,
+    ataPayer?: Address
+  ) => Promise<{ tx: TransactionBuilder; quote: IncreaseLiquidityQuote }>;
+
   /**
    * Withdraw liquidity from this position.
    *
    * If `positionWallet` is provided, the wallet owners have to sign this transaction.
    *
-   * @param liquidityInput - input that defines the desired liquidity amount and minimum tokens willing to be to withdrawn from the position.
+   * @param liquidityInput - input that defines the desired liquidity amount and minimum tokens willing to be to withdrawn from the position,
+   *                         or the fraction of the position's liquidity to withdraw and the slippage tolerance to derive them at the current price.
    * @param resolveATA -  if true, add instructions to create associated token accounts for tokenA,B for the destinationWallet if necessary. (RPC call required)
    * @param destinationWallet - the wallet to deposit tokens into when withdrawing from the position. If null, the WhirlpoolContext wallet is used.
    * @param positionWallet - the wallet to that houses the position token. If null, the WhirlpoolContext wallet is used.
@@ -239,7 +424,7 @@ export interface Position {
    * @return the transaction that will deposit the tokens into the position when executed.
    */
   decreaseLiquidity: (
-    liquidityInput: DecreaseLiquidityInput,
+    liquidityInput: DecreaseLiquidityInput | DecreaseLiquidityFractionInput,
     resolveATA?: boolean,
     destinationWallet?: Address,
     positionWallet?: Address,
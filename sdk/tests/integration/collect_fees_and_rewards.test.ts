@@ -0,0 +1,143 @@
+import { MathUtil } from "@orca-so/common-sdk";
+import * as anchor from "@project-serum/anchor";
+import { u64 } from "@solana/spl-token";
+import * as assert from "assert";
+import Decimal from "decimal.js";
+import { NUM_REWARDS, PositionData, toTx, WhirlpoolContext, WhirlpoolIx } from "../../src";
+import { createTokenAccount, getTokenBalance, sleep, TickSpacing, ZERO_BN } from "../utils";
+import { WhirlpoolTestFixture } from "../utils/fixture";
+
+describe("collect_fees_and_rewards", () => {
+  const provider = anchor.AnchorProvider.local();
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.Whirlpool;
+  const ctx = WhirlpoolContext.fromWorkspace(provider, program);
+  const fetcher = ctx.fetcher;
+
+  async function initFixture() {
+    const vaultAmount = new u64(1_000_000);
+    const fixture = await new WhirlpoolTestFixture(ctx).init({
+      tickSpacing: TickSpacing.Standard,
+      initialSqrtPrice: MathUtil.toX64(new Decimal(1)),
+      positions: [
+        { tickLowerIndex: -1280, tickUpperIndex: 1280, liquidityAmount: new anchor.BN(1_000_000) },
+      ],
+      rewards: [
+        { emissionsPerSecondX64: MathUtil.toX64(new Decimal(10)), vaultAmount },
+        { emissionsPerSecondX64: MathUtil.toX64(new Decimal(10)), vaultAmount },
+        { emissionsPerSecondX64: MathUtil.toX64(new Decimal(10)), vaultAmount },
+      ],
+    });
+    const {
+      poolInitInfo: {
+        whirlpoolPda,
+        tokenVaultAKeypair,
+        tokenVaultBKeypair,
+        tokenMintA,
+        tokenMintB,
+      },
+      positions,
+      rewards,
+    } = fixture.getInfos();
+
+    const feeAccountA = await createTokenAccount(provider, tokenMintA, provider.wallet.publicKey);
+    const feeAccountB = await createTokenAccount(provider, tokenMintB, provider.wallet.publicKey);
+    const rewardAccounts = [];
+    for (const reward of rewards) {
+      rewardAccounts.push({
+        rewardOwnerAccount: await createTokenAccount(
+          provider,
+          reward.rewardMint,
+          provider.wallet.publicKey
+        ),
+        rewardVault: reward.rewardVaultKeypair.publicKey,
+      });
+    }
+
+    const params = {
+      whirlpool: whirlpoolPda.publicKey,
+      positionAuthority: provider.wallet.publicKey,
+      position: positions[0].publicKey,
+      positionTokenAccount: positions[0].tokenAccount,
+      tickArrayLower: positions[0].tickArrayLower,
+      tickArrayUpper: positions[0].tickArrayUpper,
+      tokenOwnerAccountA: feeAccountA,
+      tokenOwnerAccountB: feeAccountB,
+      tokenVaultA: tokenVaultAKeypair.publicKey,
+      tokenVaultB: tokenVaultBKeypair.publicKey,
+      rewardAccounts,
+    };
+    return { params, positions, vaultAmount };
+  }
+
+  it("successfully updates and collects fees and all rewards", async () => {
+    const { params, positions, vaultAmount } = await initFixture();
+    await sleep(500);
+
+    await toTx(ctx, WhirlpoolIx.collectFeesAndRewardsIx(ctx.program, params)).buildAndExecute();
+
+    const position = (await fetcher.getPosition(positions[0].publicKey, true)) as PositionData;
+    assert.ok(position.feeOwedA.eq(ZERO_BN));
+    assert.ok(position.feeOwedB.eq(ZERO_BN));
+    for (let i = 0; i < NUM_REWARDS; i++) {
+      const { rewardOwnerAccount, rewardVault } = params.rewardAccounts[i];
+      // The rewards were brought up to date within the instruction before being collected
+      assert.ok(position.rewardInfos[i].growthInsideCheckpoint.gt(ZERO_BN));
+      assert.ok(position.rewardInfos[i].amountOwed.eq(ZERO_BN));
+
+      const collectedBalance = new u64(await getTokenBalance(provider, rewardOwnerAccount));
+      assert.ok(collectedBalance.gt(ZERO_BN));
+      assert.equal(
+        await getTokenBalance(provider, rewardVault),
+        vaultAmount.sub(collectedBalance).toString()
+      );
+    }
+  });
+
+  it("fails when a reward account is missing", async () => {
+    const { params } = await initFixture();
+
+    await assert.rejects(
+      toTx(
+        ctx,
+        WhirlpoolIx.collectFeesAndRewardsIx(ctx.program, {
+          ...params,
+          rewardAccounts: params.rewardAccounts.slice(0, 2),
+        })
+      ).buildAndExecute(),
+      /0x179a/ // InvalidRewardAccounts
+    );
+  });
+
+  it("fails when the reward accounts are not in reward index order", async () => {
+    const { params } = await initFixture();
+    const [reward0, reward1, reward2] = params.rewardAccounts;
+
+    await assert.rejects(
+      toTx(
+        ctx,
+        WhirlpoolIx.collectFeesAndRewardsIx(ctx.program, {
+          ...params,
+          rewardAccounts: [reward1, reward0, reward2],
+        })
+      ).buildAndExecute(),
+      /0x179a/ // InvalidRewardAccounts
+    );
+  });
+
+  it("fails when the tick arrays do not belong to the whirlpool", async () => {
+    const { params } = await initFixture();
+    const { params: otherParams } = await initFixture();
+
+    await assert.rejects(
+      toTx(
+        ctx,
+        WhirlpoolIx.collectFeesAndRewardsIx(ctx.program, {
+          ...params,
+          tickArrayLower: otherParams.tickArrayLower,
+        })
+      ).buildAndExecute(),
+      /0x7d1/ // ConstraintHasOne
+    );
+  });
+});
@@ -4,6 +4,7 @@ import { u64 } from "@solana/spl-token";
 import * as assert from "assert";
 import Decimal from "decimal.js";
 import {
+  collectAllRewardsQuote,
   collectRewardsQuote,
   NUM_REWARDS,
   PositionData,
@@ -139,6 +140,94 @@ describe("collect_reward", () => {
     }
   });
 
+  it("successfully quote all initialized rewards with their mints", async () => {
+    const lowerTickIndex = -1280,
+      upperTickIndex = 1280,
+      tickSpacing = TickSpacing.Standard;
+    const fixture = await new WhirlpoolTestFixture(ctx).init({
+      tickSpacing: tickSpacing,
+      initialSqrtPrice: MathUtil.toX64(new Decimal(1)),
+      positions: [
+        {
+          tickLowerIndex: lowerTickIndex,
+          tickUpperIndex: upperTickIndex,
+          liquidityAmount: new anchor.BN(1_000_000),
+        },
+      ],
+      rewards: [
+        {
+          emissionsPerSecondX64: MathUtil.toX64(new Decimal(10)),
+          vaultAmount: new u64(1_000_000),
+        },
+        {
+          emissionsPerSecondX64: MathUtil.toX64(new Decimal(20)),
+          vaultAmount: new u64(1_000_000),
+        },
+      ],
+    });
+    const {
+      poolInitInfo: { whirlpoolPda },
+      positions,
+      rewards,
+    } = fixture.getInfos();
+    await sleep(1200);
+    await toTx(
+      ctx,
+      WhirlpoolIx.updateFeesAndRewardsIx(ctx.program, {
+        whirlpool: whirlpoolPda.publicKey,
+        position: positions[0].publicKey,
+        tickArrayLower: positions[0].tickArrayLower,
+        tickArrayUpper: positions[0].tickArrayUpper,
+      })
+    ).buildAndExecute();
+
+    const pool = (await fetcher.getPool(whirlpoolPda.publicKey, true)) as WhirlpoolData;
+    const position = (await fetcher.getPosition(positions[0].publicKey, true)) as PositionData;
+    const tickArrayLower = (await fetcher.getTickArray(
+      positions[0].tickArrayLower,
+      true
+    )) as TickArrayData;
+    const tickArrayUpper = (await fetcher.getTickArray(
+      positions[0].tickArrayUpper,
+      true
+    )) as TickArrayData;
+    const expectation = collectRewardsQuote({
+      whirlpool: pool,
+      position,
+      tickLower: TickArrayUtil.getTickFromArray(tickArrayLower, lowerTickIndex, tickSpacing),
+      tickUpper: TickArrayUtil.getTickFromArray(tickArrayUpper, upperTickIndex, tickSpacing),
+    });
+
+    // Quoting at the last updated timestamp matches the on-chain reward growths
+    const quote = await collectAllRewardsQuote(
+      positions[0].publicKey,
+      ctx.program.programId,
+      fetcher,
+      true,
+      pool.rewardLastUpdatedTimestamp
+    );
+    assert.equal(quote.length, 2);
+    for (let i = 0; i < quote.length; i++) {
+      const mintInfo = await fetcher.getMintInfo(rewards[i].rewardMint, true);
+      assert.equal(quote[i].index, i);
+      assert.ok(quote[i].mint.equals(rewards[i].rewardMint));
+      assert.equal(quote[i].decimals, mintInfo?.decimals);
+      assert.ok(quote[i].amount.gtn(0));
+      assert.ok(quote[i].amount.eq(expectation[i]!));
+    }
+
+    // Quoting at a later timestamp accrues rewards emitted since the last update
+    const futureQuote = await collectAllRewardsQuote(
+      positions[0].publicKey,
+      ctx.program.programId,
+      fetcher,
+      false,
+      pool.rewardLastUpdatedTimestamp.addn(60)
+    );
+    assert.ok(futureQuote[0].amount.gt(quote[0].amount));
+    assert.ok(futureQuote[1].amount.gt(quote[1].amount));
+  });
+
   it("successfully collect reward with a position authority delegate", async () => {
     const vaultStartBalance = 1_000_000;
     const fixture = await new WhirlpoolTestFixture(ctx).init({
@@ -3,10 +3,12 @@ import * as anchor from "@project-serum/anchor";
 import * as assert from "assert";
 import Decimal from "decimal.js";
 import {
+  FeeTierData,
   InitPoolParams,
   MAX_SQRT_PRICE,
   MIN_SQRT_PRICE,
   PDAUtil,
+  PoolUtil,
   PriceMath,
   toTx,
   WhirlpoolContext,
@@ -21,7 +23,8 @@ import {
   TickSpacing,
   ZERO_BN,
 } from "../utils";
-import { buildTestPoolParams, initTestPool } from "../utils/init-utils";
+import { buildTestPoolParams, initFeeTier, initTestPool } from "../utils/init-utils";
+import { generateDefaultInitPoolParams } from "../utils/test-builders";
 
 describe("initialize_pool", () => {
   const provider = anchor.AnchorProvider.local();
@@ -60,6 +63,15 @@ describe("initialize_pool", () => {
     assert.equal(whirlpool.feeRate, feeTierParams.defaultFeeRate);
     assert.equal(whirlpool.protocolFeeRate, configInitInfo.defaultProtocolFeeRate);
 
+    const previewedFeeRate = await PoolUtil.getInitialFeeRate(
+      fetcher,
+      program.programId,
+      poolInitInfo.whirlpoolsConfig,
+      TickSpacing.Standard
+    );
+    assert.ok(previewedFeeRate?.numerator.eqn(whirlpool.feeRate));
+    assert.ok(previewedFeeRate?.denominator.eqn(1_000_000));
+
     assert.ok(whirlpool.sqrtPrice.eq(new anchor.BN(poolInitInfo.initSqrtPrice.toString())));
     assert.ok(whirlpool.liquidity.eq(ZERO_BN));
 
@@ -145,6 +157,70 @@ describe("initialize_pool", () => {
     });
   });
 
+  it("successfully find all pools initialized with a fee tier", async () => {
+    const { configInitInfo, configKeypairs, poolInitInfo, feeTierParams } =
+      await buildTestPoolParams(ctx, TickSpacing.Standard);
+    const { params: stableFeeTierParams } = await initFeeTier(
+      ctx,
+      configInitInfo,
+      configKeypairs.feeAuthorityKeypair,
+      TickSpacing.Stable,
+      500
+    );
+    const whirlpoolsConfig = configInitInfo.whirlpoolsConfigKeypair.publicKey;
+
+    // Two Standard pools and one Stable pool in the same config
+    const standardPoolInitInfo = await generateDefaultInitPoolParams(
+      ctx,
+      whirlpoolsConfig,
+      feeTierParams.feeTierPda.publicKey,
+      TickSpacing.Standard
+    );
+    const stablePoolInitInfo = await generateDefaultInitPoolParams(
+      ctx,
+      whirlpoolsConfig,
+      stableFeeTierParams.feeTierPda.publicKey,
+      TickSpacing.Stable
+    );
+    for (const initInfo of [poolInitInfo, standardPoolInitInfo, stablePoolInitInfo]) {
+      await toTx(ctx, WhirlpoolIx.initializePoolIx(ctx.program, initInfo)).buildAndExecute();
+    }
+
+    // A Standard pool in another config is not returned
+    await initTestPool(ctx, TickSpacing.Standard);
+
+    const standardFeeTier = (await fetcher.getFeeTier(
+      feeTierParams.feeTierPda.publicKey
+    )) as FeeTierData;
+    const standardPools = await PoolUtil.getPoolsForFeeTier(
+      ctx.connection,
+      program.programId,
+      standardFeeTier
+    );
+    assert.deepEqual(
+      standardPools.map(({ address }) => address.toBase58()).sort(),
+      [poolInitInfo.whirlpoolPda.publicKey, standardPoolInitInfo.whirlpoolPda.publicKey]
+        .map((address) => address.toBase58())
+        .sort()
+    );
+    standardPools.forEach(({ data }) => {
+      assert.equal(data.tickSpacing, TickSpacing.Standard);
+      assert.equal(data.feeRate, standardFeeTier.defaultFeeRate);
+    });
+
+    const stableFeeTier = (await fetcher.getFeeTier(
+      stableFeeTierParams.feeTierPda.publicKey
+    )) as FeeTierData;
+    const stablePools = await PoolUtil.getPoolsForFeeTier(
+      ctx.connection,
+      program.programId,
+      stableFeeTier
+    );
+    assert.equal(stablePools.length, 1);
+    assert.ok(stablePools[0].address.equals(stablePoolInitInfo.whirlpoolPda.publicKey));
+    assert.equal(stablePools[0].data.feeRate, 500);
+  });
+
   it("succeeds when funder is different than account paying for transaction fee", async () => {
     const funderKeypair = anchor.web3.Keypair.generate();
     await systemTransferTx(provider, funderKeypair.publicKey, ONE_SOL).buildAndExecute();
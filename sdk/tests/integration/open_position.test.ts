@@ -10,6 +10,7 @@ import {
   MIN_TICK_INDEX,
   OpenPositionParams,
   PDAUtil,
+  PoolUtil,
   PositionData,
   toTx,
   WhirlpoolContext,
@@ -93,6 +94,44 @@ describe("open_position", () => {
     );
   });
 
+  it("finds the positions of a pool overlapping a tick range", async () => {
+    const { poolInitInfo: rangePoolInitInfo } = await initTestPool(ctx, TickSpacing.Standard);
+    const whirlpool = rangePoolInitInfo.whirlpoolPda.publicKey;
+    const [inside, upper] = await Promise.all([
+      openPosition(ctx, whirlpool, 0, 128),
+      openPosition(ctx, whirlpool, 128, 256),
+      openPosition(ctx, whirlpool, -256, -128),
+      // A position in another pool is not returned
+      openPosition(ctx, whirlpoolPda.publicKey, 0, 128),
+    ]);
+
+    const positions = await PoolUtil.getPositionsInTickRange(
+      ctx.connection,
+      program.programId,
+      whirlpool,
+      64,
+      192
+    );
+    assert.deepEqual(
+      positions.map(({ address }) => address.toBase58()).sort(),
+      [inside.params.positionPda.publicKey, upper.params.positionPda.publicKey]
+        .map((address) => address.toBase58())
+        .sort()
+    );
+    positions.forEach(({ data }) => assert.ok(data.whirlpool.equals(whirlpool)));
+
+    // The upper tick index of the window is exclusive
+    const lowerPositions = await PoolUtil.getPositionsInTickRange(
+      ctx.connection,
+      program.programId,
+      whirlpool,
+      -512,
+      0
+    );
+    assert.equal(lowerPositions.length, 1);
+    assert.equal(lowerPositions[0].data.tickLowerIndex, -256);
+  });
+
   it("open position & verify position mint behavior", async () => {
     const newOwner = web3.Keypair.generate();
 
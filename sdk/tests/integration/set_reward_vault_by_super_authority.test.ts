@@ -0,0 +1,299 @@
+import * as anchor from "@project-serum/anchor";
+import * as assert from "assert";
+import { toTx, WhirlpoolContext, WhirlpoolData, WhirlpoolIx } from "../../src";
+import {
+  createMint,
+  createTokenAccount,
+  getTokenBalance,
+  mintToByAuthority,
+  TickSpacing,
+} from "../utils";
+import { initializeReward, initTestPool } from "../utils/init-utils";
+
+describe("set_reward_vault_by_super_authority", () => {
+  const provider = anchor.AnchorProvider.local();
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.Whirlpool;
+  const ctx = WhirlpoolContext.fromWorkspace(provider, program);
+  const fetcher = ctx.fetcher;
+
+  async function initPoolWithReward() {
+    const { configKeypairs, poolInitInfo, configInitInfo } = await initTestPool(
+      ctx,
+      TickSpacing.Standard
+    );
+    const {
+      params: { rewardMint, rewardVaultKeypair },
+    } = await initializeReward(
+      ctx,
+      configKeypairs.rewardEmissionsSuperAuthorityKeypair,
+      poolInitInfo.whirlpoolPda.publicKey,
+      0
+    );
+    return { configKeypairs, poolInitInfo, configInitInfo, rewardMint, rewardVaultKeypair };
+  }
+
+  it("successfully set_reward_vault_by_super_authority", async () => {
+    const { configKeypairs, poolInitInfo, configInitInfo, rewardMint, rewardVaultKeypair } =
+      await initPoolWithReward();
+    const whirlpoolKey = poolInitInfo.whirlpoolPda.publicKey;
+    const newRewardVault = await createTokenAccount(provider, rewardMint, whirlpoolKey);
+
+    await toTx(
+      ctx,
+      WhirlpoolIx.setRewardVaultBySuperAuthorityIx(ctx.program, {
+        whirlpoolsConfig: configInitInfo.whirlpoolsConfigKeypair.publicKey,
+        whirlpool: whirlpoolKey,
+        rewardEmissionsSuperAuthority:
+          configKeypairs.rewardEmissionsSuperAuthorityKeypair.publicKey,
+        oldRewardVault: rewardVaultKeypair.publicKey,
+        newRewardVault,
+        rewardIndex: 0,
+      })
+    )
+      .addSigner(configKeypairs.rewardEmissionsSuperAuthorityKeypair)
+      .buildAndExecute();
+
+    const pool = (await fetcher.getPool(whirlpoolKey, true)) as WhirlpoolData;
+    assert.ok(!pool.rewardInfos[0].vault.equals(rewardVaultKeypair.publicKey));
+    assert.ok(pool.rewardInfos[0].vault.equals(newRewardVault));
+    assert.ok(pool.rewardInfos[0].mint.equals(rewardMint));
+  });
+
+  it("moves the balance of the old reward vault to the new reward vault", async () => {
+    const { configKeypairs, poolInitInfo, configInitInfo, rewardMint, rewardVaultKeypair } =
+      await initPoolWithReward();
+    const whirlpoolKey = poolInitInfo.whirlpoolPda.publicKey;
+    await mintToByAuthority(provider, rewardMint, rewardVaultKeypair.publicKey, 1_000_000);
+    const newRewardVault = await createTokenAccount(provider, rewardMint, whirlpoolKey);
+
+    await toTx(
+      ctx,
+      WhirlpoolIx.setRewardVaultBySuperAuthorityIx(ctx.program, {
+        whirlpoolsConfig: configInitInfo.whirlpoolsConfigKeypair.publicKey,
+        whirlpool: whirlpoolKey,
+        rewardEmissionsSuperAuthority:
+          configKeypairs.rewardEmissionsSuperAuthorityKeypair.publicKey,
+        oldRewardVault: rewardVaultKeypair.publicKey,
+        newRewardVault,
+        rewardIndex: 0,
+      })
+    )
+      .addSigner(configKeypairs.rewardEmissionsSuperAuthorityKeypair)
+      .buildAndExecute();
+
+    assert.equal(await getTokenBalance(provider, rewardVaultKeypair.publicKey), "0");
+    assert.equal(await getTokenBalance(provider, newRewardVault), "1000000");
+    const pool = (await fetcher.getPool(whirlpoolKey, true)) as WhirlpoolData;
+    assert.ok(pool.rewardInfos[0].vault.equals(newRewardVault));
+  });
+
+  it("fails if the old reward vault is not the current reward vault", async () => {
+    const { configKeypairs, poolInitInfo, configInitInfo, rewardMint } = await initPoolWithReward();
+    const whirlpoolKey = poolInitInfo.whirlpoolPda.publicKey;
+    const otherVault = await createTokenAccount(provider, rewardMint, whirlpoolKey);
+    const newRewardVault = await createTokenAccount(provider, rewardMint, whirlpoolKey);
+
+    await assert.rejects(
+      toTx(
+        ctx,
+        WhirlpoolIx.setRewardVaultBySuperAuthorityIx(ctx.program, {
+          whirlpoolsConfig: configInitInfo.whirlpoolsConfigKeypair.publicKey,
+          whirlpool: whirlpoolKey,
+          rewardEmissionsSuperAuthority:
+            configKeypairs.rewardEmissionsSuperAuthorityKeypair.publicKey,
+          oldRewardVault: otherVault,
+          newRewardVault,
+          rewardIndex: 0,
+        })
+      )
+        .addSigner(configKeypairs.rewardEmissionsSuperAuthorityKeypair)
+        .buildAndExecute(),
+      /0x7dc/ // An address constraint was violated
+    );
+  });
+
+  it("fails if the new reward vault holds a different mint", async () => {
+    const { configKeypairs, poolInitInfo, configInitInfo, rewardVaultKeypair } =
+      await initPoolWithReward();
+    const whirlpoolKey = poolInitInfo.whirlpoolPda.publicKey;
+    const otherMint = await createMint(provider);
+    const newRewardVault = await createTokenAccount(provider, otherMint, whirlpoolKey);
+
+    await assert.rejects(
+      toTx(
+        ctx,
+        WhirlpoolIx.setRewardVaultBySuperAuthorityIx(ctx.program, {
+          whirlpoolsConfig: configInitInfo.whirlpoolsConfigKeypair.publicKey,
+          whirlpool: whirlpoolKey,
+          rewardEmissionsSuperAuthority:
+            configKeypairs.rewardEmissionsSuperAuthorityKeypair.publicKey,
+          oldRewardVault: rewardVaultKeypair.publicKey,
+          newRewardVault,
+          rewardIndex: 0,
+        })
+      )
+        .addSigner(configKeypairs.rewardEmissionsSuperAuthorityKeypair)
+        .buildAndExecute(),
+      /0x1799/ // RewardVaultMintMismatch
+    );
+  });
+
+  it("fails if the new reward vault is a token vault of the whirlpool", async () => {
+    const { configKeypairs, poolInitInfo, configInitInfo } = await initTestPool(
+      ctx,
+      TickSpacing.Standard
+    );
+    const whirlpoolKey = poolInitInfo.whirlpoolPda.publicKey;
+    const rewardVaultKeypair = anchor.web3.Keypair.generate();
+    // The reward pays out token A, so token vault A holds the reward mint
+    await toTx(
+      ctx,
+      WhirlpoolIx.initializeRewardIx(ctx.program, {
+        rewardAuthority: configKeypairs.rewardEmissionsSuperAuthorityKeypair.publicKey,
+        funder: ctx.wallet.publicKey,
+        whirlpool: whirlpoolKey,
+        rewardMint: poolInitInfo.tokenMintA,
+        rewardVaultKeypair,
+        rewardIndex: 0,
+      })
+    )
+      .addSigner(configKeypairs.rewardEmissionsSuperAuthorityKeypair)
+      .buildAndExecute();
+
+    await assert.rejects(
+      toTx(
+        ctx,
+        WhirlpoolIx.setRewardVaultBySuperAuthorityIx(ctx.program, {
+          whirlpoolsConfig: configInitInfo.whirlpoolsConfigKeypair.publicKey,
+          whirlpool: whirlpoolKey,
+          rewardEmissionsSuperAuthority:
+            configKeypairs.rewardEmissionsSuperAuthorityKeypair.publicKey,
+          oldRewardVault: rewardVaultKeypair.publicKey,
+          newRewardVault: poolInitInfo.tokenVaultAKeypair.publicKey,
+          rewardIndex: 0,
+        })
+      )
+        .addSigner(configKeypairs.rewardEmissionsSuperAuthorityKeypair)
+        .buildAndExecute(),
+      /0x179c/ // RewardVaultInUse
+    );
+  });
+
+  it("fails if the new reward vault is the vault of another reward", async () => {
+    const { configKeypairs, poolInitInfo, configInitInfo, rewardMint, rewardVaultKeypair } =
+      await initPoolWithReward();
+    const whirlpoolKey = poolInitInfo.whirlpoolPda.publicKey;
+    const otherRewardVaultKeypair = anchor.web3.Keypair.generate();
+    await toTx(
+      ctx,
+      WhirlpoolIx.initializeRewardIx(ctx.program, {
+        rewardAuthority: configKeypairs.rewardEmissionsSuperAuthorityKeypair.publicKey,
+        funder: ctx.wallet.publicKey,
+        whirlpool: whirlpoolKey,
+        rewardMint,
+        rewardVaultKeypair: otherRewardVaultKeypair,
+        rewardIndex: 1,
+      })
+    )
+      .addSigner(configKeypairs.rewardEmissionsSuperAuthorityKeypair)
+      .buildAndExecute();
+
+    await assert.rejects(
+      toTx(
+        ctx,
+        WhirlpoolIx.setRewardVaultBySuperAuthorityIx(ctx.program, {
+          whirlpoolsConfig: configInitInfo.whirlpoolsConfigKeypair.publicKey,
+          whirlpool: whirlpoolKey,
+          rewardEmissionsSuperAuthority:
+            configKeypairs.rewardEmissionsSuperAuthorityKeypair.publicKey,
+          oldRewardVault: otherRewardVaultKeypair.publicKey,
+          newRewardVault: rewardVaultKeypair.publicKey,
+          rewardIndex: 1,
+        })
+      )
+        .addSigner(configKeypairs.rewardEmissionsSuperAuthorityKeypair)
+        .buildAndExecute(),
+      /0x179c/ // RewardVaultInUse
+    );
+  });
+
+  it("fails if the new reward vault is not owned by the whirlpool", async () => {
+    const { configKeypairs, poolInitInfo, configInitInfo, rewardMint, rewardVaultKeypair } =
+      await initPoolWithReward();
+    const newRewardVault = await createTokenAccount(
+      provider,
+      rewardMint,
+      provider.wallet.publicKey
+    );
+
+    await assert.rejects(
+      toTx(
+        ctx,
+        WhirlpoolIx.setRewardVaultBySuperAuthorityIx(ctx.program, {
+          whirlpoolsConfig: configInitInfo.whirlpoolsConfigKeypair.publicKey,
+          whirlpool: poolInitInfo.whirlpoolPda.publicKey,
+          rewardEmissionsSuperAuthority:
+            configKeypairs.rewardEmissionsSuperAuthorityKeypair.publicKey,
+          oldRewardVault: rewardVaultKeypair.publicKey,
+          newRewardVault,
+          rewardIndex: 0,
+        })
+      )
+        .addSigner(configKeypairs.rewardEmissionsSuperAuthorityKeypair)
+        .buildAndExecute(),
+      /0x7d3/ // ConstraintRaw
+    );
+  });
+
+  it("fails if invalid super authority provided", async () => {
+    const { poolInitInfo, configInitInfo, rewardMint, rewardVaultKeypair } =
+      await initPoolWithReward();
+    const whirlpoolKey = poolInitInfo.whirlpoolPda.publicKey;
+    const newRewardVault = await createTokenAccount(provider, rewardMint, whirlpoolKey);
+    const invalidSuperAuthorityKeypair = anchor.web3.Keypair.generate();
+
+    await assert.rejects(
+      toTx(
+        ctx,
+        WhirlpoolIx.setRewardVaultBySuperAuthorityIx(ctx.program, {
+          whirlpoolsConfig: configInitInfo.whirlpoolsConfigKeypair.publicKey,
+          whirlpool: whirlpoolKey,
+          rewardEmissionsSuperAuthority: invalidSuperAuthorityKeypair.publicKey,
+          oldRewardVault: rewardVaultKeypair.publicKey,
+          newRewardVault,
+          rewardIndex: 0,
+        })
+      )
+        .addSigner(invalidSuperAuthorityKeypair)
+        .buildAndExecute(),
+      /0x7dc/ // An address constraint was violated
+    );
+  });
+
+  it("fails on an uninitialized reward index", async () => {
+    const { configKeypairs, poolInitInfo, configInitInfo, rewardMint, rewardVaultKeypair } =
+      await initPoolWithReward();
+    const whirlpoolKey = poolInitInfo.whirlpoolPda.publicKey;
+    const newRewardVault = await createTokenAccount(provider, rewardMint, whirlpoolKey);
+
+    await assert.rejects(
+      toTx(
+        ctx,
+        WhirlpoolIx.setRewardVaultBySuperAuthorityIx(ctx.program, {
+          whirlpoolsConfig: configInitInfo.whirlpoolsConfigKeypair.publicKey,
+          whirlpool: whirlpoolKey,
+          rewardEmissionsSuperAuthority:
+            configKeypairs.rewardEmissionsSuperAuthorityKeypair.publicKey,
+          oldRewardVault: rewardVaultKeypair.publicKey,
+          newRewardVault,
+          rewardIndex: 1,
+        })
+      )
+        .addSigner(configKeypairs.rewardEmissionsSuperAuthorityKeypair)
+        .buildAndExecute(),
+      // The uninitialized reward has no vault, so no token account matches the old reward vault
+      /0x7dc/ // An address constraint was violated
+    );
+  });
+});
@@ -0,0 +1,136 @@
+import { MathUtil } from "@orca-so/common-sdk";
+import * as anchor from "@project-serum/anchor";
+import { u64 } from "@solana/spl-token";
+import * as assert from "assert";
+import Decimal from "decimal.js";
+import { PositionData, toTx, WhirlpoolContext, WhirlpoolIx } from "../../src";
+import { sleep, TickSpacing, ZERO_BN } from "../utils";
+import { WhirlpoolTestFixture } from "../utils/fixture";
+
+describe("update_fees_and_rewards_batch", () => {
+  const provider = anchor.AnchorProvider.local();
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.Whirlpool;
+  const ctx = WhirlpoolContext.fromWorkspace(provider, program);
+  const fetcher = ctx.fetcher;
+
+  async function initFixture() {
+    const fixture = await new WhirlpoolTestFixture(ctx).init({
+      tickSpacing: TickSpacing.Standard,
+      initialSqrtPrice: MathUtil.toX64(new Decimal(1)),
+      positions: [
+        { tickLowerIndex: -1280, tickUpperIndex: 1280, liquidityAmount: new u64(1_000_000) },
+        { tickLowerIndex: -640, tickUpperIndex: 640, liquidityAmount: new u64(1_000_000) },
+        { tickLowerIndex: -640, tickUpperIndex: 640, liquidityAmount: ZERO_BN },
+      ],
+      rewards: [
+        { emissionsPerSecondX64: MathUtil.toX64(new Decimal(2)), vaultAmount: new u64(1_000_000) },
+      ],
+    });
+    const {
+      poolInitInfo: { whirlpoolPda },
+      positions,
+    } = fixture.getInfos();
+    const tickArrays = positions
+      .reduce<anchor.web3.PublicKey[]>(
+        (keys, position) => [...keys, position.tickArrayLower, position.tickArrayUpper],
+        []
+      )
+      .filter((key, index, keys) => keys.findIndex((other) => other.equals(key)) === index);
+    return { whirlpool: whirlpoolPda.publicKey, positions, tickArrays };
+  }
+
+  it("successfully updates fees and rewards of all positions", async () => {
+    const { whirlpool, positions, tickArrays } = await initFixture();
+    await sleep(1_000);
+
+    await toTx(
+      ctx,
+      WhirlpoolIx.updateFeesAndRewardsBatchIx(ctx.program, {
+        whirlpool,
+        positions: positions.map(({ publicKey }) => publicKey),
+        tickArrays,
+      })
+    ).buildAndExecute();
+
+    for (const { publicKey } of positions.slice(0, 2)) {
+      const position = (await fetcher.getPosition(publicKey, true)) as PositionData;
+      assert.ok(position.rewardInfos[0].amountOwed.gt(ZERO_BN));
+      assert.ok(position.rewardInfos[0].growthInsideCheckpoint.gt(ZERO_BN));
+    }
+
+    // The position without liquidity is skipped
+    const emptyPosition = (await fetcher.getPosition(positions[2].publicKey, true)) as PositionData;
+    assert.ok(emptyPosition.rewardInfos[0].amountOwed.eq(ZERO_BN));
+    assert.ok(emptyPosition.rewardInfos[0].growthInsideCheckpoint.eq(ZERO_BN));
+  });
+
+  it("fails when a position does not belong to the whirlpool", async () => {
+    const { whirlpool, positions, tickArrays } = await initFixture();
+    const { positions: otherPositions } = await initFixture();
+
+    await assert.rejects(
+      toTx(
+        ctx,
+        WhirlpoolIx.updateFeesAndRewardsBatchIx(ctx.program, {
+          whirlpool,
+          positions: [positions[0].publicKey, otherPositions[0].publicKey],
+          tickArrays,
+        })
+      ).buildAndExecute(),
+      /0x179b/ // PositionWhirlpoolMismatch
+    );
+  });
+
+  it("fails when a tick array of a position is not provided", async () => {
+    const { whirlpool, positions } = await initFixture();
+
+    await assert.rejects(
+      toTx(
+        ctx,
+        WhirlpoolIx.updateFeesAndRewardsBatchIx(ctx.program, {
+          whirlpool,
+          positions: [positions[0].publicKey],
+          tickArrays: [],
+        })
+      ).buildAndExecute(),
+      /0x1779/ // TickNotFound
+    );
+  });
+
+  it("fails when there are fewer remaining accounts than tick arrays", async () => {
+    const { whirlpool, tickArrays } = await initFixture();
+    const ix = ctx.program.instruction.updateFeesAndRewardsBatch(tickArrays.length + 1, {
+      accounts: {
+        whirlpool,
+      },
+      remainingAccounts: tickArrays.map((pubkey) => ({
+        pubkey,
+        isWritable: false,
+        isSigner: false,
+      })),
+    });
+
+    await assert.rejects(
+      toTx(ctx, { instructions: [ix], cleanupInstructions: [], signers: [] }).buildAndExecute(),
+      /0x1787/ // InvalidTickArraySequence
+    );
+  });
+
+  it("fails when the tick arrays belong to another whirlpool", async () => {
+    const { whirlpool, positions } = await initFixture();
+    const { tickArrays: otherTickArrays } = await initFixture();
+
+    await assert.rejects(
+      toTx(
+        ctx,
+        WhirlpoolIx.updateFeesAndRewardsBatchIx(ctx.program, {
+          whirlpool,
+          positions: [positions[0].publicKey],
+          tickArrays: otherTickArrays,
+        })
+      ).buildAndExecute(),
+      /0x1779/ // TickNotFound
+    );
+  });
+});
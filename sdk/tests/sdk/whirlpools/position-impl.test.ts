@@ -4,6 +4,7 @@ import * as assert from "assert";
 import Decimal from "decimal.js";
 import {
   buildWhirlpoolClient,
+  decreaseLiquidityQuoteByFraction,
   decreaseLiquidityQuoteByLiquidity,
   increaseLiquidityQuoteByInputToken,
   PriceMath,
@@ -105,6 +106,71 @@ describe("position-impl", () => {
     assert.equal(postWithdrawData.liquidity.toString(), expectedPostWithdrawLiquidity.toString());
   });
 
+  it("decrease a fraction of the liquidity on position", async () => {
+    const { poolInitInfo } = await initTestPool(
+      ctx,
+      TickSpacing.Standard,
+      PriceMath.priceToSqrtPriceX64(new Decimal(100), 6, 6)
+    );
+
+    // Create and mint tokens in this wallet
+    await mintTokensToTestAccount(
+      ctx.provider,
+      poolInitInfo.tokenMintA,
+      10_500_000_000,
+      poolInitInfo.tokenMintB,
+      10_500_000_000
+    );
+
+    const pool = await client.getPool(poolInitInfo.whirlpoolPda.publicKey);
+    const lowerTick = PriceMath.priceToTickIndex(
+      new Decimal(89),
+      pool.getTokenAInfo().decimals,
+      pool.getTokenBInfo().decimals
+    );
+    const upperTick = PriceMath.priceToTickIndex(
+      new Decimal(120),
+      pool.getTokenAInfo().decimals,
+      pool.getTokenBInfo().decimals
+    );
+
+    // [Action] Initialize Tick Arrays
+    const initTickArrayTx = (await pool.initTickArrayForTicks([lowerTick, upperTick]))!;
+    await initTickArrayTx.buildAndExecute();
+
+    // [Action] Create a position at price 89, 120 with 50 token A
+    const { positionAddress } = await initPosition(
+      ctx,
+      pool,
+      new Decimal(89),
+      new Decimal(120),
+      poolInitInfo.tokenMintA,
+      50
+    );
+
+    // [Action] Withdraw 25% of the liquidity away from the position and verify
+    const position = await client.getPosition(positionAddress.publicKey);
+    const preWithdrawData = position.getData();
+    const fraction = Percentage.fromFraction(25, 100);
+    const slippageTolerance = Percentage.fromFraction(1, 100);
+    const expectedQuote = await decreaseLiquidityQuoteByFraction(
+      fraction,
+      slippageTolerance,
+      position,
+      pool
+    );
+
+    await (
+      await position.decreaseLiquidity({ fraction, slippageTolerance }, false)
+    ).buildAndExecute();
+
+    const postWithdrawData = await position.refreshData();
+    const expectedPostWithdrawLiquidity = preWithdrawData.liquidity.sub(
+      expectedQuote.liquidityAmount
+    );
+    assert.equal(postWithdrawData.liquidity.toString(), expectedPostWithdrawLiquidity.toString());
+  });
+
   it("increase & decrease liquidity on position with a different destination, position wallet", async () => {
     const { poolInitInfo } = await initTestPool(
       ctx,
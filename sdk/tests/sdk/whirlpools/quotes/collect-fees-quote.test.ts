@@ -0,0 +1,135 @@
+import { BN } from "@project-serum/anchor";
+import * as assert from "assert";
+import Decimal from "decimal.js";
+import {
+  collectFeesQuoteFromSnapshots,
+  collectFeesQuoteValue,
+  compoundedApy,
+  feeAprFromSnapshots,
+} from "../../../../src";
+
+describe("collectFeesQuoteValue", () => {
+  it("Values fees in both tokens", async () => {
+    // 1.5 SOL (9 decimals) at $20 and 12.25 USDC (6 decimals) at $1
+    const quote = { feeOwedA: new BN(1_500_000_000), feeOwedB: new BN(12_250_000) };
+    const value = collectFeesQuoteValue(quote, 9, 6, new Decimal(20), new Decimal(1));
+    assert.ok(value.eq(new Decimal("42.25")));
+  });
+
+  it("Values fees in a single token", async () => {
+    const quote = { feeOwedA: new BN(0), feeOwedB: new BN(123_456) };
+    const value = collectFeesQuoteValue(quote, 6, 8, new Decimal(5), new Decimal("30000"));
+    assert.ok(value.eq(new Decimal("37.0368")));
+  });
+
+  it("No fees owed", async () => {
+    const quote = { feeOwedA: new BN(0), feeOwedB: new BN(0) };
+    const value = collectFeesQuoteValue(quote, 6, 6, new Decimal(1), new Decimal(1));
+    assert.ok(value.isZero());
+  });
+});
+
+describe("feeAprFromSnapshots", () => {
+  const liquidity = new BN(1_000_000);
+  const snapshotA = {
+    liquidity,
+    feeGrowthInsideA: new BN(7).shln(64),
+    feeGrowthInsideB: new BN(11).shln(64),
+  };
+  // 5 token A & 3 token B (6 decimals) earned
+  const snapshotB = {
+    liquidity,
+    feeGrowthInsideA: new BN(12).shln(64),
+    feeGrowthInsideB: new BN(14).shln(64),
+  };
+
+  it("Fees earned between snapshots", async () => {
+    const quote = collectFeesQuoteFromSnapshots(snapshotA, snapshotB);
+    assert.ok(quote.feeOwedA.eqn(5_000_000));
+    assert.ok(quote.feeOwedB.eqn(3_000_000));
+  });
+
+  it("Fees earned when the fee growth wraps around", async () => {
+    const u128Max = new BN(1).shln(128).subn(1);
+    const wrappedSnapshotA = {
+      liquidity,
+      feeGrowthInsideA: u128Max.sub(new BN(1).shln(64)).addn(1),
+      feeGrowthInsideB: u128Max,
+    };
+    const wrappedSnapshotB = {
+      liquidity,
+      feeGrowthInsideA: new BN(1).shln(64),
+      feeGrowthInsideB: new BN(0),
+    };
+    const quote = collectFeesQuoteFromSnapshots(wrappedSnapshotA, wrappedSnapshotB);
+    assert.ok(quote.feeOwedA.eqn(2_000_000));
+    assert.ok(quote.feeOwedB.eqn(0));
+  });
+
+  it("APR over a day", async () => {
+    // $10 of token A and $3 of token B earned in a day on a $1000 position
+    const apr = feeAprFromSnapshots(
+      snapshotA,
+      snapshotB,
+      60 * 60 * 24,
+      new Decimal(1000),
+      6,
+      6,
+      new Decimal(2),
+      new Decimal(1)
+    );
+    assert.ok(apr.eq(new Decimal("4.745")));
+  });
+
+  it("APR over a year", async () => {
+    const apr = feeAprFromSnapshots(
+      snapshotA,
+      snapshotB,
+      60 * 60 * 24 * 365,
+      new Decimal(130),
+      6,
+      6,
+      new Decimal(2),
+      new Decimal(1)
+    );
+    assert.ok(apr.eq(new Decimal("0.1")));
+  });
+
+  it("Liquidity changed between snapshots", async () => {
+    const changedSnapshotB = { ...snapshotB, liquidity: liquidity.addn(1) };
+    assert.throws(() => collectFeesQuoteFromSnapshots(snapshotA, changedSnapshotB));
+  });
+});
+
+describe("compoundedApy", () => {
+  it("Compounding once a year equals the simple APR", async () => {
+    const apy = compoundedApy(new Decimal("0.12"), new Decimal("0.05"), 1);
+    assert.ok(apy.eq(new Decimal("0.17")));
+  });
+
+  it("Compounding monthly", async () => {
+    // (1 + 0.12 / 12) ^ 12 - 1
+    const apy = compoundedApy(new Decimal("0.12"), new Decimal(0), 12);
+    assert.ok(apy.sub("0.1268250301").abs().lt("0.0000000001"));
+  });
+
+  it("Compounding daily exceeds compounding monthly", async () => {
+    const feeApr = new Decimal("0.12");
+    const rewardApr = new Decimal("0.05");
+    const monthly = compoundedApy(feeApr, rewardApr, 12);
+    const daily = compoundedApy(feeApr, rewardApr, 365);
+    assert.ok(monthly.sub("0.1838917").abs().lt("0.0000001"));
+    assert.ok(daily.sub("0.1852579").abs().lt("0.0000001"));
+    assert.ok(daily.gt(monthly));
+    assert.ok(monthly.gt(feeApr.add(rewardApr)));
+  });
+
+  it("No yield", async () => {
+    assert.ok(compoundedApy(new Decimal(0), new Decimal(0), 365).isZero());
+  });
+
+  it("Invalid compounding frequency", async () => {
+    assert.throws(() => compoundedApy(new Decimal("0.1"), new Decimal(0), 0));
+    assert.throws(() => compoundedApy(new Decimal("0.1"), new Decimal(0), 1.5));
+  });
+});
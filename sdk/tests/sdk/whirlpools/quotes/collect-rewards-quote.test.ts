@@ -0,0 +1,77 @@
+import { BN } from "@project-serum/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  PositionData,
+  projectedRewardsQuote,
+  TickData,
+  WhirlpoolData,
+  WhirlpoolRewardInfoData,
+} from "../../../../src";
+
+describe("projectedRewardsQuote", () => {
+  const emptyRewardInfo: WhirlpoolRewardInfoData = {
+    mint: PublicKey.default,
+    vault: PublicKey.default,
+    authority: PublicKey.default,
+    emissionsPerSecondX64: new BN(0),
+    growthGlobalX64: new BN(0),
+  };
+  // 10 tokens per second emitted to the whole pool liquidity, which the position owns
+  const whirlpool = {
+    liquidity: new BN(1_000_000),
+    tickCurrentIndex: 0,
+    rewardLastUpdatedTimestamp: new BN(1_000),
+    rewardInfos: [
+      {
+        ...emptyRewardInfo,
+        mint: Keypair.generate().publicKey,
+        vault: Keypair.generate().publicKey,
+        emissionsPerSecondX64: new BN(10).shln(64),
+      },
+      emptyRewardInfo,
+      emptyRewardInfo,
+    ],
+  } as WhirlpoolData;
+  const position = {
+    liquidity: new BN(1_000_000),
+    tickLowerIndex: -128,
+    tickUpperIndex: 128,
+    rewardInfos: [...Array(3)].map(() => ({
+      growthInsideCheckpoint: new BN(0),
+      amountOwed: new BN(0),
+    })),
+  } as PositionData;
+  const tick = { rewardGrowthsOutside: [new BN(0), new BN(0), new BN(0)] } as TickData;
+  const params = { whirlpool, position, tickLower: tick, tickUpper: tick };
+
+  it("Projects rewards to the target timestamp", async () => {
+    const quote = projectedRewardsQuote({ ...params, targetTimestamp: new BN(1_100) });
+    assert.ok(quote[0]?.eqn(1_000));
+    assert.equal(quote[1], undefined);
+    assert.equal(quote[2], undefined);
+  });
+
+  it("Projects no rewards before the last update", async () => {
+    const quote = projectedRewardsQuote({ ...params, targetTimestamp: new BN(900) });
+    assert.ok(quote[0]?.isZero());
+  });
+
+  it("Stops projecting when the reward vault is exhausted", async () => {
+    const quote = projectedRewardsQuote({
+      ...params,
+      targetTimestamp: new BN(1_100),
+      rewardVaultAmounts: [new BN(500)],
+    });
+    assert.ok(quote[0]?.eqn(500));
+  });
+
+  it("Does not cap the projection with a sufficient reward vault", async () => {
+    const quote = projectedRewardsQuote({
+      ...params,
+      targetTimestamp: new BN(1_100),
+      rewardVaultAmounts: [new BN(5_000)],
+    });
+    assert.ok(quote[0]?.eqn(1_000));
+  });
+});
@@ -0,0 +1,72 @@
+import { Percentage } from "@orca-so/common-sdk";
+import { BN } from "@project-serum/anchor";
+import * as assert from "assert";
+import {
+  decreaseLiquidityQuoteByFractionWithParams,
+  decreaseLiquidityQuoteByLiquidityWithParams,
+  PriceMath,
+} from "../../../../src";
+
+describe("decreaseLiquidityQuoteByFractionWithParams", () => {
+  const positionLiquidity = new BN(1_000_000_000);
+  const params = {
+    positionLiquidity,
+    tickLowerIndex: -1280,
+    tickUpperIndex: 1280,
+    tickCurrentIndex: 0,
+    sqrtPrice: PriceMath.tickIndexToSqrtPriceX64(0),
+    slippageTolerance: Percentage.fromFraction(1, 100),
+  };
+
+  function quoteByLiquidity(liquidity: BN) {
+    const { positionLiquidity: _, ...rest } = params;
+    return decreaseLiquidityQuoteByLiquidityWithParams({ ...rest, liquidity });
+  }
+
+  it("Withdraws 25% of the position", async () => {
+    const quote = decreaseLiquidityQuoteByFractionWithParams({
+      ...params,
+      fraction: Percentage.fromFraction(25, 100),
+    });
+    assert.equal(quote.liquidityAmount.toString(), "250000000");
+    assert.deepEqual(quote, quoteByLiquidity(new BN(250_000_000)));
+    assert.ok(quote.tokenMinA.lt(quote.tokenEstA));
+    assert.ok(quote.tokenMinB.lt(quote.tokenEstB));
+  });
+
+  it("Withdraws 50% of the position", async () => {
+    const quote = decreaseLiquidityQuoteByFractionWithParams({
+      ...params,
+      fraction: Percentage.fromFraction(1, 2),
+    });
+    assert.equal(quote.liquidityAmount.toString(), "500000000");
+    assert.deepEqual(quote, quoteByLiquidity(new BN(500_000_000)));
+  });
+
+  it("Withdraws 100% of the position", async () => {
+    const quote = decreaseLiquidityQuoteByFractionWithParams({
+      ...params,
+      fraction: Percentage.fromFraction(100, 100),
+    });
+    assert.equal(quote.liquidityAmount.toString(), positionLiquidity.toString());
+    assert.deepEqual(quote, quoteByLiquidity(positionLiquidity));
+  });
+
+  it("Rounds the withdrawn liquidity down", async () => {
+    const quote = decreaseLiquidityQuoteByFractionWithParams({
+      ...params,
+      positionLiquidity: new BN(10),
+      fraction: Percentage.fromFraction(1, 3),
+    });
+    assert.equal(quote.liquidityAmount.toString(), "3");
+  });
+
+  it("Rejects fractions above 1", async () => {
+    assert.throws(() =>
+      decreaseLiquidityQuoteByFractionWithParams({
+        ...params,
+        fraction: Percentage.fromFraction(101, 100),
+      })
+    );
+  });
+});
@@ -0,0 +1,59 @@
+import { Percentage, ZERO } from "@orca-so/common-sdk";
+import { BN } from "@project-serum/anchor";
+import * as assert from "assert";
+import { increaseLiquidityQuoteByTokenAmountsWithParams, PriceMath } from "../../../../src";
+
+describe("increaseLiquidityQuoteByTokenAmountsWithParams", () => {
+  const params = {
+    tokenAmountA: new BN(1_000_000),
+    tokenAmountB: new BN(1_000_000),
+    tickLowerIndex: -1280,
+    tickUpperIndex: 1280,
+    slippageTolerance: Percentage.fromFraction(1, 100),
+  };
+
+  function quoteAtTick(tickCurrentIndex: number, overrides = {}) {
+    return increaseLiquidityQuoteByTokenAmountsWithParams({
+      ...params,
+      tickCurrentIndex,
+      sqrtPrice: PriceMath.tickIndexToSqrtPriceX64(tickCurrentIndex),
+      ...overrides,
+    });
+  }
+
+  it("Deposits only tokenA below the range", async () => {
+    const quote = quoteAtTick(-2560);
+    assert.ok(quote.liquidityAmount.gt(ZERO));
+    assert.ok(quote.tokenEstA.lte(params.tokenAmountA));
+    assert.ok(quote.tokenEstB.isZero());
+    assert.ok(quote.tokenMaxA.gt(quote.tokenEstA));
+    assert.ok(quote.tokenMaxB.isZero());
+  });
+
+  it("Deposits only tokenB above the range", async () => {
+    const quote = quoteAtTick(2560);
+    assert.ok(quote.liquidityAmount.gt(ZERO));
+    assert.ok(quote.tokenEstA.isZero());
+    assert.ok(quote.tokenEstB.lte(params.tokenAmountB));
+    assert.ok(quote.tokenMaxA.isZero());
+    assert.ok(quote.tokenMaxB.gt(quote.tokenEstB));
+  });
+
+  it("Is limited by the scarcer token in range", async () => {
+    const balanced = quoteAtTick(0);
+    assert.ok(balanced.tokenEstA.lte(params.tokenAmountA));
+    assert.ok(balanced.tokenEstB.lte(params.tokenAmountB));
+
+    const limitedByB = quoteAtTick(0, { tokenAmountB: new BN(500_000) });
+    assert.ok(limitedByB.liquidityAmount.lt(balanced.liquidityAmount));
+    assert.ok(limitedByB.tokenEstB.lte(new BN(500_000)));
+    assert.ok(limitedByB.tokenEstA.lt(params.tokenAmountA));
+  });
+
+  it("Quotes no liquidity when the limiting token amount is zero", async () => {
+    const quote = quoteAtTick(0, { tokenAmountA: ZERO });
+    assert.ok(quote.liquidityAmount.isZero());
+    assert.ok(quote.tokenEstA.isZero());
+    assert.ok(quote.tokenEstB.isZero());
+  });
+});
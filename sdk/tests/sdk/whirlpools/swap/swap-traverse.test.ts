@@ -1165,4 +1165,65 @@ describe("swap traversal tests", async () => {
     const afterVaultAmounts = await getVaultAmounts(ctx, whirlpoolData);
     assertQuoteAndResults(aToB, quote, newData, beforeVaultAmounts, afterVaultAmounts);
   });
+
+  /**
+   * |--------------------|b-----x2----a-------b-|x1-a------------------|
+   */
+  it("buildSwap returns the quote and pool snapshot matching the executed swap, a->b", async () => {
+    const currIndex = arrayTickIndexToTickIndex({ arrayIndex: 0, offsetIndex: 15 }, tickSpacing);
+    const aToB = true;
+    const whirlpool = await setupSwapTest({
+      ctx,
+      client,
+      tickSpacing,
+      initSqrtPrice: PriceMath.tickIndexToSqrtPriceX64(currIndex),
+      initArrayStartTicks: [-11264, -5632, 0],
+      fundedPositions: [
+        buildPosition(
+          // a
+          { arrayIndex: -1, offsetIndex: 44 },
+          { arrayIndex: 0, offsetIndex: 30 },
+          tickSpacing,
+          new BN(250_000)
+        ),
+        buildPosition(
+          //b
+          { arrayIndex: -1, offsetIndex: 0 },
+          { arrayIndex: -1, offsetIndex: TICK_ARRAY_SIZE - 1 },
+          tickSpacing,
+          new BN(350_000)
+        ),
+      ],
+    });
+
+    const whirlpoolData = await whirlpool.refreshData();
+    const beforeVaultAmounts = await getVaultAmounts(ctx, whirlpoolData);
+    const quote = await swapQuoteByInputToken(
+      whirlpool,
+      whirlpoolData.tokenMintA,
+      new u64(150000),
+      slippageTolerance,
+      ctx.program.programId,
+      fetcher,
+      true
+    );
+
+    const slotBeforeBuild = await ctx.connection.getSlot();
+    const result = await whirlpool.buildSwap(quote);
+    assert.equal(result.quote, quote);
+    assert.ok(result.poolSnapshotSlot >= slotBeforeBuild);
+    assert.equal(result.poolSnapshot.sqrtPrice.toString(), whirlpoolData.sqrtPrice.toString());
+    assert.equal(result.poolSnapshot.tickCurrentIndex, whirlpoolData.tickCurrentIndex);
+    assert.ok(result.tickArraysUsed[0].equals(quote.tickArray0));
+    assert.equal(
+      new Set(result.tickArraysUsed.map((address) => address.toBase58())).size,
+      result.tickArraysUsed.length
+    );
+
+    await result.tx.buildAndExecute();
+
+    const newData = await whirlpool.refreshData();
+    const afterVaultAmounts = await getVaultAmounts(ctx, whirlpoolData);
+    assertQuoteAndResults(aToB, result.quote, newData, beforeVaultAmounts, afterVaultAmounts);
+  });
 });
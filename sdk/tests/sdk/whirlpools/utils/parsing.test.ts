@@ -0,0 +1,37 @@
+import { BorshAccountsCoder, Idl } from "@project-serum/anchor";
+import { Keypair } from "@solana/web3.js";
+import * as assert from "assert";
+import { AccountName, getAccountDiscriminator, ParsableWhirlpoolAccount } from "../../../../src";
+import * as WhirlpoolIDL from "../../../../src/artifacts/whirlpool.json";
+
+describe("ParsableWhirlpoolAccount", () => {
+  const coder = new BorshAccountsCoder(WhirlpoolIDL as Idl);
+
+  it("Decodes an account by its discriminator", async () => {
+    const feeTier = {
+      whirlpoolsConfig: Keypair.generate().publicKey,
+      tickSpacing: 64,
+      defaultFeeRate: 3000,
+    };
+    const data = await coder.encode(AccountName.FeeTier, feeTier);
+
+    const account = ParsableWhirlpoolAccount.parse(data);
+    assert.ok(account);
+    assert.equal(account.accountName, AccountName.FeeTier);
+    assert.ok(getAccountDiscriminator(AccountName.FeeTier).equals(data.slice(0, 8)));
+    if (account.accountName === AccountName.FeeTier) {
+      assert.ok(account.data.whirlpoolsConfig.equals(feeTier.whirlpoolsConfig));
+      assert.equal(account.data.tickSpacing, feeTier.tickSpacing);
+      assert.equal(account.data.defaultFeeRate, feeTier.defaultFeeRate);
+    }
+  });
+
+  it("Returns null for an unknown discriminator", async () => {
+    assert.equal(ParsableWhirlpoolAccount.parse(Buffer.alloc(64, 1)), null);
+  });
+
+  it("Returns null for data shorter than a discriminator", async () => {
+    assert.equal(ParsableWhirlpoolAccount.parse(Buffer.alloc(4)), null);
+    assert.equal(ParsableWhirlpoolAccount.parse(null), null);
+  });
+});
@@ -0,0 +1,28 @@
+import * as assert from "assert";
+import { PublicKey } from "@solana/web3.js";
+import { ORCA_WHIRLPOOL_PROGRAM_ID, ORCA_WHIRLPOOLS_CONFIG, PDAUtil } from "../../../../src";
+
+describe("PDAUtil tests", () => {
+  // SOL/USDC (tick spacing 64) Whirlpool on mainnet and its Oracle
+  const whirlpool = new PublicKey("HJPjoWUrhoZzkNfRpHuieeFk9WcZWjwy6PBjZ81ngndJ");
+  const oracle = new PublicKey("4GkRbcYg1VKsZropgai4dMf2Nj2PkXNLf43knFpavrSi");
+
+  describe("getOracle", () => {
+    it("Derives the Oracle of a known Whirlpool", async () => {
+      const pda = PDAUtil.getOracle(ORCA_WHIRLPOOL_PROGRAM_ID, whirlpool);
+      assert.ok(pda.publicKey.equals(oracle));
+      assert.equal(pda.bump, 255);
+    });
+
+    it("Known Whirlpool is derived from the mainnet WhirlpoolsConfig", async () => {
+      const pda = PDAUtil.getWhirlpool(
+        ORCA_WHIRLPOOL_PROGRAM_ID,
+        ORCA_WHIRLPOOLS_CONFIG,
+        new PublicKey("So11111111111111111111111111111111111111112"),
+        new PublicKey("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
+        64
+      );
+      assert.ok(pda.publicKey.equals(whirlpool));
+    });
+  });
+});
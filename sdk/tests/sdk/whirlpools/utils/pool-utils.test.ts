@@ -1,5 +1,7 @@
 import * as assert from "assert";
-import { TokenType, PoolUtil } from "../../../../src";
+import { Percentage } from "@orca-so/common-sdk";
+import { TokenType, PoolUtil, PriceMath, PDAUtil } from "../../../../src";
+import { SwapErrorCode, WhirlpoolsError } from "../../../../src/errors/errors";
 import { testWhirlpoolData } from "../../../utils/testDataTypes";
 import { Keypair } from "@solana/web3.js";
 
@@ -23,4 +25,129 @@ describe("PoolUtils tests", () => {
       assert.ok(result === undefined);
     });
   });
+
+  describe("validateTokenVaults", () => {
+    it("Vaults match the Whirlpool", async () => {
+      const whirlpoolData = testWhirlpoolData;
+      PoolUtil.validateTokenVaults(
+        whirlpoolData,
+        whirlpoolData.tokenVaultA,
+        whirlpoolData.tokenVaultB.toBase58()
+      );
+    });
+
+    it("Token vault A does not match", async () => {
+      const whirlpoolData = testWhirlpoolData;
+      assert.throws(
+        () =>
+          PoolUtil.validateTokenVaults(
+            whirlpoolData,
+            Keypair.generate().publicKey,
+            whirlpoolData.tokenVaultB
+          ),
+        (err) =>
+          WhirlpoolsError.isWhirlpoolsErrorCode(err, SwapErrorCode.TokenVaultMismatch) &&
+          (err as WhirlpoolsError).message.startsWith("tokenVaultA mismatch")
+      );
+    });
+
+    it("Token vaults are swapped", async () => {
+      const whirlpoolData = testWhirlpoolData;
+      assert.throws(
+        () =>
+          PoolUtil.validateTokenVaults(
+            whirlpoolData,
+            whirlpoolData.tokenVaultB,
+            whirlpoolData.tokenVaultA
+          ),
+        (err) => WhirlpoolsError.isWhirlpoolsErrorCode(err, SwapErrorCode.TokenVaultMismatch)
+      );
+    });
+  });
+
+  describe("validateOracle", () => {
+    const programId = Keypair.generate().publicKey;
+    const whirlpool = Keypair.generate().publicKey;
+
+    it("Oracle matches the Whirlpool", async () => {
+      const oracle = PDAUtil.getOracle(programId, whirlpool).publicKey;
+      PoolUtil.validateOracle(programId, whirlpool, oracle);
+      PoolUtil.validateOracle(programId.toBase58(), whirlpool.toBase58(), oracle.toBase58());
+    });
+
+    it("Oracle of another Whirlpool", async () => {
+      const otherOracle = PDAUtil.getOracle(programId, Keypair.generate().publicKey).publicKey;
+      assert.throws(
+        () => PoolUtil.validateOracle(programId, whirlpool, otherOracle),
+        (err) =>
+          WhirlpoolsError.isWhirlpoolsErrorCode(err, SwapErrorCode.OracleMismatch) &&
+          (err as WhirlpoolsError).message.startsWith("oracle mismatch")
+      );
+    });
+
+    it("Oracle derived from another program", async () => {
+      const otherOracle = PDAUtil.getOracle(Keypair.generate().publicKey, whirlpool).publicKey;
+      assert.throws(
+        () => PoolUtil.validateOracle(programId, whirlpool, otherOracle),
+        (err) => WhirlpoolsError.isWhirlpoolsErrorCode(err, SwapErrorCode.OracleMismatch)
+      );
+    });
+  });
+
+  describe("getFeeTierFeeRate", () => {
+    it("Returns the default fee rate of the fee tier", async () => {
+      const feeTier = {
+        whirlpoolsConfig: Keypair.generate().publicKey,
+        tickSpacing: 64,
+        defaultFeeRate: 3000,
+      };
+      const result = PoolUtil.getFeeTierFeeRate(feeTier);
+      assert.equal(result.numerator.toNumber(), 3000);
+      assert.equal(result.denominator.toNumber(), 1_000_000);
+    });
+
+    it("Zero fee tier", async () => {
+      const feeTier = {
+        whirlpoolsConfig: Keypair.generate().publicKey,
+        tickSpacing: 1,
+        defaultFeeRate: 0,
+      };
+      assert.ok(PoolUtil.getFeeTierFeeRate(feeTier).numerator.isZero());
+    });
+  });
+
+  describe("isPriceWithinSwapBounds", () => {
+    const preSwapSqrtPrice = PriceMath.tickIndexToSqrtPriceX64(0);
+    const postSwapSqrtPrice = PriceMath.tickIndexToSqrtPriceX64(100);
+    const tolerance = Percentage.fromFraction(50, 10000);
+
+    it("Price within the expected range", async () => {
+      const sqrtPrice = PriceMath.tickIndexToSqrtPriceX64(50);
+      assert.ok(
+        PoolUtil.isPriceWithinSwapBounds(preSwapSqrtPrice, postSwapSqrtPrice, sqrtPrice, tolerance)
+      );
+    });
+
+    it("Price drift within tolerance", async () => {
+      const above = PriceMath.tickIndexToSqrtPriceX64(140);
+      const below = PriceMath.tickIndexToSqrtPriceX64(-40);
+      assert.ok(
+        PoolUtil.isPriceWithinSwapBounds(preSwapSqrtPrice, postSwapSqrtPrice, above, tolerance)
+      );
+      assert.ok(
+        PoolUtil.isPriceWithinSwapBounds(preSwapSqrtPrice, postSwapSqrtPrice, below, tolerance)
+      );
+    });
+
+    it("Price drift out of tolerance", async () => {
+      const above = PriceMath.tickIndexToSqrtPriceX64(160);
+      const below = PriceMath.tickIndexToSqrtPriceX64(-60);
+      assert.ok(
+        !PoolUtil.isPriceWithinSwapBounds(preSwapSqrtPrice, postSwapSqrtPrice, above, tolerance)
+      );
+      assert.ok(
+        !PoolUtil.isPriceWithinSwapBounds(preSwapSqrtPrice, postSwapSqrtPrice, below, tolerance)
+      );
+    });
+  });
 });
@@ -0,0 +1,69 @@
+import * as assert from "assert";
+import Decimal from "decimal.js";
+import { PriceMath } from "../../../../src";
+
+describe("PriceMath tests", () => {
+  describe("sqrtPriceX64ToPrice", () => {
+    it("Adjusts the price of token A in terms of token B for the token decimals", async () => {
+      // 1 token A (9 decimals) = 20 token B (6 decimals), so 1 raw unit of A = 0.02 raw units of B
+      const sqrtPriceX64 = PriceMath.priceToSqrtPriceX64(new Decimal(20), 9, 6);
+      const rawPrice = PriceMath.sqrtPriceX64ToPrice(sqrtPriceX64, 0, 0);
+      assert.ok(rawPrice.toSignificantDigits(15).eq(new Decimal("0.02")));
+
+      const price = PriceMath.sqrtPriceX64ToPrice(sqrtPriceX64, 9, 6);
+      assert.ok(price.toSignificantDigits(15).eq(new Decimal(20)));
+    });
+
+    it("Matches the price at the tick index", async () => {
+      const tickIndex = 29952;
+      const price = PriceMath.tickIndexToPrice(tickIndex, 9, 6);
+      assert.ok(
+        price.eq(
+          PriceMath.sqrtPriceX64ToPrice(PriceMath.tickIndexToSqrtPriceX64(tickIndex), 9, 6)
+        )
+      );
+      // A price between the tick and the next tick rounds down to the tick
+      assert.equal(PriceMath.priceToTickIndex(price.mul("1.00001"), 9, 6), tickIndex);
+    });
+  });
+
+  describe("invertPrice", () => {
+    it("Inverts a price", async () => {
+      assert.ok(PriceMath.invertPrice(new Decimal(4)).eq(new Decimal(0.25)));
+      assert.ok(PriceMath.invertPrice(new Decimal("0.0001")).eq(new Decimal(10000)));
+    });
+
+    it("Round-trip inversion returns the original price", async () => {
+      const prices = ["1", "22.4173", "0.000001234", "98765.4321"];
+      prices.forEach((price) => {
+        const original = new Decimal(price);
+        const roundTrip = PriceMath.invertPrice(PriceMath.invertPrice(original));
+        assert.ok(roundTrip.toSignificantDigits(15).eq(original), price);
+      });
+    });
+  });
+
+  describe("invertPriceWithDecimals", () => {
+    it("Matches inverting the decimals-adjusted price from the sqrt-price", async () => {
+      const sqrtPriceX64 = PriceMath.tickIndexToSqrtPriceX64(-39424);
+      const rawPrice = PriceMath.sqrtPriceX64ToPrice(sqrtPriceX64, 0, 0);
+      const price = PriceMath.sqrtPriceX64ToPrice(sqrtPriceX64, 9, 6);
+
+      const inverted = PriceMath.invertPriceWithDecimals(rawPrice, 9, 6);
+      assert.ok(
+        inverted.toSignificantDigits(15).eq(PriceMath.invertPrice(price).toSignificantDigits(15))
+      );
+    });
+
+    it("Round-trip inversion returns the original price", async () => {
+      // 1 token A (9 decimals) = 20 token B (6 decimals)
+      const rawPrice = new Decimal("0.02");
+      const inverted = PriceMath.invertPriceWithDecimals(rawPrice, 9, 6);
+      assert.ok(inverted.eq(new Decimal("0.05")));
+
+      // The raw price of token B in terms of token A is 50, inverting it with swapped decimals
+      const rawInvertedPrice = PriceMath.invertPrice(rawPrice);
+      assert.ok(PriceMath.invertPriceWithDecimals(rawInvertedPrice, 6, 9).eq(new Decimal(20)));
+    });
+  });
+});
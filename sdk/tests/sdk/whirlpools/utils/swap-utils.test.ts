@@ -1,5 +1,10 @@
+import { MathUtil, Percentage } from "@orca-so/common-sdk";
+import { BN } from "@project-serum/anchor";
+import { u64 } from "@solana/spl-token";
 import * as assert from "assert";
-import { SwapUtils, SwapDirection } from "../../../../src";
+import Decimal from "decimal.js";
+import { PriceMath, SwapUtils, SwapDirection } from "../../../../src";
+import { getNextSqrtPrice } from "../../../../src/utils/math/token-math";
 import { testWhirlpoolData } from "../../../utils/testDataTypes";
 import { Keypair } from "@solana/web3.js";
 
@@ -41,4 +46,63 @@ describe("SwapUtils tests", () => {
       assert.equal(result, undefined);
     });
   });
+
+  describe("getLiquidityForPriceImpact", () => {
+    const amountIn = new u64(1_000_000_000);
+
+    function getPriceImpact(sqrtPrice: BN, liquidity: BN, aToB: boolean): Decimal {
+      const nextSqrtPrice = getNextSqrtPrice(sqrtPrice, liquidity, amountIn, true, aToB);
+      const price = MathUtil.fromX64(sqrtPrice).pow(2);
+      const nextPrice = MathUtil.fromX64(nextSqrtPrice).pow(2);
+      return nextPrice.sub(price).abs().div(price);
+    }
+
+    [
+      { tickIndex: 0, impactBps: 50 },
+      { tickIndex: 0, impactBps: 100 },
+      { tickIndex: -39104, impactBps: 10 },
+      { tickIndex: 25000, impactBps: 500 },
+    ].forEach(({ tickIndex, impactBps }) => {
+      [true, false].forEach((aToB) => {
+        it(`tick ${tickIndex}, ${impactBps} bps, aToB = ${aToB}`, async () => {
+          const sqrtPrice = PriceMath.tickIndexToSqrtPriceX64(tickIndex);
+          const target = new Decimal(impactBps).div(10_000);
+          const liquidity = SwapUtils.getLiquidityForPriceImpact(
+            sqrtPrice,
+            amountIn,
+            aToB,
+            Percentage.fromFraction(impactBps, 10_000)
+          );
+
+          const impact = getPriceImpact(sqrtPrice, liquidity, aToB);
+          assert.ok(impact.lte(target), `impact ${impact} above target ${target}`);
+          assert.ok(impact.gt(target.mul(0.9999)), `impact ${impact} far below target ${target}`);
+        });
+      });
+    });
+
+    it("Less liquidity has more price impact", async () => {
+      const sqrtPrice = PriceMath.tickIndexToSqrtPriceX64(0);
+      const liquidity = SwapUtils.getLiquidityForPriceImpact(
+        sqrtPrice,
+        amountIn,
+        true,
+        Percentage.fromFraction(1, 100)
+      );
+      const impact = getPriceImpact(sqrtPrice, liquidity.divn(2), true);
+      assert.ok(impact.gt(new Decimal("0.01")));
+    });
+
+    it("Invalid price impact", async () => {
+      const sqrtPrice = PriceMath.tickIndexToSqrtPriceX64(0);
+      const noImpact = Percentage.fromFraction(0, 100);
+      const fullImpact = Percentage.fromFraction(1, 1);
+      assert.throws(() =>
+        SwapUtils.getLiquidityForPriceImpact(sqrtPrice, amountIn, true, noImpact)
+      );
+      assert.throws(() =>
+        SwapUtils.getLiquidityForPriceImpact(sqrtPrice, amountIn, true, fullImpact)
+      );
+    });
+  });
 });
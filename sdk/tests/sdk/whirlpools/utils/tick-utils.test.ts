@@ -0,0 +1,114 @@
+import * as assert from "assert";
+import { TickArrayUtil, TickRounding, TickUtil } from "../../../../src";
+
+describe("TickUtil tests", () => {
+  describe("getFullRangeTickIndex", () => {
+    it("tickSpacing = 1", async () => {
+      assert.deepEqual(TickUtil.getFullRangeTickIndex(1), [-443636, 443636]);
+    });
+
+    it("tickSpacing = 64", async () => {
+      assert.deepEqual(TickUtil.getFullRangeTickIndex(64), [-443584, 443584]);
+    });
+
+    it("tickSpacing = 128", async () => {
+      assert.deepEqual(TickUtil.getFullRangeTickIndex(128), [-443520, 443520]);
+    });
+  });
+});
+
+describe("TickUtil.getNearestInitializableTickIndex", () => {
+  it("Rounds a positive tick", async () => {
+    assert.equal(TickUtil.getNearestInitializableTickIndex(100, 64, TickRounding.Down), 64);
+    assert.equal(TickUtil.getNearestInitializableTickIndex(100, 64, TickRounding.Up), 128);
+    assert.equal(TickUtil.getNearestInitializableTickIndex(100, 64, TickRounding.Nearest), 128);
+    assert.equal(TickUtil.getNearestInitializableTickIndex(90, 64, TickRounding.Nearest), 64);
+  });
+
+  it("Rounds a negative tick", async () => {
+    assert.equal(TickUtil.getNearestInitializableTickIndex(-100, 64, TickRounding.Down), -128);
+    assert.equal(TickUtil.getNearestInitializableTickIndex(-100, 64, TickRounding.Up), -64);
+    assert.equal(TickUtil.getNearestInitializableTickIndex(-100, 64, TickRounding.Nearest), -128);
+    assert.equal(TickUtil.getNearestInitializableTickIndex(-90, 64, TickRounding.Nearest), -64);
+  });
+
+  it("Rounds halfway ticks up with Nearest", async () => {
+    assert.equal(TickUtil.getNearestInitializableTickIndex(32, 64, TickRounding.Nearest), 64);
+    assert.equal(TickUtil.getNearestInitializableTickIndex(-32, 64, TickRounding.Nearest), 0);
+  });
+
+  it("Keeps initializable ticks", async () => {
+    [TickRounding.Down, TickRounding.Up, TickRounding.Nearest].forEach((round) => {
+      assert.equal(TickUtil.getNearestInitializableTickIndex(-128, 64, round), -128);
+      assert.equal(TickUtil.getNearestInitializableTickIndex(0, 64, round), 0);
+    });
+  });
+
+  it("Rejects out of bounds ticks", async () => {
+    // MAX_TICK_INDEX = 443636
+    assert.throws(() => TickUtil.getNearestInitializableTickIndex(443600, 64, TickRounding.Up));
+    assert.throws(() => TickUtil.getNearestInitializableTickIndex(-443600, 64, TickRounding.Down));
+    assert.equal(TickUtil.getNearestInitializableTickIndex(443600, 64, TickRounding.Down), 443584);
+  });
+});
+
+describe("TickUtil.getMaxRangeInTickArrays", () => {
+  // Each tick-array covers 5632 ticks with a tick spacing of 64
+  it("K = 1 around the middle of a tick-array", async () => {
+    assert.deepEqual(TickUtil.getMaxRangeInTickArrays(2826, 64, 1), [64, 5568]);
+  });
+
+  it("K = 1 at the start of a tick-array", async () => {
+    assert.equal(TickUtil.getMaxRangeInTickArrays(0, 64, 1), null);
+  });
+
+  it("K = 2 around the middle of a tick-array", async () => {
+    assert.deepEqual(TickUtil.getMaxRangeInTickArrays(2816, 64, 2), [0, 5632]);
+  });
+
+  it("K = 3 around the middle of a tick-array", async () => {
+    assert.deepEqual(TickUtil.getMaxRangeInTickArrays(2826, 64, 3), [-5568, 11200]);
+  });
+
+  it("K = 3 around a negative tick", async () => {
+    // Center tick -2880 is at offset 2752 of the tick-array starting at -5632
+    assert.deepEqual(TickUtil.getMaxRangeInTickArrays(-2850, 64, 3), [-11264, 5504]);
+  });
+
+  it("Range is clamped to the full range", async () => {
+    const [minTickIndex, maxTickIndex] = TickUtil.getFullRangeTickIndex(64);
+    const range = TickUtil.getMaxRangeInTickArrays(0, 64, 1000);
+    assert.deepEqual(range, [-443584, 443584]);
+    assert.ok(range![0] >= minTickIndex && range![1] <= maxTickIndex);
+  });
+});
+
+describe("TickArrayUtil tests", () => {
+  describe("getFullRangeTickArrayStartIndexes", () => {
+    it("tickSpacing = 1", async () => {
+      assert.deepEqual(TickArrayUtil.getFullRangeTickArrayStartIndexes(1), [-443696, 443608]);
+    });
+
+    it("tickSpacing = 8", async () => {
+      assert.deepEqual(TickArrayUtil.getFullRangeTickArrayStartIndexes(8), [-444224, 443520]);
+    });
+
+    it("tickSpacing = 64", async () => {
+      assert.deepEqual(TickArrayUtil.getFullRangeTickArrayStartIndexes(64), [-444928, 439296]);
+    });
+
+    it("tickSpacing = 128", async () => {
+      assert.deepEqual(TickArrayUtil.getFullRangeTickArrayStartIndexes(128), [-450560, 439296]);
+    });
+
+    it("start indexes contain the full-range ticks", async () => {
+      for (const tickSpacing of [1, 8, 64, 128]) {
+        const [minTickIndex, maxTickIndex] = TickUtil.getFullRangeTickIndex(tickSpacing);
+        const [lower, upper] = TickArrayUtil.getFullRangeTickArrayStartIndexes(tickSpacing);
+        const ticksInArray = 88 * tickSpacing;
+        assert.ok(minTickIndex >= lower && minTickIndex < lower + ticksInArray);
+        assert.ok(maxTickIndex >= upper && maxTickIndex < upper + ticksInArray);
+      }
+    });
+  });
+});
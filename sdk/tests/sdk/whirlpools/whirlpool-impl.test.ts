@@ -1,5 +1,6 @@
-import { deriveATA, Percentage, TransactionBuilder } from "@orca-so/common-sdk";
+import { deriveATA, MathUtil, Percentage, TransactionBuilder } from "@orca-so/common-sdk";
 import * as anchor from "@project-serum/anchor";
+import { u64 } from "@solana/spl-token";
 import * as assert from "assert";
 import Decimal from "decimal.js";
 import {
@@ -7,8 +8,12 @@ import {
   decreaseLiquidityQuoteByLiquidity,
   increaseLiquidityQuoteByInputToken,
   PDAUtil,
+  PositionData,
   PriceMath,
+  swapQuoteByInputToken,
   TickUtil,
+  toTx,
+  WhirlpoolIx,
 } from "../../../src";
 import { WhirlpoolContext } from "../../../src/context";
 import {
@@ -18,9 +23,11 @@ import {
   systemTransferTx,
   TickSpacing,
   transfer,
+  ZERO_BN,
 } from "../../utils";
-import { initTestPool } from "../../utils/init-utils";
-import { mintTokensToTestAccount } from "../../utils/test-builders";
+import { WhirlpoolTestFixture } from "../../utils/fixture";
+import { buildTestPoolParams, initTestPool } from "../../utils/init-utils";
+import { initTestPoolWithPositions, mintTokensToTestAccount } from "../../utils/test-builders";
 
 describe("whirlpool-impl", () => {
   const provider = anchor.AnchorProvider.local();
@@ -279,4 +286,362 @@ describe("whirlpool-impl", () => {
       expectationQuote.tokenMinB.toString()
     );
   });
+
+  it("close a position with uncollected fees in a single transaction", async () => {
+    // In same tick array - start index 22528
+    const tickLowerIndex = 29440;
+    const tickUpperIndex = 33536;
+
+    const fixture = await new WhirlpoolTestFixture(ctx).init({
+      tickSpacing: TickSpacing.Standard,
+      positions: [{ tickLowerIndex, tickUpperIndex, liquidityAmount: new u64(10_000_000) }],
+    });
+    const {
+      poolInitInfo: {
+        whirlpoolPda,
+        tokenVaultAKeypair,
+        tokenVaultBKeypair,
+        tokenMintA,
+        tokenMintB,
+      },
+      tokenAccountA,
+      tokenAccountB,
+      positions,
+    } = fixture.getInfos();
+    const positionAddress = positions[0].publicKey;
+
+    const tickArrayPda = PDAUtil.getTickArray(ctx.program.programId, whirlpoolPda.publicKey, 22528);
+    const oraclePda = PDAUtil.getOracle(ctx.program.programId, whirlpoolPda.publicKey);
+
+    // Accrue fees in token A
+    await toTx(
+      ctx,
+      WhirlpoolIx.swapIx(ctx.program, {
+        amount: new u64(200_000),
+        otherAmountThreshold: ZERO_BN,
+        sqrtPriceLimit: MathUtil.toX64(new Decimal(4)),
+        amountSpecifiedIsInput: true,
+        aToB: true,
+        whirlpool: whirlpoolPda.publicKey,
+        tokenAuthority: ctx.wallet.publicKey,
+        tokenOwnerAccountA: tokenAccountA,
+        tokenVaultA: tokenVaultAKeypair.publicKey,
+        tokenOwnerAccountB: tokenAccountB,
+        tokenVaultB: tokenVaultBKeypair.publicKey,
+        tickArray0: tickArrayPda.publicKey,
+        tickArray1: tickArrayPda.publicKey,
+        tickArray2: tickArrayPda.publicKey,
+        oracle: oraclePda.publicKey,
+      })
+    ).buildAndExecute();
+
+    await toTx(
+      ctx,
+      WhirlpoolIx.updateFeesAndRewardsIx(ctx.program, {
+        whirlpool: whirlpoolPda.publicKey,
+        position: positionAddress,
+        tickArrayLower: tickArrayPda.publicKey,
+        tickArrayUpper: tickArrayPda.publicKey,
+      })
+    ).buildAndExecute();
+
+    const positionBeforeClose = (await fetcher.getPosition(positionAddress, true)) as PositionData;
+    assert.ok(positionBeforeClose.feeOwedA.gt(ZERO_BN));
+
+    const pool = await client.getPool(whirlpoolPda.publicKey, true);
+    const decreaseQuote = await decreaseLiquidityQuoteByLiquidity(
+      positionBeforeClose.liquidity,
+      Percentage.fromDecimal(new Decimal(0)),
+      await client.getPosition(positionAddress),
+      pool
+    );
+
+    // [Action] Close the position to a fresh wallet without collecting fees first
+    const destinationWallet = anchor.web3.Keypair.generate().publicKey;
+    await (
+      await pool.closePosition(positionAddress, Percentage.fromFraction(1, 100), destinationWallet)
+    ).buildAndExecute();
+
+    // Verify position is closed and the destination wallet received both the liquidity and the fees
+    const postClosePosition = await fetcher.getPosition(positionAddress, true);
+    assert.ok(postClosePosition === null);
+
+    const destinationTokenA = await deriveATA(destinationWallet, tokenMintA);
+    const destinationTokenB = await deriveATA(destinationWallet, tokenMintB);
+    assert.equal(
+      await getTokenBalance(ctx.provider, destinationTokenA),
+      decreaseQuote.tokenMinA.add(positionBeforeClose.feeOwedA).toString()
+    );
+    assert.equal(
+      await getTokenBalance(ctx.provider, destinationTokenB),
+      decreaseQuote.tokenMinB.add(positionBeforeClose.feeOwedB).toString()
+    );
+  });
+
+  it("open a position and initialize its tick arrays in the same transaction", async () => {
+    const funderKeypair = anchor.web3.Keypair.generate();
+    await systemTransferTx(provider, funderKeypair.publicKey, ONE_SOL).buildAndExecute();
+
+    const { pool } = await initTestPoolWithPositions(ctx, []);
+    const poolData = pool.getData();
+    const tokenAInfo = pool.getTokenAInfo();
+    const tokenBInfo = pool.getTokenBInfo();
+
+    const tickLower = PriceMath.priceToInitializableTickIndex(
+      new Decimal(96),
+      tokenAInfo.decimals,
+      tokenBInfo.decimals,
+      poolData.tickSpacing
+    );
+    const tickUpper = PriceMath.priceToInitializableTickIndex(
+      new Decimal(101),
+      tokenAInfo.decimals,
+      tokenBInfo.decimals,
+      poolData.tickSpacing
+    );
+    const quote = increaseLiquidityQuoteByInputToken(
+      poolData.tokenMintA,
+      new Decimal(50),
+      tickLower,
+      tickUpper,
+      Percentage.fromFraction(1, 100),
+      pool
+    );
+
+    // [Action] Open Position (and increase L) without initializing the tick arrays beforehand
+    const { positionMint, tx: openIx } = await pool.openPosition(
+      tickLower,
+      tickUpper,
+      quote,
+      ctx.wallet.publicKey,
+      funderKeypair.publicKey,
+      true
+    );
+    await openIx.addSigner(funderKeypair).buildAndExecute();
+
+    // Verify the tick arrays were initialized by the open position transaction
+    const tickArrayLowerPda = PDAUtil.getTickArrayFromTickIndex(
+      tickLower,
+      poolData.tickSpacing,
+      pool.getAddress(),
+      ctx.program.programId
+    );
+    const tickArrayUpperPda = PDAUtil.getTickArrayFromTickIndex(
+      tickUpper,
+      poolData.tickSpacing,
+      pool.getAddress(),
+      ctx.program.programId
+    );
+    assert.ok(!!(await fetcher.getTickArray(tickArrayLowerPda.publicKey, true)));
+    assert.ok(!!(await fetcher.getTickArray(tickArrayUpperPda.publicKey, true)));
+
+    // Verify position exists and numbers fit input parameters
+    const positionAddress = PDAUtil.getPosition(ctx.program.programId, positionMint).publicKey;
+    const positionData = (await client.getPosition(positionAddress)).getData();
+    assert.ok(positionData.liquidity.eq(quote.liquidityAmount));
+    assert.ok(positionData.tickLowerIndex === tickLower);
+    assert.ok(positionData.tickUpperIndex === tickUpper);
+
+    // [Action] Open a second position on the same range, the tick arrays are already initialized
+    const { tx: secondOpenIx } = await pool.openPosition(
+      tickLower,
+      tickUpper,
+      quote,
+      ctx.wallet.publicKey,
+      funderKeypair.publicKey,
+      true
+    );
+    await secondOpenIx.addSigner(funderKeypair).buildAndExecute();
+  });
+
+  it("fetch the positions owned by a wallet with their pools", async () => {
+    // [Action] Open two positions on a new pool
+    const { pool, positions: openedPositions } = await initTestPoolWithPositions(ctx);
+    const tokenAInfo = pool.getTokenAInfo();
+    const tokenBInfo = pool.getTokenBInfo();
+    const positionMints = openedPositions.map(({ positionMint }) => positionMint);
+
+    // [Action] Fetch the positions of the wallet
+    const positions = (await client.getPositionsForOwner(ctx.wallet.publicKey, true)).filter(
+      ({ pool: positionPool }) => positionPool.getAddress().equals(pool.getAddress())
+    );
+
+    assert.equal(positions.length, 2);
+    positions.forEach(({ position, pool: positionPool }) => {
+      const positionData = position.getData();
+      assert.ok(positionMints.some((mint) => mint.equals(positionData.positionMint)));
+      assert.ok(positionData.whirlpool.equals(pool.getAddress()));
+      assert.ok(positionPool.getTokenAInfo().mint.equals(tokenAInfo.mint));
+      assert.equal(positionPool.getTokenAInfo().decimals, tokenAInfo.decimals);
+      assert.ok(positionPool.getTokenBInfo().mint.equals(tokenBInfo.mint));
+      assert.equal(positionPool.getTokenBInfo().decimals, tokenBInfo.decimals);
+    });
+  });
+
+  it("harvest the fees of all positions owned by a wallet", async () => {
+    // [Action] Open two positions around the current price
+    const { pool, positions } = await initTestPoolWithPositions(ctx);
+    const positionAddresses = positions.map(({ positionAddress }) => positionAddress.publicKey);
+
+    // [Action] Swap through both positions to accrue fees
+    const swapQuote = await swapQuoteByInputToken(
+      await client.getPool(pool.getAddress(), true),
+      pool.getData().tokenMintA,
+      new u64(1_000_000),
+      Percentage.fromFraction(1, 100),
+      ctx.program.programId,
+      fetcher,
+      true
+    );
+    await (await pool.swap(swapQuote)).buildAndExecute();
+
+    // Both harvests fit in one transaction, but together they reference more than 12 accounts
+    const owner = ctx.wallet.publicKey;
+    assert.equal(await client.getHarvestTransactionCount(owner, undefined, true), 1);
+    assert.equal(await client.getHarvestTransactionCount(owner, 12, true), 2);
+
+    // [Action] Harvest all positions of the wallet
+    const txs = await client.harvestAllPositions(ctx.wallet.publicKey, true);
+    assert.equal(txs.length, 1);
+    for (const tx of txs) {
+      await tx.buildAndExecute();
+    }
+
+    for (const positionAddress of positionAddresses) {
+      const position = (await fetcher.getPosition(positionAddress, true)) as PositionData;
+      assert.ok(!position.feeGrowthCheckpointA.isZero());
+      assert.ok(position.feeOwedA.isZero());
+      assert.ok(position.feeOwedB.isZero());
+    }
+  });
+
+  it("open and add liquidity to a position from a price range and an input token amount", async () => {
+    const funderKeypair = anchor.web3.Keypair.generate();
+    await systemTransferTx(provider, funderKeypair.publicKey, ONE_SOL).buildAndExecute();
+
+    // Create a pool and mint tokens in this wallet
+    const { poolInitInfo, pool, mintedTokenAmount, userTokenAAccount, userTokenBAccount } =
+      await initTestPoolWithPositions(ctx, []);
+    const tokenAInfo = pool.getTokenAInfo();
+    const tokenBInfo = pool.getTokenBInfo();
+    const poolData = pool.getData();
+
+    const lowerPrice = new Decimal(96);
+    const upperPrice = new Decimal(101);
+    const expectedTickLower = PriceMath.priceToInitializableTickIndex(
+      lowerPrice,
+      tokenAInfo.decimals,
+      tokenBInfo.decimals,
+      poolData.tickSpacing
+    );
+    const expectedTickUpper = PriceMath.priceToInitializableTickIndex(
+      upperPrice,
+      tokenAInfo.decimals,
+      tokenBInfo.decimals,
+      poolData.tickSpacing
+    );
+
+    // [Action] Initialize Tick Arrays
+    const initTickArrayTx = (
+      await pool.initTickArrayForTicks(
+        [expectedTickLower, expectedTickUpper],
+        funderKeypair.publicKey
+      )
+    )?.addSigner(funderKeypair);
+    assert.ok(!!initTickArrayTx);
+
+    // [Action] Open Position (and increase L) from a price range and 50 token B
+    const {
+      positionMint,
+      tx: openIx,
+      quote,
+    } = await pool.openPositionByPriceRange(
+      lowerPrice,
+      upperPrice,
+      tokenBInfo.mint,
+      new Decimal(50),
+      Percentage.fromFraction(1, 100),
+      ctx.wallet.publicKey,
+      funderKeypair.publicKey,
+      false
+    );
+    openIx.addSigner(funderKeypair);
+    await TransactionBuilder.sendAll(ctx.provider, [initTickArrayTx, openIx]);
+
+    assert.equal(quote.tickLowerIndex, expectedTickLower);
+    assert.equal(quote.tickUpperIndex, expectedTickUpper);
+    assert.ok(quote.tokenEstB.lte(new anchor.BN(50_000_000)));
+
+    // Verify position exists and numbers fit input parameters
+    const positionAddress = PDAUtil.getPosition(ctx.program.programId, positionMint).publicKey;
+    const position = await client.getPosition(positionAddress);
+    const positionData = position.getData();
+    assert.ok(positionData.liquidity.eq(quote.liquidityAmount));
+    assert.ok(positionData.tickLowerIndex === expectedTickLower);
+    assert.ok(positionData.tickUpperIndex === expectedTickUpper);
+    assert.ok(positionData.whirlpool.equals(poolInitInfo.whirlpoolPda.publicKey));
+
+    // Verify the deposited amounts match the quote
+    assert.equal(
+      await getTokenBalance(ctx.provider, userTokenAAccount),
+      new anchor.BN(mintedTokenAmount).sub(quote.tokenEstA).toString()
+    );
+    assert.equal(
+      await getTokenBalance(ctx.provider, userTokenBAccount),
+      new anchor.BN(mintedTokenAmount).sub(quote.tokenEstB).toString()
+    );
+  });
+
+  it("create a pool with its initial tick array", async () => {
+    const { configInitInfo, poolInitInfo } = await buildTestPoolParams(ctx, TickSpacing.Standard);
+    const initialPrice = new Decimal(100);
+
+    const { poolKey, tx } = await client.createPool(
+      configInitInfo.whirlpoolsConfigKeypair.publicKey,
+      poolInitInfo.tokenMintA,
+      poolInitInfo.tokenMintB,
+      TickSpacing.Standard,
+      initialPrice
+    );
+    assert.ok(poolKey.equals(poolInitInfo.whirlpoolPda.publicKey));
+    await tx.buildAndExecute();
+
+    const pool = await client.getPool(poolKey, true);
+    const poolData = pool.getData();
+    assert.ok(poolData.tokenMintA.equals(poolInitInfo.tokenMintA));
+    assert.ok(poolData.tokenMintB.equals(poolInitInfo.tokenMintB));
+    assert.equal(poolData.tickSpacing, TickSpacing.Standard);
+    assert.ok(
+      poolData.sqrtPrice.eq(
+        PriceMath.priceToSqrtPriceX64(
+          initialPrice,
+          pool.getTokenAInfo().decimals,
+          pool.getTokenBInfo().decimals
+        )
+      )
+    );
+
+    const tickArrayPda = PDAUtil.getTickArrayFromTickIndex(
+      poolData.tickCurrentIndex,
+      poolData.tickSpacing,
+      poolKey,
+      ctx.program.programId
+    );
+    assert.ok(await fetcher.getTickArray(tickArrayPda.publicKey, true));
+  });
+
+  it("create a pool rejects mints out of order", async () => {
+    const { configInitInfo, poolInitInfo } = await buildTestPoolParams(ctx, TickSpacing.Standard);
+
+    await assert.rejects(
+      client.createPool(
+        configInitInfo.whirlpoolsConfigKeypair.publicKey,
+        poolInitInfo.tokenMintB,
+        poolInitInfo.tokenMintA,
+        TickSpacing.Standard,
+        new Decimal(100)
+      ),
+      /InvalidTokenMintOrder/
+    );
+  });
 });
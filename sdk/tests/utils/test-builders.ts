@@ -3,8 +3,9 @@ import { AnchorProvider } from "@project-serum/anchor";
 import { ASSOCIATED_TOKEN_PROGRAM_ID, Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import Decimal from "decimal.js";
-import { createAndMintToAssociatedTokenAccount, createMint } from ".";
+import { createAndMintToAssociatedTokenAccount, createMint, TickSpacing } from ".";
 import {
+  buildWhirlpoolClient,
   increaseLiquidityQuoteByInputToken,
   InitConfigParams,
   InitFeeTierParams,
@@ -16,6 +17,7 @@ import {
   Whirlpool,
 } from "../../src";
 import { WhirlpoolContext } from "../../src/context";
+import { initTestPool } from "./init-utils";
 
 export interface TestWhirlpoolsConfigKeypairs {
   feeAuthorityKeypair: Keypair;
@@ -204,7 +206,8 @@ export async function initPosition(
   upperPrice: Decimal,
   inputTokenMint: PublicKey,
   inputTokenAmount: number,
-  sourceWallet?: Keypair
+  sourceWallet?: Keypair,
+  initTickArrays = false
 ) {
   const sourceWalletKey = sourceWallet ? sourceWallet.publicKey : ctx.wallet.publicKey;
   const tokenADecimal = pool.getTokenAInfo().decimals;
@@ -237,7 +240,8 @@ export async function initPosition(
     upperTick,
     quote,
     sourceWalletKey,
-    ctx.wallet.publicKey
+    ctx.wallet.publicKey,
+    initTickArrays
   );
 
   if (sourceWallet) {
@@ -251,3 +255,49 @@ export async function initPosition(
     positionAddress: PDAUtil.getPosition(ctx.program.programId, positionMint),
   };
 }
+
+/**
+ * Initialize a pool at price 100, mint 150 of each token to the wallet and open a position with
+ * 10 token A on each price range. The tick arrays of the positions are initialized on the way.
+ */
+export async function initTestPoolWithPositions(
+  ctx: WhirlpoolContext,
+  priceRanges: [number, number][] = [
+    [96, 101],
+    [90, 110],
+  ]
+) {
+  const { poolInitInfo } = await initTestPool(
+    ctx,
+    TickSpacing.Standard,
+    PriceMath.priceToSqrtPriceX64(new Decimal(100), 6, 6)
+  );
+  const pool = await buildWhirlpoolClient(ctx).getPool(poolInitInfo.whirlpoolPda.publicKey);
+
+  const mintedTokenAmount = 150_000_000;
+  const [userTokenAAccount, userTokenBAccount] = await mintTokensToTestAccount(
+    ctx.provider,
+    pool.getTokenAInfo().mint,
+    mintedTokenAmount,
+    pool.getTokenBInfo().mint,
+    mintedTokenAmount
+  );
+
+  const positions = [];
+  for (const [lowerPrice, upperPrice] of priceRanges) {
+    positions.push(
+      await initPosition(
+        ctx,
+        pool,
+        new Decimal(lowerPrice),
+        new Decimal(upperPrice),
+        poolInitInfo.tokenMintA,
+        10,
+        undefined,
+        true
+      )
+    );
+  }
+
+  return { poolInitInfo, pool, mintedTokenAmount, userTokenAAccount, userTokenBAccount, positions };
+}